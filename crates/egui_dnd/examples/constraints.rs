@@ -0,0 +1,54 @@
+use eframe::{egui, NativeOptions};
+use egui::{CentralPanel, Frame};
+use egui_dnd::dnd;
+
+pub fn main() -> eframe::Result<()> {
+    let mut stages = vec![
+        "Source", "Decode", "Resize", "Denoise", "Sharpen", "Encode", "Sink",
+    ];
+    let mut reason = None;
+
+    eframe::run_simple_native(
+        "DnD Constraints Example",
+        NativeOptions::default(),
+        move |ctx, _frame| {
+            CentralPanel::default().show(ctx, |ui| {
+                ui.label("The first and last stage are pinned, Decode has to stay before Encode");
+                let len = stages.len();
+                let decode = stages.iter().position(|s| *s == "Decode");
+                let encode = stages.iter().position(|s| *s == "Encode");
+
+                let response = dnd(ui, "pipeline")
+                    .with_axis_lock(true)
+                    .with_can_drop(|from, to| match (decode, encode) {
+                        (Some(decode), Some(encode)) if from == decode => to <= encode,
+                        (Some(decode), Some(encode)) if from == encode => to > decode,
+                        _ => true,
+                    })
+                    .show_vec(&mut stages, |ui, stage, handle, state| {
+                        let pinned = state.index == 0 || state.index == len - 1;
+                        handle.pinned(pinned).ui(ui, |ui| {
+                            Frame::none()
+                                .fill(ui.visuals().faint_bg_color)
+                                .inner_margin(8.0)
+                                .show(ui, |ui| {
+                                    ui.set_width(ui.available_width());
+                                    if pinned {
+                                        ui.label(format!("📌 {stage}"));
+                                    } else {
+                                        ui.label(*stage);
+                                    }
+                                });
+                        });
+                    });
+
+                if response.is_drag_finished() {
+                    reason = response.cancellation_reason();
+                }
+                if let Some(reason) = reason {
+                    ui.colored_label(ui.visuals().error_fg_color, reason);
+                }
+            });
+        },
+    )
+}
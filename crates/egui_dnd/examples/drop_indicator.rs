@@ -0,0 +1,55 @@
+use eframe::{egui, NativeOptions};
+use egui::{CentralPanel, Frame};
+use egui_dnd::{dnd, DropIndicator, Placeholder, PreviewStyle};
+
+pub fn main() -> eframe::Result<()> {
+    let mut items: Vec<String> = (1..=8).map(|i| format!("Item {i}")).collect();
+    let mut line = true;
+    let mut placeholder = Placeholder::Ghost(0.3);
+    let mut lifted = true;
+
+    eframe::run_simple_native(
+        "DnD Drop Indicator Example",
+        NativeOptions::default(),
+        move |ctx, _frame| {
+            CentralPanel::default().show(ctx, |ui| {
+                ui.checkbox(
+                    &mut line,
+                    "Insertion line (items don't move while dragging)",
+                );
+                ui.horizontal(|ui| {
+                    ui.label("Placeholder:");
+                    ui.selectable_value(&mut placeholder, Placeholder::Empty, "Empty");
+                    ui.selectable_value(&mut placeholder, Placeholder::Ghost(0.3), "Ghost");
+                    ui.selectable_value(&mut placeholder, Placeholder::Outline, "Outline");
+                });
+                ui.checkbox(&mut lifted, "Lifted preview (scale and shadow)");
+                ui.separator();
+
+                dnd(ui, "drop_indicator")
+                    .with_drop_indicator(if line {
+                        DropIndicator::Line
+                    } else {
+                        DropIndicator::Gap
+                    })
+                    .with_placeholder(placeholder)
+                    .with_preview_style(if lifted {
+                        PreviewStyle::lifted()
+                    } else {
+                        PreviewStyle::default()
+                    })
+                    .show_vec(&mut items, |ui, item, handle, _state| {
+                        handle.ui(ui, |ui| {
+                            Frame::none()
+                                .fill(ui.visuals().faint_bg_color)
+                                .inner_margin(8.0)
+                                .show(ui, |ui| {
+                                    ui.set_width(ui.available_width());
+                                    ui.label(item.as_str());
+                                });
+                        });
+                    });
+            });
+        },
+    )
+}
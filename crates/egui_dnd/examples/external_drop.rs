@@ -0,0 +1,77 @@
+use eframe::{egui, NativeOptions};
+use egui::{CentralPanel, Frame, Ui};
+use egui_dnd::{dnd, Handle};
+
+fn item_ui(ui: &mut Ui, item: &'static str, handle: Handle) {
+    // The payload can be dropped on the trash can or the archive
+    handle.payload(item).ui(ui, |ui| {
+        Frame::none()
+            .fill(ui.visuals().faint_bg_color)
+            .inner_margin(8.0)
+            .show(ui, |ui| {
+                ui.set_width(ui.available_width());
+                ui.label(item);
+            });
+    });
+}
+
+pub fn main() -> eframe::Result<()> {
+    let mut inbox = vec!["invoice", "newsletter", "meeting notes", "vacation photos"];
+    let mut archive: Vec<&'static str> = vec!["old report"];
+
+    eframe::run_simple_native(
+        "DnD External Drop Example",
+        NativeOptions::default(),
+        move |ctx, _frame| {
+            CentralPanel::default().show(ctx, |ui| {
+                ui.columns(2, |uis| {
+                    // The archive is shown before the inbox, so the inbox notices in the same
+                    // frame that the archive took the payload
+                    uis[0].heading("Archive");
+                    Frame::group(uis[0].style()).show(&mut uis[0], |ui| {
+                        ui.set_min_height(200.0);
+                        let response = dnd(ui, "archive")
+                            .with_external_drop::<&'static str>()
+                            .show_vec(&mut archive, |ui, item, handle, _state| {
+                                item_ui(ui, item, handle);
+                            });
+
+                        if let Some((idx, item)) = response.external_payload::<&'static str>() {
+                            archive.insert(idx, *item);
+                        }
+                        if let Some(idx) = response.dropped_externally() {
+                            archive.remove(idx);
+                        }
+                    });
+
+                    uis[1].heading("Inbox");
+                    Frame::group(uis[1].style()).show(&mut uis[1], |ui| {
+                        ui.set_min_height(200.0);
+                        let response =
+                            dnd(ui, "inbox").show_vec(&mut inbox, |ui, item, handle, _state| {
+                                item_ui(ui, item, handle);
+                            });
+
+                        if let Some(idx) = response.dropped_externally() {
+                            inbox.remove(idx);
+                        }
+                    });
+                });
+
+                ui.add_space(16.0);
+
+                let (_, payload) = ui.dnd_drop_zone::<&'static str, ()>(
+                    Frame::group(ui.style()).inner_margin(16.0),
+                    |ui| {
+                        ui.label("🗑 Drop here to delete");
+                    },
+                );
+                if payload.is_some() {
+                    // The trash can is shown after the lists, so we need to tell them that
+                    // the item was dropped
+                    egui_dnd::notify_external_drop(ui.ctx());
+                }
+            });
+        },
+    )
+}
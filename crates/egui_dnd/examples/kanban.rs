@@ -0,0 +1,66 @@
+use eframe::{egui, NativeOptions};
+use egui::{CentralPanel, Frame, Id};
+use egui_dnd::dnd;
+
+struct Column {
+    name: &'static str,
+    items: Vec<&'static str>,
+}
+
+pub fn main() -> eframe::Result<()> {
+    let mut columns = [
+        Column {
+            name: "Todo",
+            items: vec!["write docs", "fix bug", "release"],
+        },
+        Column {
+            name: "Doing",
+            items: vec!["add feature"],
+        },
+        Column {
+            name: "Done",
+            items: vec![],
+        },
+    ];
+
+    eframe::run_simple_native(
+        "DnD Kanban Example",
+        NativeOptions::default(),
+        move |ctx, _frame| {
+            CentralPanel::default().show(ctx, |ui| {
+                let mut cross_list_update = None;
+
+                ui.columns(columns.len(), |uis| {
+                    for (ui, column) in uis.iter_mut().zip(columns.iter_mut()) {
+                        ui.heading(column.name);
+                        Frame::group(ui.style()).show(ui, |ui| {
+                            ui.set_min_height(200.0);
+                            let response = dnd(ui, column.name).with_group("kanban").show_vec(
+                                &mut column.items,
+                                |ui, item, handle, _state| {
+                                    handle.ui(ui, |ui| {
+                                        Frame::none()
+                                            .fill(ui.visuals().faint_bg_color)
+                                            .inner_margin(8.0)
+                                            .show(ui, |ui| {
+                                                ui.set_width(ui.available_width());
+                                                ui.label(*item);
+                                            });
+                                    });
+                                },
+                            );
+
+                            if let Some(update) = response.cross_list_update() {
+                                cross_list_update = Some(update);
+                            }
+                        });
+                    }
+                });
+
+                if let Some(update) = cross_list_update {
+                    update.apply_lists(&mut columns, |c| Id::new(c.name), |c| &mut c.items);
+                }
+            });
+        },
+    )
+}
@@ -0,0 +1,37 @@
+use eframe::{egui, NativeOptions};
+use egui::CentralPanel;
+use egui_dnd::dnd;
+
+pub fn main() -> eframe::Result<()> {
+    let mut items = vec!["alfred", "bernhard", "christian", "david", "emil"];
+
+    eframe::run_simple_native(
+        "DnD Keyboard Example",
+        NativeOptions::default(),
+        move |ctx, _frame| {
+            CentralPanel::default().show(ctx, |ui| {
+                ui.label("Focus a handle with tab, press space to pick up the item,");
+                ui.label("move it with the arrow keys, space to drop and escape to cancel.");
+
+                let response = dnd(ui, "dnd_keyboard")
+                    .with_keyboard_reordering(true)
+                    .show_vec(&mut items, |ui, item, handle, state| {
+                        ui.horizontal(|ui| {
+                            handle.ui(ui, |ui| {
+                                if state.dragged {
+                                    ui.label("moving");
+                                } else {
+                                    ui.label("drag");
+                                }
+                            });
+                            ui.label(*item);
+                        });
+                    });
+
+                if let Some(reason) = response.cancellation_reason() {
+                    println!("Cancelled: {reason}");
+                }
+            });
+        },
+    )
+}
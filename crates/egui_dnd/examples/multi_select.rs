@@ -0,0 +1,38 @@
+use eframe::{egui, NativeOptions};
+use egui::{CentralPanel, Frame};
+use egui_dnd::dnd;
+
+pub fn main() -> eframe::Result<()> {
+    let mut items: Vec<String> = (1..=10).map(|i| format!("Track {i}")).collect();
+
+    eframe::run_simple_native(
+        "DnD Multi Select Example",
+        NativeOptions::default(),
+        move |ctx, _frame| {
+            CentralPanel::default().show(ctx, |ui| {
+                ui.label(
+                    "Click to select, Ctrl / Cmd-click to toggle, Shift-click to select a range.",
+                );
+                ui.label("Drag a selected item to move all selected items.");
+
+                let response = dnd(ui, "dnd_multi_select")
+                    .with_multi_select(true)
+                    .show_vec(&mut items, |ui, item, handle, state| {
+                        handle.ui(ui, |ui| {
+                            let fill = if state.selected {
+                                ui.visuals().selection.bg_fill
+                            } else {
+                                ui.visuals().faint_bg_color
+                            };
+                            Frame::none().fill(fill).inner_margin(4.0).show(ui, |ui| {
+                                ui.set_width(200.0);
+                                ui.label(item.as_str());
+                            });
+                        });
+                    });
+
+                ui.label(format!("{} selected", response.selected_ids().len()));
+            });
+        },
+    )
+}
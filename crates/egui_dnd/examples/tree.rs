@@ -0,0 +1,72 @@
+use eframe::{egui, NativeOptions};
+use egui::{CentralPanel, Id};
+use egui_dnd::{dnd, DragDropItem};
+
+struct Node {
+    name: &'static str,
+    children: Vec<Node>,
+}
+
+impl DragDropItem for &Node {
+    fn id(&self) -> Id {
+        Id::new(self.name)
+    }
+}
+
+fn node(name: &'static str, children: Vec<Node>) -> Node {
+    Node { name, children }
+}
+
+/// Flatten the tree to the visible rows, with their depth
+fn flatten<'a>(nodes: &'a [Node], depth: usize, rows: &mut Vec<(&'a Node, usize)>) {
+    for node in nodes {
+        rows.push((node, depth));
+        flatten(&node.children, depth + 1, rows);
+    }
+}
+
+pub fn main() -> eframe::Result<()> {
+    let mut tree = vec![
+        node(
+            "src",
+            vec![
+                node("lib.rs", vec![]),
+                node("utils", vec![node("mod.rs", vec![])]),
+            ],
+        ),
+        node("examples", vec![node("tree.rs", vec![])]),
+        node("Cargo.toml", vec![]),
+        node("README.md", vec![]),
+    ];
+
+    eframe::run_simple_native(
+        "DnD Tree Example",
+        NativeOptions::default(),
+        move |ctx, _frame| {
+            CentralPanel::default().show(ctx, |ui| {
+                ui.label(
+                    "Drop on an item to move it into the item, drop between items to reorder.",
+                );
+                ui.label("Drag left or right to change the depth.");
+
+                let mut rows = Vec::new();
+                flatten(&tree, 0, &mut rows);
+
+                let response =
+                    dnd(ui, "dnd_tree").show_tree(rows.into_iter(), |ui, node, handle, _state| {
+                        handle.ui(ui, |ui| {
+                            if node.children.is_empty() {
+                                ui.label(format!("📄 {}", node.name));
+                            } else {
+                                ui.label(format!("📁 {}", node.name));
+                            }
+                        });
+                    });
+
+                if let Some(update) = response.tree_update() {
+                    update.apply(&mut tree, |node| &mut node.children);
+                }
+            });
+        },
+    )
+}
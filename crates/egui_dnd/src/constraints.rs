@@ -0,0 +1,122 @@
+use egui::{pos2, CursorIcon, Id, Layout, Pos2, Stroke, Ui};
+
+use crate::state::{DragDetectionState, DragDropResponse, DragDropUi};
+
+/// The reason reported by [`DragDropResponse::cancellation_reason`] if the item was dropped at a
+/// position rejected by [`crate::Dnd::with_can_drop`]
+pub(crate) const REJECTED_REASON: &str = "The item can't be dropped at this position";
+
+/// An item other items can't be moved past, see [`crate::Handle::pinned`] and [`crate::Handle::barrier`]
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Barrier {
+    pub idx: usize,
+    pub id: Id,
+    pub pos: Pos2,
+}
+
+impl DragDropUi {
+    /// Where the dragged item is drawn for the pointer position, see [`crate::Dnd::with_axis_lock`]
+    pub(crate) fn dragged_item_pos(&self, pointer_pos: Pos2, layout: Layout) -> Option<Pos2> {
+        let DragDetectionState::Dragging { offset, origin, .. } = &self.detection_state else {
+            return None;
+        };
+        let pos = pointer_pos + *offset;
+        Some(if !self.axis_lock {
+            pos
+        } else if layout.is_horizontal() {
+            pos2(pos.x, origin.y)
+        } else {
+            pos2(origin.x, pos.y)
+        })
+    }
+
+    /// Move the target of the dragged item back next to the first barrier it would pass
+    pub(crate) fn clamp_to_barriers(&mut self) {
+        let DragDetectionState::Dragging {
+            source_idx,
+            closest_item,
+            hovering_idx,
+            hovering_last_item,
+            ..
+        } = &mut self.detection_state
+        else {
+            return;
+        };
+
+        let to = if *hovering_last_item {
+            *hovering_idx + 1
+        } else {
+            *hovering_idx
+        };
+
+        if to > *source_idx + 1 {
+            // The items between the source and the target move up
+            let barrier = self
+                .barriers
+                .iter()
+                .filter(|b| b.idx > *source_idx && b.idx < to)
+                .min_by_key(|b| b.idx);
+            if let Some(barrier) = barrier {
+                // Insert before the barrier
+                *closest_item = (barrier.id, barrier.pos);
+                *hovering_idx = barrier.idx;
+                *hovering_last_item = false;
+            }
+        } else if to < *source_idx {
+            // The items between the target and the source move down
+            let barrier = self
+                .barriers
+                .iter()
+                .filter(|b| b.idx >= to && b.idx < *source_idx)
+                .max_by_key(|b| b.idx);
+            if let Some(barrier) = barrier {
+                // Insert after the barrier
+                *closest_item = (barrier.id, barrier.pos);
+                *hovering_idx = barrier.idx;
+                *hovering_last_item = true;
+            }
+        }
+    }
+
+    /// Remove the update from the response if `can_drop` rejects it and show that the item
+    /// can't be dropped here, see [`crate::Dnd::with_can_drop`]
+    pub(crate) fn check_can_drop(
+        &self,
+        ui: &Ui,
+        response: &mut DragDropResponse,
+        can_drop: &dyn Fn(usize, usize) -> bool,
+    ) {
+        // Moving the item back after the drag was cancelled is always allowed
+        if response.cancellation_reason.is_some() {
+            return;
+        }
+
+        let allowed = |from: usize, to: usize| from == to || from + 1 == to || can_drop(from, to);
+        let rejected = if let Some(update) = &response.update {
+            !allowed(update.from, update.to)
+        } else if let Some(update) = &response.multi_update {
+            !update.from.iter().all(|from| allowed(*from, update.to))
+        } else {
+            false
+        };
+        if !rejected {
+            return;
+        }
+
+        response.update = None;
+        response.multi_update = None;
+        response.has_changed = false;
+        if response.finished {
+            response.cancellation_reason = Some(REJECTED_REASON);
+        } else {
+            ui.output_mut(|o| o.cursor_icon = CursorIcon::NotAllowed);
+            if let Some(rect) = self.gap_rect {
+                ui.painter().rect_stroke(
+                    rect,
+                    ui.visuals().widgets.noninteractive.rounding,
+                    Stroke::new(2.0, ui.visuals().error_fg_color),
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,106 @@
+use std::any::Any;
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+
+use egui::{Context, DragAndDrop, Id, Pos2};
+
+/// Accepts egui [`DragAndDrop`] payloads of a type, see [`crate::Dnd::with_external_drop`]
+#[derive(Clone, Copy)]
+pub(crate) struct ExternalDropZone {
+    has_payload: fn(&Context) -> bool,
+    take_payload: fn(&Context) -> Option<Arc<dyn Any + Send + Sync>>,
+}
+
+impl Debug for ExternalDropZone {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ExternalDropZone").finish_non_exhaustive()
+    }
+}
+
+impl ExternalDropZone {
+    pub fn new<P: Any + Send + Sync>() -> Self {
+        Self {
+            has_payload: DragAndDrop::has_payload_of_type::<P>,
+            take_payload: |ctx| {
+                DragAndDrop::take_payload::<P>(ctx).map(|p| p as Arc<dyn Any + Send + Sync>)
+            },
+        }
+    }
+
+    /// Is a payload of the accepted type being dragged?
+    pub fn has_payload(self, ctx: &Context) -> bool {
+        (self.has_payload)(ctx)
+    }
+
+    /// Take the payload and tell the list it was dragged from that it was dropped
+    pub fn take_payload(self, ctx: &Context) -> Option<Arc<dyn Any + Send + Sync>> {
+        let payload = (self.take_payload)(ctx);
+        if payload.is_some() {
+            notify_external_drop(ctx);
+        }
+        payload
+    }
+}
+
+/// Where an external payload hovering the list would be inserted
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ExternalHover {
+    /// The item the payload would be inserted before
+    pub target_item: Option<(Id, Pos2)>,
+    pub target_idx: usize,
+    /// True if the payload would be inserted after the last item
+    pub after_last: bool,
+}
+
+impl ExternalHover {
+    /// Calculate where the payload would be inserted from the first item after the gap and the
+    /// last item of the list
+    pub fn from_iterator(
+        insert_before: Option<(usize, Id, Pos2)>,
+        last_item: Option<(usize, Id, Pos2)>,
+    ) -> Self {
+        match (insert_before, last_item) {
+            (Some((idx, id, pos)), _) => Self {
+                target_item: Some((id, pos)),
+                target_idx: idx,
+                after_last: false,
+            },
+            (None, Some((idx, id, pos))) => Self {
+                target_item: Some((id, pos)),
+                target_idx: idx + 1,
+                after_last: true,
+            },
+            (None, None) => Self::default(),
+        }
+    }
+}
+
+fn accepted_id() -> Id {
+    Id::new("egui_dnd_external_drop_accepted")
+}
+
+/// Tell the [`crate::Dnd`] list an item was dragged from that your drop zone accepted its payload,
+/// so the list can report it via [`crate::DragDropResponse::dropped_externally`].
+///
+/// This is only needed if the drop zone is shown **after** the list in the frame the item is
+/// dropped. Otherwise the list notices that the payload was taken.
+/// ```rust no_run
+/// # egui::__run_test_ui(|ui| {
+/// let (_, payload) = ui.dnd_drop_zone::<String, ()>(egui::Frame::default(), |ui| {
+///     ui.label("Trash");
+/// });
+/// if let Some(item) = payload {
+///     egui_dnd::notify_external_drop(ui.ctx());
+///     println!("Deleted {item}");
+/// }
+/// # });
+/// ```
+pub fn notify_external_drop(ctx: &Context) {
+    let pass = ctx.cumulative_pass_nr();
+    ctx.data_mut(|data| data.insert_temp(accepted_id(), pass));
+}
+
+/// Was a payload accepted in the given pass?
+pub(crate) fn was_accepted_in(ctx: &Context, pass: u64) -> bool {
+    ctx.data(|data| data.get_temp::<u64>(accepted_id())) == Some(pass)
+}
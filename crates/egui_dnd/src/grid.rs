@@ -0,0 +1,52 @@
+use egui::{Id, Pos2, Rect};
+
+/// A cell of a wrapping layout, e.g. when using [`crate::Dnd::show_sized`] in a
+/// `horizontal_wrapped` ui
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct GridCell {
+    /// The item shown in the cell, or `None` for the gap of the dragged item
+    pub item: Option<(usize, Id, Pos2)>,
+    pub rect: Rect,
+}
+
+/// Find the item the dragged item would be inserted before, by first finding the row `pos` is in
+/// and then the cell in that row. If `pos` is in the left half of a cell, the item is inserted
+/// before it, otherwise after it.
+///
+/// Returns `None` if the item would be inserted after the last item.
+/// Since the gap is a cell too, the target doesn't change while hovering the gap, so the items
+/// don't jump back and forth.
+pub(crate) fn grid_insert_before(cells: &[GridCell], pos: Pos2) -> Option<(usize, Id, Pos2)> {
+    let rows = rows(cells);
+
+    let distance = |rect: &Rect| (rect.y_range().clamp(pos.y) - pos.y).abs();
+    let row = rows
+        .iter()
+        .min_by(|(_, a), (_, b)| distance(a).total_cmp(&distance(b)))?
+        .0
+        .clone();
+
+    let insert_at = row
+        .clone()
+        .find(|i| pos.x < cells[*i].rect.center().x)
+        .unwrap_or(row.end);
+
+    // Inserting before the gap or after it doesn't change anything,
+    // so this is the first item at or after the insert position
+    cells[insert_at..].iter().find_map(|cell| cell.item)
+}
+
+/// Group the cells into rows. A new row starts when a cell is left of the previous cell.
+fn rows(cells: &[GridCell]) -> Vec<(std::ops::Range<usize>, Rect)> {
+    let mut rows: Vec<(std::ops::Range<usize>, Rect)> = Vec::new();
+    for (i, cell) in cells.iter().enumerate() {
+        match rows.last_mut() {
+            Some((range, rect)) if cell.rect.left() >= cells[range.end - 1].rect.left() => {
+                range.end = i + 1;
+                *rect = rect.union(cell.rect);
+            }
+            _ => rows.push((i..i + 1, cell.rect)),
+        }
+    }
+    rows
+}
@@ -0,0 +1,117 @@
+use std::collections::HashMap;
+
+use egui::{Context, Id, Pos2, Rect};
+
+use crate::state::DragDetectionState;
+
+/// Configures a [`crate::Dnd`] to be part of a drop group
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct DndGroup {
+    /// Id of the group, shared between all lists in the group
+    pub id: Id,
+    /// Id of the list, as passed by the user (`Id::new(id_source)`)
+    pub list_id: Id,
+}
+
+/// A drag that is ongoing in one of the lists of a group
+#[derive(Debug, Clone)]
+pub(crate) struct GroupDrag {
+    pub source_list: Id,
+    pub item_id: Id,
+    pub source_idx: usize,
+    /// The rect of the floating item
+    pub dragged_item_rect: Rect,
+    /// The list (other than the source list) that the pointer is hovering
+    pub target_list: Option<Id>,
+    /// The item in the target list the dragged item would be inserted before
+    pub target_item: Option<(Id, Pos2)>,
+    /// Index where the item would be inserted in the target list
+    pub target_idx: usize,
+    /// True if the item would be inserted after the last item of the target list
+    pub target_after_last: bool,
+}
+
+/// Shared state of all lists in a group, stored in egui memory
+#[derive(Debug, Clone, Default)]
+pub(crate) struct GroupState {
+    /// The rects of all lists in the group, from the last frame they were shown
+    pub list_rects: HashMap<Id, Rect>,
+    pub drag: Option<GroupDrag>,
+}
+
+impl GroupState {
+    fn memory_id(group: Id) -> Id {
+        group.with("dnd_group")
+    }
+
+    pub fn load(ctx: &Context, group: Id) -> Self {
+        ctx.data_mut(|data| {
+            data.get_temp::<Self>(Self::memory_id(group))
+                .unwrap_or_default()
+        })
+    }
+
+    pub fn store(self, ctx: &Context, group: Id) {
+        ctx.data_mut(|data| data.insert_temp(Self::memory_id(group), self));
+    }
+
+    /// Called by every list in the group before it shows its items.
+    /// Publishes the drag of the list (if any) and checks whether the list is hovered by a drag
+    /// from another list.
+    pub fn begin_list(
+        &mut self,
+        list: Id,
+        detection_state: &DragDetectionState,
+        dragged_item_rect: Option<Rect>,
+        pointer_pos: Option<Pos2>,
+    ) {
+        if let (DragDetectionState::Dragging { id, source_idx, .. }, Some(rect)) =
+            (detection_state, dragged_item_rect)
+        {
+            match &mut self.drag {
+                Some(drag) if drag.source_list == list && drag.item_id == *id => {
+                    drag.source_idx = *source_idx;
+                    drag.dragged_item_rect = rect;
+                }
+                _ => {
+                    self.drag = Some(GroupDrag {
+                        source_list: list,
+                        item_id: *id,
+                        source_idx: *source_idx,
+                        dragged_item_rect: rect,
+                        target_list: None,
+                        target_item: None,
+                        target_idx: 0,
+                        target_after_last: false,
+                    });
+                }
+            }
+        } else if self.drag.as_ref().is_some_and(|d| d.source_list == list) {
+            self.drag = None;
+        }
+
+        if let Some(drag) = &mut self.drag {
+            if drag.source_list != list {
+                let hovered = pointer_pos
+                    .is_some_and(|pos| self.list_rects.get(&list).is_some_and(|r| r.contains(pos)));
+                if hovered {
+                    if drag.target_list != Some(list) {
+                        drag.target_list = Some(list);
+                        drag.target_item = None;
+                        drag.target_idx = 0;
+                        drag.target_after_last = false;
+                    }
+                } else if drag.target_list == Some(list) {
+                    drag.target_list = None;
+                }
+            }
+        }
+    }
+
+    /// The list the dragged item is hovering, if it isn't the source list
+    pub fn foreign_target(&self, list: Id) -> Option<&GroupDrag> {
+        self.drag
+            .as_ref()
+            .filter(|drag| drag.source_list != list && drag.target_list == Some(list))
+    }
+}
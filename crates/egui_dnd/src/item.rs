@@ -1,10 +1,16 @@
+use egui::emath::TSTransform;
 use egui::{
-    CursorIcon, Id, InnerResponse, LayerId, Layout, Order, Pos2, Rect, Sense, Ui, UiBuilder, Vec2,
+    lerp, Align2, CursorIcon, FontId, Id, InnerResponse, LayerId, Layout, Order, Pos2, Rect, Sense,
+    Shape, Ui, UiBuilder, Vec2,
 };
 use egui_animation::animate_position;
 
+use crate::keyboard::handle_keyboard;
 use crate::state::DragDetectionState;
-use crate::{DragDropUi, Handle, ItemState};
+use crate::{DragDropUi, Handle, ItemState, Placeholder};
+
+/// How far away from the pointer the dragged item is laid out, see `draw_floating_at_position`
+const HIDDEN_OFFSET: f32 = 100_000.0;
 
 pub struct Item<'a> {
     id: Id,
@@ -58,14 +64,9 @@ impl<'a> Item<'a> {
         let id = self.id;
         let index = self.state.index;
         let last_pointer_pos = self.dnd_state.detection_state.last_pointer_pos();
-        if let DragDetectionState::Dragging {
-            id: dragging_id,
-            offset,
-            ..
-        } = &mut self.dnd_state.detection_state
-        {
+        if let Some(dragging_id) = self.dnd_state.detection_state.dragged_item() {
             // Draw the item item in it's original position in the first frame to avoid flickering
-            if id == *dragging_id {
+            if id == dragging_id {
                 ui.output_mut(|o| o.cursor_icon = CursorIcon::Grabbing);
 
                 let _layer_id = LayerId::new(Order::Tooltip, id);
@@ -75,7 +76,10 @@ impl<'a> Item<'a> {
                     .pointer_hover_pos()
                     .or(last_pointer_pos)
                     .unwrap_or_else(|| ui.next_widget_position());
-                let position = pointer_pos + *offset;
+                let position = self
+                    .dnd_state
+                    .dragged_item_pos(pointer_pos, *ui.layout())
+                    .unwrap_or(pointer_pos);
 
                 // We animate so the animated position is updated, even though we don't use it here.
                 animate_position(
@@ -153,7 +157,48 @@ impl<'a> Item<'a> {
             }
         }
 
+        if self.dnd_state.detection_state.keyboard_item() == Some(id) {
+            // The item is shown floating above the space where it will be dropped
+            let end_pos = self
+                .dnd_state
+                .keyboard_gap_pos
+                .unwrap_or_else(|| ui.next_widget_position());
+            let position = animate_position(
+                ui,
+                id,
+                end_pos,
+                self.dnd_state.swap_animation_time,
+                self.easing,
+                false,
+            );
+
+            let InnerResponse { inner: rect, .. } = Self::draw_floating_at_position(
+                self.state,
+                self.dnd_state,
+                ui,
+                id,
+                position,
+                hovering_over_any_handle,
+                size,
+                *ui.layout(),
+                drag_body,
+            );
+
+            if let DragDetectionState::KeyboardDragging {
+                dragged_item_size, ..
+            } = &mut self.dnd_state.detection_state
+            {
+                *dragged_item_size = rect.size();
+            }
+
+            let rect = Rect::from_min_size(end_pos, rect.size());
+            handle_keyboard(self.dnd_state, ui, id, index, rect);
+
+            return ItemResponse(rect);
+        }
+
         let was_dragging = self.dnd_state.detection_state.is_dragging();
+        let animate = was_dragging || self.dnd_state.detection_state.is_keyboard_dragging();
 
         let rect = if let Some(size) = size {
             // We need to do it like this because in some layouts
@@ -170,7 +215,7 @@ impl<'a> Item<'a> {
                 true,
             );
 
-            let position = if self.dnd_state.detection_state.is_dragging() {
+            let position = if animate || self.dnd_state.detection_state.is_dragging() {
                 animated_position
             } else {
                 rect.min
@@ -208,7 +253,7 @@ impl<'a> Item<'a> {
                 true,
             );
 
-            let position = if self.dnd_state.detection_state.is_dragging() {
+            let position = if animate || self.dnd_state.detection_state.is_dragging() {
                 animated_position
             } else {
                 position
@@ -262,25 +307,135 @@ impl<'a> Item<'a> {
         layout: Layout,
         body: impl FnOnce(&mut Ui, Handle, ItemState),
     ) -> InnerResponse<Rect> {
-        egui::Area::new(Id::new("draggable_item"))
+        // While an item with a payload is dragged, it is laid out far away from the pointer and only
+        // its shapes are moved to `pos`, so its widgets don't cover drop zones below the pointer
+        // (see `Handle::payload`). The payload is set by the handle, so we know it from the last frame.
+        let hidden_pos = if dnd_state.detection_state.is_dragging() && dnd_state.has_payload {
+            pos - Vec2::new(0.0, HIDDEN_OFFSET)
+        } else {
+            pos
+        };
+        let delta = pos - hidden_pos;
+
+        // Blend the preview style in when the item is picked up and out when it is dropped
+        let lifted = dnd_state.detection_state.is_dragging()
+            || dnd_state.detection_state.is_keyboard_dragging();
+        let t = ui.ctx().animate_bool_with_time(
+            id.with("dnd_preview_style"),
+            lifted,
+            dnd_state.swap_animation_time,
+        );
+        let style = dnd_state.preview_style;
+        let scale = lerp(1.0..=style.scale, t);
+        let capture_ghost = dnd_state.detection_state.is_dragging()
+            && matches!(dnd_state.placeholder, Placeholder::Ghost(_));
+
+        let mut response = egui::Area::new(Id::new("draggable_item"))
             .interactable(false)
-            .fixed_pos(pos)
+            .fixed_pos(hidden_pos)
             .order(Order::Tooltip)
             .constrain(false)
             .show(ui.ctx(), |ui| {
-                ui.with_layout(layout, |ui| {
-                    if let Some(size) = size.or(dnd_state.detection_state.dragged_item_size()) {
-                        ui.set_max_size(size);
-                    }
-                    body(
-                        ui,
-                        Handle::new(id, state.index, dnd_state, hovering_over_any_handle, pos),
-                        state,
+                ui.set_clip_rect(ui.ctx().screen_rect().translate(-delta));
+                ui.multiply_opacity(lerp(1.0..=style.opacity, t));
+                let shadow_idx = ui.painter().add(Shape::Noop);
+
+                let count = dnd_state.detection_state.dragged_count();
+                let item_size = size.or(dnd_state.detection_state.dragged_item_size());
+
+                if let (true, Some(item_size)) = (count > 1, item_size) {
+                    Self::draw_stack(ui, Rect::from_min_size(hidden_pos, item_size), count);
+                }
+
+                let rect = ui
+                    .with_layout(layout, |ui| {
+                        if let Some(size) = item_size {
+                            ui.set_max_size(size);
+                        }
+                        body(
+                            ui,
+                            Handle::new(id, state.index, dnd_state, hovering_over_any_handle, pos),
+                            state,
+                        );
+                    })
+                    .response
+                    .rect;
+
+                if count > 1 {
+                    Self::draw_count_badge(ui, rect, count);
+                }
+
+                if capture_ghost {
+                    let shapes = ui.ctx().graphics(|graphics| {
+                        graphics
+                            .get(ui.layer_id())
+                            .map(|list| {
+                                list.all_entries().skip(shadow_idx.0 + 1).cloned().collect()
+                            })
+                            .unwrap_or_default()
+                    });
+                    dnd_state.preview_shapes = Some((rect, shapes));
+                }
+
+                if let Some(mut shadow) = style.shadow {
+                    shadow.color = shadow.color.gamma_multiply(t);
+                    ui.painter().set(
+                        shadow_idx,
+                        shadow.as_shape(rect, ui.visuals().widgets.active.rounding),
                     );
-                })
-                .response
-                .rect
-            })
+                }
+
+                rect
+            });
+
+        if delta != Vec2::ZERO || scale != 1.0 {
+            let mut transform = TSTransform::from_translation(delta);
+            if scale != 1.0 {
+                let center = response.inner.translate(delta).center().to_vec2();
+                transform = TSTransform::from_translation(center)
+                    * TSTransform::from_scaling(scale)
+                    * TSTransform::from_translation(-center)
+                    * transform;
+            }
+            ui.ctx()
+                .transform_layer_shapes(response.response.layer_id, transform);
+            response.inner = response.inner.translate(delta);
+        }
+        response
+    }
+
+    /// Draw some cards behind the dragged item, to show that multiple items are dragged
+    fn draw_stack(ui: &Ui, rect: Rect, count: usize) {
+        let visuals = ui.visuals();
+        for i in (1..count.min(3)).rev() {
+            #[allow(clippy::cast_precision_loss)]
+            let offset = Vec2::splat(4.0 * i as f32);
+            ui.painter().rect(
+                rect.translate(offset),
+                visuals.widgets.noninteractive.rounding,
+                visuals.window_fill,
+                visuals.window_stroke,
+            );
+        }
+    }
+
+    /// Draw the number of dragged items at the top right corner of the dragged item
+    fn draw_count_badge(ui: &Ui, rect: Rect, count: usize) {
+        let visuals = ui.visuals();
+        let galley = ui.painter().layout_no_wrap(
+            count.to_string(),
+            FontId::proportional(11.0),
+            visuals.selection.stroke.color,
+        );
+        let radius = (galley.size().max_elem() / 2.0 + 3.0).max(8.0);
+        let center = rect.right_top();
+        ui.painter()
+            .circle_filled(center, radius, visuals.selection.bg_fill);
+        ui.painter().galley(
+            Align2::CENTER_CENTER.anchor_size(center, galley.size()).min,
+            galley,
+            visuals.selection.stroke.color,
+        );
     }
 }
 
@@ -1,6 +1,8 @@
+use crate::grid::{grid_insert_before, GridCell};
 use crate::item::{Item, ItemResponse};
 use crate::state::DragDetectionState;
-use crate::{DragDropUi, ItemState};
+use crate::tree::TreeRow;
+use crate::{DragDropUi, DropIndicator, ItemState, Placeholder, PreviewStyle};
 use egui::{Id, Layout, Pos2, Rect, Ui, UiBuilder, Vec2};
 
 /// Calculates some information that is later used to detect in which index the dragged item should be placed.
@@ -23,6 +25,42 @@ pub struct ItemIterator<'a> {
 
     #[allow(clippy::type_complexity)]
     pub(crate) closest_item: Option<(f32, Option<(usize, Id, Pos2)>)>,
+
+    /// True if an item of another list in the same group is dragged over this list
+    foreign_drag: bool,
+    /// The first item that is after the foreign dragged item
+    pub(crate) foreign_insert: Option<(usize, Id, Pos2)>,
+    /// The union of all item (and space) rects
+    pub(crate) items_rect: Rect,
+
+    /// The ids of all items in order, used for shift-click selection
+    pub(crate) item_ids: Vec<Id>,
+    /// The indices of all dragged items, if multiple selected items are dragged
+    pub(crate) dragged_indices: Vec<usize>,
+
+    /// The item picked up with the keyboard, the index it will be dropped at and its size
+    keyboard: Option<(Id, usize, Vec2)>,
+    /// Number of shown items, excluding the item picked up with the keyboard
+    keyboard_count: usize,
+    /// The space where the item picked up with the keyboard will be dropped
+    pub(crate) keyboard_gap: Option<Rect>,
+    /// Current index of the item picked up with the keyboard
+    pub(crate) keyboard_source: Option<usize>,
+    /// The rect of the item a keyboard command was sent from
+    pub(crate) keyboard_action_rect: Option<Rect>,
+
+    /// The rows shown by [`crate::Dnd::show_tree`], if in tree mode
+    pub(crate) tree_rows: Option<Vec<TreeRow>>,
+    tree_depth: usize,
+
+    /// Where the dragged item would be inserted for [`DropIndicator::Line`]: the target index and
+    /// the rects of the items before and after it
+    pub(crate) line_target: Option<(usize, Option<Rect>, Option<Rect>)>,
+    /// Where the [`Placeholder`] is drawn
+    pub(crate) placeholder_rect: Option<Rect>,
+
+    /// The cells of a wrapping layout, used to find the target by row and column
+    grid_cells: Vec<GridCell>,
 }
 
 impl<'a> ItemIterator<'a> {
@@ -31,20 +69,33 @@ impl<'a> ItemIterator<'a> {
         dragged_item_rect: Option<Rect>,
         layout: Layout,
     ) -> Self {
+        // With the line indicator, there is no gap the items move apart for
+        let show_gap = state.drop_indicator == DropIndicator::Gap;
+
         let hovering_item = match state.detection_state {
             DragDetectionState::Dragging {
                 closest_item: item, ..
-            } => Some(item),
+            } if show_gap => Some(item),
             _ => None,
         };
 
         let hovering_last_item = match state.detection_state {
             DragDetectionState::Dragging {
                 hovering_last_item, ..
-            } => hovering_last_item,
+            } => hovering_last_item && show_gap,
             _ => false,
         };
 
+        let keyboard = match state.detection_state {
+            DragDetectionState::KeyboardDragging {
+                id,
+                target_idx,
+                dragged_item_size,
+                ..
+            } => Some((id, target_idx, dragged_item_size)),
+            _ => None,
+        };
+
         Self {
             state,
             dragged_item_rect,
@@ -60,14 +111,91 @@ impl<'a> ItemIterator<'a> {
             is_after_hovered_item: false,
             hovering_over_any_handle: false,
             source_item: None,
+
+            foreign_drag: false,
+            foreign_insert: None,
+            items_rect: Rect::NOTHING,
+
+            item_ids: Vec::new(),
+            dragged_indices: Vec::new(),
+
+            keyboard,
+            keyboard_count: 0,
+            keyboard_gap: None,
+            keyboard_source: None,
+            keyboard_action_rect: None,
+
+            tree_rows: None,
+            tree_depth: 0,
+
+            line_target: None,
+            placeholder_rect: None,
+
+            grid_cells: Vec::new(),
         }
     }
 
+    /// Same as [`crate::Dnd::with_drop_indicator`].
+    /// This needs to be called before the first call to [`ItemIterator::next`].
+    pub fn set_drop_indicator(&mut self, indicator: DropIndicator) {
+        self.state.drop_indicator = indicator;
+        if indicator == DropIndicator::Line && !self.foreign_drag {
+            self.hovering_item = None;
+            self.hovering_last_item = false;
+        }
+    }
+
+    /// Same as [`crate::Dnd::with_placeholder`]
+    pub fn set_placeholder(&mut self, placeholder: Placeholder) {
+        self.state.placeholder = placeholder;
+    }
+
+    /// Same as [`crate::Dnd::with_preview_style`]
+    pub fn set_preview_style(&mut self, style: PreviewStyle) {
+        self.state.preview_style = style;
+    }
+
+    /// Collect the rows of a tree instead of calculating the closest item.
+    /// Items keep their position while dragging, the drop target is calculated from the rows.
+    pub(crate) fn with_tree(mut self) -> Self {
+        self.tree_rows = Some(Vec::new());
+        self.dragged_item_rect = None;
+        self.hovering_item = None;
+        self.hovering_last_item = false;
+        self
+    }
+
+    /// Same as [`ItemIterator::next`], but records the depth of the item in the tree
+    pub(crate) fn next_tree(
+        &mut self,
+        ui: &mut Ui,
+        id: Id,
+        idx: usize,
+        depth: usize,
+        content: impl FnOnce(&mut Ui, Item) -> ItemResponse,
+    ) {
+        self.tree_depth = depth;
+        self.next(ui, id, idx, true, content);
+    }
+
+    /// Show the space for an item that is dragged from another list in the same group
+    pub(crate) fn with_foreign_drag(
+        mut self,
+        hovering_item: Option<(Id, Pos2)>,
+        hovering_last_item: bool,
+    ) -> Self {
+        self.foreign_drag = true;
+        self.hovering_item = hovering_item;
+        self.hovering_last_item = hovering_last_item;
+        self
+    }
+
     /// Draw a dnd item. This should be called for each item in the list.
     ///
     /// If `add_surrounding_space_automatically` is false, you need to call
     /// [`ItemIterator::space_before`] and [`ItemIterator::space_after`] manually.
     /// This is useful, e.g. to add a divider between items. Check the custom ui example.
+    #[allow(clippy::too_many_lines)] // TODO: refactor this to reduce the number of lines
     pub fn next(
         &mut self,
         ui: &mut Ui,
@@ -78,6 +206,17 @@ impl<'a> ItemIterator<'a> {
     ) {
         let is_dragged_item = self.state.detection_state.is_dragging_item(id);
 
+        self.item_ids.push(id);
+        if self.state.detection_state.dragged_count() > 1 {
+            if is_dragged_item {
+                self.dragged_indices.push(idx);
+            } else if self.state.detection_state.is_dragging_along(id) {
+                // Selected items are hidden while they are dragged along with the dragged item
+                self.dragged_indices.push(idx);
+                return;
+            }
+        }
+
         if let Some((distance, pos)) = self.mark_next_as_closest_item {
             self.mark_next_as_closest_item = None;
             self.closest_item = Some((distance, Some((idx, id, pos))));
@@ -93,6 +232,8 @@ impl<'a> ItemIterator<'a> {
             }
         }
 
+        self.add_keyboard_gap(ui, id, idx);
+
         if add_surrounding_space_automatically {
             self.space_before(ui, id, |_ui, _space| {});
         }
@@ -103,6 +244,7 @@ impl<'a> ItemIterator<'a> {
             id,
             ItemState {
                 dragged: is_dragged_item,
+                selected: self.state.multi_select && self.state.selection.contains(id),
                 index: idx,
             },
             self.state,
@@ -122,7 +264,9 @@ impl<'a> ItemIterator<'a> {
                     .0;
                 Rect::from_min_size(start, rect.size())
             } else {
-                content(ui, item).0
+                let start = ui.next_widget_position();
+                let rect = content(ui, item).0;
+                Rect::from_min_size(start, rect.size())
             }
         } else {
             content(ui, item).0
@@ -130,20 +274,50 @@ impl<'a> ItemIterator<'a> {
 
         if dragging != self.state.detection_state.is_dragging() {
             self.set_next_item_as_hovering_above = true;
+            self.record_drag_start();
         }
 
         if add_surrounding_space_automatically {
             self.space_after(ui, id, |_ui, _space| {});
         }
 
+        self.items_rect = self.items_rect.union(rect);
+
+        if self
+            .state
+            .keyboard_actions
+            .as_ref()
+            .is_some_and(|(action_id, _, _)| *action_id == id)
+        {
+            self.keyboard_action_rect = Some(rect);
+        }
+
+        if let Some(rows) = &mut self.tree_rows {
+            rows.push(TreeRow {
+                id,
+                depth: self.tree_depth,
+                rect,
+            });
+        }
+
         if let Some(dragged_item_rect) = self.dragged_item_rect {
-            if self.layout.main_wrap {
-                if rect.contains(dragged_item_rect.center()) {
-                    if self.is_after_hovered_item {
-                        self.mark_next_as_closest_item = Some((0.0, rect.min));
-                    } else {
-                        self.closest_item = Some((0.0, Some((idx, id, rect.min))));
-                    }
+            if self.foreign_drag {
+                if self.foreign_insert.is_none() && self.is_before(dragged_item_rect.center(), rect)
+                {
+                    self.foreign_insert = Some((idx, id, rect.min));
+                }
+            } else if self.state.drop_indicator == DropIndicator::Line {
+                if is_dragged_item {
+                    self.placeholder_rect = Some(rect);
+                }
+                self.check_line_target(dragged_item_rect.center(), idx, rect);
+            } else if self.layout.main_wrap {
+                // The dragged item doesn't take any space while it's hovering the gap
+                if !is_dragged_item {
+                    self.grid_cells.push(GridCell {
+                        item: Some((idx, id, rect.min)),
+                        rect,
+                    });
                 }
             } else {
                 let (distance, mark_next) = self.get_distance(dragged_item_rect, rect);
@@ -158,6 +332,72 @@ impl<'a> ItemIterator<'a> {
         self.last_item = Some((idx, id, rect.min));
     }
 
+    /// The drag started on the current item in this frame, so neither it nor the selected items
+    /// shown before it were recorded as dragged yet
+    fn record_drag_start(&mut self) {
+        let detection_state = &self.state.detection_state;
+        if detection_state.dragged_count() > 1 {
+            self.dragged_indices = self
+                .item_ids
+                .iter()
+                .enumerate()
+                .filter(|(_, id)| {
+                    detection_state.is_dragging_item(**id)
+                        || detection_state.is_dragging_along(**id)
+                })
+                .map(|(idx, _)| idx)
+                .collect();
+        }
+    }
+
+    /// Add the space where the item picked up with the keyboard will be dropped, if it's before this item
+    fn add_keyboard_gap(&mut self, ui: &mut Ui, id: Id, idx: usize) {
+        if let Some((keyboard_id, target_idx, size)) = self.keyboard {
+            if keyboard_id == id {
+                self.keyboard_source = Some(idx);
+            } else {
+                if self.keyboard_count == target_idx {
+                    self.keyboard_gap = Some(ui.allocate_space(size).1);
+                }
+                self.keyboard_count += 1;
+            }
+        }
+    }
+
+    /// In wrapping layouts, the item the dragged item would be inserted before.
+    /// Returns `None` if the layout doesn't wrap, `Some(None)` to insert after the last item.
+    #[allow(clippy::option_option)]
+    pub(crate) fn grid_target(&self) -> Option<Option<(usize, Id, Pos2)>> {
+        let dragged_item_rect = self.dragged_item_rect?;
+        (!self.grid_cells.is_empty())
+            .then(|| grid_insert_before(&self.grid_cells, dragged_item_rect.center()))
+    }
+
+    /// Update where the dragged item would be inserted, for [`DropIndicator::Line`].
+    /// The target is before the first item `pos` is before, or after the last item.
+    fn check_line_target(&mut self, pos: Pos2, idx: usize, rect: Rect) {
+        if let Some((_, _, Some(_))) = self.line_target {
+            return;
+        }
+        let previous = self.line_target.and_then(|(_, before, _)| before);
+        self.line_target = Some(if self.is_before(pos, rect) {
+            (idx, previous, Some(rect))
+        } else {
+            (idx + 1, Some(rect), None)
+        });
+    }
+
+    /// Returns true if `pos` is before `rect` in the layout
+    fn is_before(&self, pos: Pos2, rect: Rect) -> bool {
+        if self.layout.main_wrap {
+            pos.y < rect.top() || (pos.y <= rect.bottom() && pos.x < rect.center().x)
+        } else if self.layout.is_horizontal() {
+            pos.x < rect.center().x
+        } else {
+            pos.y < rect.center().y
+        }
+    }
+
     fn get_distance(&mut self, dragged_item_rect: Rect, rect: Rect) -> (f32, bool) {
         let size_difference = dragged_item_rect.size() - rect.size();
         let (distance, mark_next) = if self.layout.is_horizontal() {
@@ -207,8 +447,16 @@ impl<'a> ItemIterator<'a> {
                         })
                         .response
                         .rect;
-                    let (distance, _mark_next) = self.get_distance(dragged_item_rect, rect);
-                    self.check_closest_item(distance, rect.min, None, false);
+                    self.items_rect = self.items_rect.union(rect);
+                    if !self.foreign_drag {
+                        self.placeholder_rect = Some(rect);
+                        if self.layout.main_wrap {
+                            self.grid_cells.push(GridCell { item: None, rect });
+                        } else {
+                            let (distance, _mark_next) = self.get_distance(dragged_item_rect, rect);
+                            self.check_closest_item(distance, rect.min, None, false);
+                        }
+                    }
                 }
             }
         }
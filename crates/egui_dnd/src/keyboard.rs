@@ -0,0 +1,294 @@
+use egui::{
+    output::OutputEvent, EventFilter, Id, Key, Modifiers, Rect, Response, Sense, Ui, WidgetInfo,
+    WidgetType,
+};
+
+use crate::state::{DragDetectionState, DragDropResponse, DragDropUi, DragUpdate};
+
+/// A keyboard command for the focused handle, see [`crate::Dnd::with_keyboard_reordering`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum KeyboardAction {
+    PickUp,
+    Move(isize),
+    Drop,
+    Cancel,
+}
+
+/// Make the handle focusable and check for keyboard commands.
+/// The commands are applied in [`DragDropUi::apply_keyboard_action`], after all items are shown.
+pub(crate) fn handle_keyboard(state: &mut DragDropUi, ui: &mut Ui, id: Id, idx: usize, rect: Rect) {
+    let focus_id = id.with("dnd_keyboard_handle");
+    if state.keyboard_refocus == Some(id) {
+        state.keyboard_refocus = None;
+        ui.memory_mut(|mem| mem.request_focus(focus_id));
+    }
+    let response = ui.interact(rect, focus_id, Sense::focusable_noninteractive());
+    if !response.has_focus() {
+        return;
+    }
+
+    let picked_up = state.detection_state.keyboard_item() == Some(id);
+    if state.detection_state.is_keyboard_dragging() && !picked_up {
+        // Only the picked up item reacts to the keyboard, it takes the focus back if needed
+        return;
+    }
+    // The focus lock filter only applies to the events of the next frame
+    let arrows_locked = picked_up && ui.memory(|mem| mem.had_focus_last_frame(focus_id));
+
+    let actions = ui.input_mut(|i| {
+        let mut actions = Vec::new();
+        let mut picked_up = picked_up;
+        if i.consume_key(Modifiers::NONE, Key::Space) {
+            actions.push(if picked_up {
+                KeyboardAction::Drop
+            } else {
+                KeyboardAction::PickUp
+            });
+            picked_up = !picked_up;
+        }
+        // The item can be moved in the same frame it is picked up
+        if picked_up {
+            if i.consume_key(Modifiers::NONE, Key::ArrowUp)
+                || i.consume_key(Modifiers::NONE, Key::ArrowLeft)
+            {
+                actions.push(KeyboardAction::Move(-1));
+            } else if i.consume_key(Modifiers::NONE, Key::ArrowDown)
+                || i.consume_key(Modifiers::NONE, Key::ArrowRight)
+            {
+                actions.push(KeyboardAction::Move(1));
+            } else if i.consume_key(Modifiers::NONE, Key::Escape) {
+                actions.push(KeyboardAction::Cancel);
+            }
+        }
+        actions
+    });
+
+    let picks_up = actions.contains(&KeyboardAction::PickUp);
+    if picked_up || picks_up {
+        // Keep the focus while the arrow keys and escape are used to move the item
+        ui.memory_mut(|mem| {
+            mem.set_focus_lock_filter(
+                focus_id,
+                EventFilter {
+                    tab: false,
+                    horizontal_arrows: true,
+                    vertical_arrows: true,
+                    escape: true,
+                },
+            );
+        });
+    }
+    if !arrows_locked
+        && actions
+            .iter()
+            .any(|action| matches!(action, KeyboardAction::Move(_)))
+    {
+        // egui moves the focus with the arrow keys at the end of the frame
+        state.keyboard_refocus = Some(id);
+    }
+    if !actions.is_empty() {
+        state.keyboard_actions = Some((id, idx, actions));
+    }
+
+    paint_focus(ui, &response);
+}
+
+fn paint_focus(ui: &Ui, response: &Response) {
+    ui.painter().rect_stroke(
+        response.rect.expand(2.0),
+        ui.visuals().widgets.active.rounding,
+        ui.visuals().selection.stroke,
+    );
+}
+
+/// Convert the target index (the index the item will have after the move) to a [`DragUpdate`]
+fn update(source_idx: usize, target_idx: usize) -> DragUpdate {
+    DragUpdate {
+        from: source_idx,
+        to: if target_idx > source_idx {
+            target_idx + 1
+        } else {
+            target_idx
+        },
+    }
+}
+
+impl DragDropUi {
+    /// Apply the keyboard command of this frame (if any) and update the response.
+    /// `source_idx` is the current index of the picked up item, `item_rect` the rect of the item
+    /// the command was sent from.
+    #[allow(clippy::too_many_lines)] // TODO: refactor this to reduce the number of lines
+    pub(crate) fn apply_keyboard_action(
+        &mut self,
+        ui: &Ui,
+        item_count: usize,
+        source_idx: Option<usize>,
+        item_rect: Option<Rect>,
+        response: &mut DragDropResponse,
+    ) {
+        // Update the index, in case the list was updated while the item was picked up
+        if let DragDetectionState::KeyboardDragging {
+            source_idx: source_idx_out,
+            ..
+        } = &mut self.detection_state
+        {
+            if let Some(source_idx) = source_idx {
+                *source_idx_out = source_idx;
+            } else {
+                // The item isn't in the list anymore
+                self.detection_state = DragDetectionState::None;
+                self.keyboard_gap_pos = None;
+            }
+        }
+
+        let mut announcements = Vec::new();
+        if let Some((id, idx, actions)) = self.keyboard_actions.take() {
+            for action in actions {
+                announcements.extend(self.apply_single_keyboard_action(
+                    id, idx, action, item_count, item_rect, response,
+                ));
+            }
+            response.state = self.detection_state.clone();
+        }
+        self.announce(ui, announcements);
+
+        if let DragDetectionState::KeyboardDragging {
+            source_idx,
+            target_idx,
+            ..
+        } = self.detection_state
+        {
+            response.update = Some(update(source_idx, target_idx));
+            response.state = self.detection_state.clone();
+        }
+    }
+
+    /// Apply a keyboard command of the item `id` at `idx` and return the announcement, if any
+    fn apply_single_keyboard_action(
+        &mut self,
+        id: Id,
+        idx: usize,
+        action: KeyboardAction,
+        item_count: usize,
+        item_rect: Option<Rect>,
+        response: &mut DragDropResponse,
+    ) -> Option<String> {
+        match (action, &mut self.detection_state) {
+            (
+                KeyboardAction::PickUp,
+                DragDetectionState::None | DragDetectionState::Cancelled(_),
+            ) => {
+                self.detection_state = DragDetectionState::KeyboardDragging {
+                    id,
+                    start_idx: idx,
+                    source_idx: idx,
+                    target_idx: idx,
+                    dragged_item_size: item_rect.map(|rect| rect.size()).unwrap_or_default(),
+                };
+                self.keyboard_gap_pos = item_rect.map(|rect| rect.min);
+                Some(format!(
+                    "Picked up item {}. Use the arrow keys to move it, space to drop it and escape to cancel.",
+                    idx + 1
+                ))
+            }
+            (
+                KeyboardAction::Move(delta),
+                DragDetectionState::KeyboardDragging {
+                    start_idx,
+                    target_idx,
+                    ..
+                },
+            ) => {
+                let new_target = target_idx
+                    .saturating_add_signed(delta)
+                    .min(item_count.saturating_sub(1));
+                if new_target != *target_idx {
+                    *target_idx = new_target;
+                    response.has_changed = true;
+                }
+                Some(format!(
+                    "Item {} moved to position {}",
+                    *start_idx + 1,
+                    new_target + 1
+                ))
+            }
+            (
+                KeyboardAction::Drop,
+                DragDetectionState::KeyboardDragging {
+                    start_idx,
+                    source_idx,
+                    target_idx,
+                    ..
+                },
+            ) => {
+                response.update = Some(update(*source_idx, *target_idx));
+                response.finished = true;
+                let message = format!(
+                    "Item {} dropped at position {}",
+                    *start_idx + 1,
+                    *target_idx + 1
+                );
+                self.detection_state = DragDetectionState::None;
+                self.keyboard_gap_pos = None;
+                Some(message)
+            }
+            (
+                KeyboardAction::Cancel,
+                DragDetectionState::KeyboardDragging {
+                    start_idx,
+                    source_idx,
+                    ..
+                },
+            ) => {
+                // Move the item back, in case the list was updated while the item was picked up
+                response.update = Some(update(*source_idx, *start_idx));
+                response.has_changed = true;
+                response.cancellation_reason = Some("Cancelled with the escape key");
+                let message = format!(
+                    "Reordering cancelled. Item {} returned to position {}",
+                    *start_idx + 1,
+                    *start_idx + 1
+                );
+                self.detection_state = DragDetectionState::None;
+                self.keyboard_gap_pos = None;
+                Some(message)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl DragDropUi {
+    /// Send the new announcements to the platform and keep showing the last one
+    fn announce(&mut self, ui: &Ui, messages: Vec<String>) {
+        for message in messages {
+            ui.ctx().output_mut(|o| {
+                o.events.push(OutputEvent::ValueChanged(WidgetInfo::labeled(
+                    WidgetType::Other,
+                    true,
+                    &message,
+                )));
+            });
+            self.announcement = Some(message);
+        }
+        self.announce_live_region(ui);
+    }
+
+    /// Add the last announcement as a live region to the accessibility tree,
+    /// so screen readers read it
+    #[cfg(feature = "accesskit")]
+    fn announce_live_region(&self, ui: &Ui) {
+        if let Some(message) = &self.announcement {
+            ui.ctx()
+                .accesskit_node_builder(ui.id().with("dnd_announcement"), |builder| {
+                    builder.set_role(egui::accesskit::Role::Status);
+                    builder.set_live(egui::accesskit::Live::Assertive);
+                    builder.set_name(message.clone());
+                });
+        }
+    }
+
+    #[cfg(not(feature = "accesskit"))]
+    #[allow(clippy::unused_self)]
+    fn announce_live_region(&self, _ui: &Ui) {}
+}
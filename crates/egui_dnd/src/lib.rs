@@ -3,23 +3,46 @@
 #![warn(missing_docs)]
 
 use egui::{Id, Ui};
-pub use state::{DragDropConfig, DragDropItem, DragDropResponse, DragUpdate, Handle};
+pub use state::{
+    CrossListUpdate, DragDropConfig, DragDropItem, DragDropResponse, DragUpdate, Handle,
+    MultiDragUpdate,
+};
 
+pub use crate::external::notify_external_drop;
+use crate::external::ExternalDropZone;
+use crate::group::DndGroup;
 pub use crate::item_iterator::ItemIterator;
 use crate::state::DragDropUi;
+pub use crate::style::{DropIndicator, Placeholder, PreviewStyle};
+pub use crate::tree::TreeDragUpdate;
+use std::any::Any;
 use std::hash::Hash;
 
+mod constraints;
+mod external;
+mod grid;
+mod group;
 mod item;
 mod item_iterator;
+mod keyboard;
+mod selection;
 mod state;
+mod style;
+#[cfg(feature = "testing")]
+pub mod testing;
+mod tree;
 /// Helper functions to support the drag and drop functionality
 pub mod utils;
 
 /// Helper struct for ease of use.
 pub struct Dnd<'a> {
     id: Id,
+    list_id: Id,
+    tree_indent: Option<f32>,
     ui: &'a mut Ui,
     drag_drop_ui: DragDropUi,
+    #[allow(clippy::type_complexity)]
+    can_drop: Option<Box<dyn Fn(usize, usize) -> bool + 'a>>,
 }
 
 /// Main entry point for the drag and drop functionality.
@@ -52,17 +75,30 @@ pub struct Dnd<'a> {
 /// }
 /// ```
 pub fn dnd(ui: &mut Ui, id_source: impl Hash) -> Dnd {
-    let id = Id::new(id_source).with("dnd");
+    let list_id = Id::new(id_source);
+    let id = list_id.with("dnd");
     let mut dnd_ui: DragDropUi =
         ui.data_mut(|data| (*data.get_temp_mut_or_default::<DragDropUi>(id)).clone());
 
     dnd_ui.return_animation_time = ui.style().animation_time;
     dnd_ui.swap_animation_time = ui.style().animation_time;
+    dnd_ui.group = None;
+    dnd_ui.tree_indent = None;
+    dnd_ui.multi_select = false;
+    dnd_ui.keyboard_reordering = false;
+    dnd_ui.external_drop = None;
+    dnd_ui.drop_indicator = DropIndicator::default();
+    dnd_ui.placeholder = Placeholder::default();
+    dnd_ui.preview_style = PreviewStyle::default();
+    dnd_ui.axis_lock = false;
 
     Dnd {
         id,
+        list_id,
+        tree_indent: None,
         ui,
         drag_drop_ui: dnd_ui,
+        can_drop: None,
     }
 }
 
@@ -118,6 +154,112 @@ impl<'a> Dnd<'a> {
         self
     }
 
+    /// Adds the list to a drop group. Items can be dragged between all lists with the same group id.
+    /// When an item is dropped in another list, the response of the list the item was dragged from
+    /// contains a [`CrossListUpdate`] (see [`DragDropResponse::cross_list_update`]).
+    /// You need to apply it yourself, e.g. via [`CrossListUpdate::apply_vecs`].
+    /// Check the [kanban example](https://github.com/lucasmerlin/hello_egui/blob/main/crates/egui_dnd/examples/kanban.rs).
+    ///
+    /// Item ids need to be unique across all lists in the group.
+    #[must_use]
+    pub fn with_group(mut self, group_id: impl Hash) -> Self {
+        self.drag_drop_ui.group = Some(DndGroup {
+            id: Id::new(group_id),
+            list_id: self.list_id,
+        });
+        self
+    }
+
+    /// Enables selecting items by clicking their handle.
+    /// Ctrl / Cmd-click toggles an item, Shift-click selects a range.
+    /// Dragging a selected item drags all selected items together and returns a [`MultiDragUpdate`]
+    /// (see [`DragDropResponse::multi_update`]), which is applied by [`DragDropResponse::update_vec`].
+    /// Use [`ItemState::selected`] to highlight selected items.
+    #[must_use]
+    pub fn with_multi_select(mut self, multi_select: bool) -> Self {
+        self.drag_drop_ui.multi_select = multi_select;
+        self
+    }
+
+    /// Enables reordering with the keyboard. Handles can be focused with tab, space picks up
+    /// the item, the arrow keys move it, space drops it and escape cancels.
+    /// The response contains the [`DragUpdate`] like for pointer drags, and the changes are
+    /// announced to screen readers (enable the `accesskit` feature for AccessKit support).
+    #[must_use]
+    pub fn with_keyboard_reordering(mut self, keyboard_reordering: bool) -> Self {
+        self.drag_drop_ui.keyboard_reordering = keyboard_reordering;
+        self
+    }
+
+    /// Allows dropping egui [`egui::DragAndDrop`] payloads of type `P` in the list, e.g. items of
+    /// another list that set a payload via [`Handle::payload`] or anything that uses
+    /// [`egui::Response::dnd_set_drag_payload`].
+    /// While a payload is hovering the list, the items move apart to show where it would be inserted.
+    /// When it is dropped, [`DragDropResponse::external_payload`] returns the index and the payload,
+    /// and you need to insert it yourself.
+    #[must_use]
+    pub fn with_external_drop<P: Any + Send + Sync>(mut self) -> Self {
+        self.drag_drop_ui.external_drop = Some(ExternalDropZone::new::<P>());
+        self
+    }
+
+    /// Sets how the position the dragged item would be dropped at is shown.
+    /// With [`DropIndicator::Line`] the items don't move while dragging and the update is only
+    /// applied once the item is dropped, which is useful for dense lists.
+    /// The default is [`DropIndicator::Gap`].
+    #[must_use]
+    pub fn with_drop_indicator(mut self, indicator: DropIndicator) -> Self {
+        self.drag_drop_ui.drop_indicator = indicator;
+        self
+    }
+
+    /// Sets what is drawn in the space the dragged item would be dropped at.
+    /// The default is [`Placeholder::Empty`].
+    /// For a completely custom placeholder, use [`Dnd::show_custom`] and draw it in
+    /// [`ItemIterator::space_before`] / [`ItemIterator::space_after`].
+    #[must_use]
+    pub fn with_placeholder(mut self, placeholder: Placeholder) -> Self {
+        self.drag_drop_ui.placeholder = placeholder;
+        self
+    }
+
+    /// Sets how the floating preview of the dragged item is drawn (opacity, scale and shadow).
+    /// Use [`ItemState::dragged`] if you want to draw the dragged item differently yourself.
+    #[must_use]
+    pub fn with_preview_style(mut self, style: PreviewStyle) -> Self {
+        self.drag_drop_ui.preview_style = style;
+        self
+    }
+
+    /// Locks the dragged item to the main axis of the layout, e.g. in a vertical list it can only
+    /// be moved up and down.
+    #[must_use]
+    pub fn with_axis_lock(mut self, axis_lock: bool) -> Self {
+        self.drag_drop_ui.axis_lock = axis_lock;
+        self
+    }
+
+    /// Sets a callback that decides whether the item at index `from` can be moved to `to`
+    /// (with the same indices as [`DragUpdate`]).
+    /// While the item hovers a rejected position, the list isn't updated and the position is
+    /// highlighted. If the item is dropped there, there is no update and
+    /// [`DragDropResponse::cancellation_reason`] contains the reason.
+    ///
+    /// To keep single items in place, use [`Handle::pinned`] or [`Handle::barrier`] instead.
+    #[must_use]
+    pub fn with_can_drop(mut self, can_drop: impl Fn(usize, usize) -> bool + 'a) -> Self {
+        self.can_drop = Some(Box::new(can_drop));
+        self
+    }
+
+    /// Sets the indentation per level for [`Dnd::show_tree`].
+    /// The default is the egui indent spacing.
+    #[must_use]
+    pub fn with_tree_indent(mut self, indent: f32) -> Self {
+        self.tree_indent = Some(indent);
+        self
+    }
+
     /// Display the drag and drop UI.
     /// `items` should be an iterator over items that should be sortable.
     /// Each item needs to implement [`DragDropItem`]. This is automatically implement for every type that implements [Hash].
@@ -137,7 +279,7 @@ impl<'a> Dnd<'a> {
         items: impl Iterator<Item = T>,
         mut item_ui: impl FnMut(&mut Ui, T, Handle, ItemState),
     ) -> DragDropResponse {
-        self._show_with_inner(|_id, ui, drag_drop_ui| {
+        self.show_with_inner_impl(|_id, ui, drag_drop_ui| {
             drag_drop_ui.ui(ui, |ui, iter| {
                 items.enumerate().for_each(|(i, item)| {
                     iter.next(ui, item.id(), i, true, |ui, item_handle| {
@@ -159,7 +301,7 @@ impl<'a> Dnd<'a> {
         size: egui::Vec2,
         mut item_ui: impl FnMut(&mut Ui, T, Handle, ItemState),
     ) -> DragDropResponse {
-        self._show_with_inner(|_id, ui, drag_drop_ui| {
+        self.show_with_inner_impl(|_id, ui, drag_drop_ui| {
             drag_drop_ui.ui(ui, |ui, iter| {
                 items.enumerate().for_each(|(i, item)| {
                     iter.next(ui, item.id(), i, true, |ui, item_handle| {
@@ -172,6 +314,40 @@ impl<'a> Dnd<'a> {
         })
     }
 
+    /// Display the items as a tree, allowing items to be moved to other parents.
+    /// `items` should iterate over the visible items of the tree in display order (depth first),
+    /// together with their depth (0 for root items). Collapsed children can simply be skipped.
+    ///
+    /// Dropping an item on the middle of another item makes it the first child of that item,
+    /// dropping it near the top or bottom edge inserts it as a sibling. When inserting below an item,
+    /// the depth can be changed by dragging the item left or right.
+    ///
+    /// The tree isn't updated automatically, use [`DragDropResponse::tree_update`] and
+    /// [`TreeDragUpdate::apply`] to do so. Check the [tree example](https://github.com/lucasmerlin/hello_egui/blob/main/crates/egui_dnd/examples/tree.rs).
+    pub fn show_tree<T: DragDropItem>(
+        mut self,
+        items: impl Iterator<Item = (T, usize)>,
+        mut item_ui: impl FnMut(&mut Ui, T, Handle, ItemState),
+    ) -> DragDropResponse {
+        let indent = self.tree_indent.unwrap_or(self.ui.spacing().indent);
+        self.drag_drop_ui.tree_indent = Some(indent);
+        self.show_with_inner_impl(|_id, ui, drag_drop_ui| {
+            drag_drop_ui.ui(ui, |ui, iter| {
+                items.enumerate().for_each(|(i, (item, depth))| {
+                    iter.next_tree(ui, item.id(), i, depth, |ui, item_handle| {
+                        item_handle.ui(ui, |ui, handle, state| {
+                            ui.horizontal(|ui| {
+                                #[allow(clippy::cast_precision_loss)]
+                                ui.add_space(indent * depth as f32);
+                                ui.vertical(|ui| item_ui(ui, item, handle, state));
+                            });
+                        })
+                    });
+                });
+            })
+        })
+    }
+
     /// Same as [`Dnd::show`], but automatically sorts the items.
     pub fn show_vec<T: Hash>(
         self,
@@ -198,7 +374,7 @@ impl<'a> Dnd<'a> {
     /// This will allow for very flexible UI. You can use it to e.g. render outlines around items
     /// or render items in complex layouts. This is **experimental**.
     pub fn show_custom(self, f: impl FnOnce(&mut Ui, &mut ItemIterator)) -> DragDropResponse {
-        self._show_with_inner(|_id, ui, drag_drop_ui| drag_drop_ui.ui(ui, f))
+        self.show_with_inner_impl(|_id, ui, drag_drop_ui| drag_drop_ui.ui(ui, f))
     }
 
     /// Same as [`Dnd::show_custom`], but automatically sorts the items.
@@ -212,17 +388,23 @@ impl<'a> Dnd<'a> {
         response
     }
 
-    fn _show_with_inner(
+    fn show_with_inner_impl(
         self,
         inner_fn: impl FnOnce(Id, &mut Ui, &mut DragDropUi) -> DragDropResponse,
     ) -> DragDropResponse {
         let Dnd {
             id,
             ui,
+            list_id: _,
+            tree_indent: _,
             mut drag_drop_ui,
+            can_drop,
         } = self;
 
-        let response = inner_fn(id, ui, &mut drag_drop_ui);
+        let mut response = inner_fn(id, ui, &mut drag_drop_ui);
+        if let Some(can_drop) = can_drop {
+            drag_drop_ui.check_can_drop(ui, &mut response, &can_drop);
+        }
 
         ui.ctx().data_mut(|data| data.insert_temp(id, drag_drop_ui));
 
@@ -234,6 +416,8 @@ impl<'a> Dnd<'a> {
 pub struct ItemState {
     /// True if the item is currently being dragged.
     pub dragged: bool,
+    /// True if the item is selected. See [`Dnd::with_multi_select`].
+    pub selected: bool,
     /// Index of the item in the list.
     /// Note that when you sort the source list while the drag is still ongoing (default behaviour
     /// of [`Dnd::show_vec`]), this index will updated while the item is being dragged.
@@ -0,0 +1,45 @@
+use egui::{Id, Modifiers};
+
+/// The selected items of a list, see [`crate::Dnd::with_multi_select`]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Selection {
+    pub ids: Vec<Id>,
+    /// The item that was clicked last without shift, used as start of shift-click ranges
+    anchor: Option<Id>,
+}
+
+impl Selection {
+    pub fn contains(&self, id: Id) -> bool {
+        self.ids.contains(&id)
+    }
+
+    /// Select only this item
+    pub fn select_only(&mut self, id: Id) {
+        self.ids = vec![id];
+        self.anchor = Some(id);
+    }
+
+    /// Update the selection after an item was clicked.
+    /// `order` contains the ids of all items in the list, in order.
+    pub fn click(&mut self, id: Id, modifiers: Modifiers, order: &[Id]) {
+        let range = self
+            .anchor
+            .and_then(|anchor| order.iter().position(|i| *i == anchor))
+            .zip(order.iter().position(|i| *i == id));
+
+        match range {
+            Some((anchor, clicked)) if modifiers.shift => {
+                self.ids = order[anchor.min(clicked)..=anchor.max(clicked)].to_vec();
+            }
+            _ if modifiers.command => {
+                if let Some(pos) = self.ids.iter().position(|i| *i == id) {
+                    self.ids.remove(pos);
+                } else {
+                    self.ids.push(id);
+                }
+                self.anchor = Some(id);
+            }
+            _ => self.select_only(id),
+        }
+    }
+}
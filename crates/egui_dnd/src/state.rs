@@ -1,14 +1,20 @@
+use std::any::Any;
 use std::hash::Hash;
-#[cfg(not(target_arch = "wasm32"))]
-use std::time::{Duration, SystemTime};
+use std::sync::Arc;
+use std::time::Duration;
 
-use egui::{CursorIcon, Id, Pos2, Rect, Sense, Ui, Vec2};
-
-#[cfg(target_arch = "wasm32")]
-use web_time::{Duration, SystemTime};
+use egui::epaint::ClippedShape;
+use egui::{Context, CursorIcon, DragAndDrop, Id, Modifiers, Pos2, Rect, Sense, Ui, Vec2};
 
+use crate::constraints::Barrier;
+use crate::external::{was_accepted_in, ExternalDropZone, ExternalHover};
+use crate::group::{DndGroup, GroupState};
 use crate::item_iterator::ItemIterator;
-use crate::utils::shift_vec;
+use crate::keyboard::{handle_keyboard, KeyboardAction};
+use crate::selection::Selection;
+use crate::style::{paint_insertion_line, DropIndicator, Placeholder, PreviewStyle};
+use crate::tree::{tree_drop_target, TreeDragUpdate};
+use crate::utils::{move_between_vecs, shift_vec, shift_vec_multi};
 
 /// Item that can be reordered using drag and drop
 pub trait DragDropItem {
@@ -33,18 +39,120 @@ pub struct DragUpdate {
     pub to: usize,
 }
 
+/// An instruction in what order to update the source list when multiple selected items are dragged.
+/// The items at the from indices should be removed from the list and inserted at to, keeping their order.
+/// You can use [`shift_vec_multi`] to do this for a Vec.
+/// See [`crate::Dnd::with_multi_select`].
+#[derive(Debug, Clone)]
+pub struct MultiDragUpdate {
+    /// Indices of the items to move, sorted ascending
+    pub from: Vec<usize>,
+    /// Where to move the items to
+    pub to: usize,
+}
+
+/// An instruction to move an item from one list to another list in the same drop group.
+/// The item at from should be removed from the source list and inserted at to in the target list.
+/// You can use [`CrossListUpdate::apply_vecs`] to do this for two Vecs, or
+/// [`CrossListUpdate::apply_lists`] for a board of lists.
+/// See [`crate::Dnd::with_group`].
+#[derive(Debug, Clone)]
+pub struct CrossListUpdate {
+    /// Id of the list the item was dragged from (`Id::new(id_source)`)
+    pub from_list: Id,
+    /// Index of the item in the source list
+    pub from: usize,
+    /// Id of the list the item was dropped in (`Id::new(id_source)`)
+    pub to_list: Id,
+    /// Index in the target list where the item should be inserted
+    pub to: usize,
+}
+
+impl CrossListUpdate {
+    /// Utility function to move the item from the source vec to the target vec.
+    pub fn apply_vecs<T>(&self, source: &mut Vec<T>, target: &mut Vec<T>) {
+        move_between_vecs(self.from, self.to, source, target);
+    }
+
+    /// Utility function to apply the update to a board of lists, e.g. the columns of a kanban board.
+    /// `list_id` should return the id each list was shown with (`Id::new(id_source)`) and `items`
+    /// the vec of items of the list.
+    /// Returns false if the source or target list wasn't found, in which case nothing is changed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use egui::Id;
+    /// use egui_dnd::CrossListUpdate;
+    ///
+    /// struct Column {
+    ///     name: &'static str,
+    ///     items: Vec<u32>,
+    /// }
+    ///
+    /// let mut board = vec![
+    ///     Column { name: "todo", items: vec![1, 2] },
+    ///     Column { name: "done", items: vec![3] },
+    /// ];
+    /// let update = CrossListUpdate {
+    ///     from_list: Id::new("todo"),
+    ///     from: 0,
+    ///     to_list: Id::new("done"),
+    ///     to: 1,
+    /// };
+    /// assert!(update.apply_lists(&mut board, |c| Id::new(c.name), |c| &mut c.items));
+    /// assert_eq!(board[0].items, [2]);
+    /// assert_eq!(board[1].items, [3, 1]);
+    /// ```
+    pub fn apply_lists<L, T>(
+        &self,
+        lists: &mut [L],
+        list_id: impl Fn(&L) -> Id,
+        mut items: impl FnMut(&mut L) -> &mut Vec<T>,
+    ) -> bool {
+        let from = lists
+            .iter()
+            .position(|list| list_id(list) == self.from_list);
+        let to = lists.iter().position(|list| list_id(list) == self.to_list);
+        let (Some(from), Some(to)) = (from, to) else {
+            return false;
+        };
+
+        if from == to {
+            let vec = items(&mut lists[from]);
+            let item = vec.remove(self.from);
+            vec.insert(self.to, item);
+        } else {
+            let (first, second) = lists.split_at_mut(from.max(to));
+            let (source, target) = if from < to {
+                (&mut first[from], &mut second[0])
+            } else {
+                (&mut second[0], &mut first[to])
+            };
+            self.apply_vecs(items(source), items(target));
+        }
+        true
+    }
+}
+
 /// Response containing state of the drag & drop list and a potential update to the source list.
 /// The update can be applied immediately or at latest when [`DragDropResponse::is_drag_finished`] returns true.
 #[derive(Debug, Clone)]
 pub struct DragDropResponse {
-    state: DragDetectionState,
+    pub(crate) state: DragDetectionState,
     /// Contains ongoing information about which index is currently being dragged where.
     /// You can use this to consistently update the source list while the drag & drop event is ongoing.
     /// If you only want to update the source list when the drag & drop event has finished, use [`DragDropResponse::final_update`] instead.
     pub update: Option<DragUpdate>,
-    finished: bool,
-    cancellation_reason: Option<&'static str>,
-    has_changed: bool,
+    pub(crate) multi_update: Option<MultiDragUpdate>,
+    selection: Vec<Id>,
+    cross_list_update: Option<CrossListUpdate>,
+    tree_update: Option<TreeDragUpdate>,
+    dropped_externally: Option<usize>,
+    external_payload: Option<(usize, Arc<dyn Any + Send + Sync>)>,
+    pub(crate) finished: bool,
+    pub(crate) cancellation_reason: Option<&'static str>,
+    pub(crate) has_changed: bool,
 }
 
 impl DragDropResponse {
@@ -54,16 +162,16 @@ impl DragDropResponse {
         self.state.is_evaluating_drag()
     }
 
-    /// Returns true if we are currently dragging an item.
+    /// Returns true if we are currently dragging an item, with the pointer or the keyboard.
     #[must_use]
     pub fn is_dragging(&self) -> bool {
-        self.state.is_dragging()
+        self.state.is_dragging() || self.state.is_keyboard_dragging()
     }
 
     /// Returns the id of the item that is currently being dragged.
     #[must_use]
     pub fn dragged_item_id(&self) -> Option<Id> {
-        self.state.dragged_item()
+        self.state.dragged_item().or(self.state.keyboard_item())
     }
 
     /// Returns true if the drag & drop event has finished and the item has been dropped.
@@ -75,14 +183,31 @@ impl DragDropResponse {
 
     /// Utility function to update a Vec with the current drag & drop state.
     /// You can use this to consistently update the source list while the drag & drop event is ongoing.
+    /// If multiple selected items are dragged, the [`MultiDragUpdate`] is applied instead.
     pub fn update_vec<T>(&self, vec: &mut [T]) {
         if self.has_changed || self.finished {
             if let Some(update) = &self.update {
                 shift_vec(update.from, update.to, vec);
+            } else if let Some(update) = &self.multi_update {
+                shift_vec_multi(&update.from, update.to, vec);
             }
         }
     }
 
+    /// Contains ongoing information about which indices are currently being dragged where, if
+    /// multiple selected items are dragged (see [`crate::Dnd::with_multi_select`]).
+    /// In this case, [`DragDropResponse::update`] is `None`.
+    #[must_use]
+    pub fn multi_update(&self) -> Option<MultiDragUpdate> {
+        self.multi_update.clone()
+    }
+
+    /// Returns the ids of the selected items (see [`crate::Dnd::with_multi_select`]).
+    #[must_use]
+    pub fn selected_ids(&self) -> &[Id] {
+        &self.selection
+    }
+
     /// Returns the update if the drag & drop event has finished and the item has been dropped.
     /// Useful for the if let syntax.
     #[must_use]
@@ -94,6 +219,35 @@ impl DragDropResponse {
         }
     }
 
+    /// Returns the update if an item of this list has been dropped in another list of the same group.
+    /// Only the response of the list the item was dragged from contains the update.
+    #[must_use]
+    pub fn cross_list_update(&self) -> Option<CrossListUpdate> {
+        self.cross_list_update.clone()
+    }
+
+    /// Returns the update if an item has been dropped in a tree shown with [`crate::Dnd::show_tree`].
+    /// This is `None` if the item was dropped somewhere it can't be moved to, e.g. on its own children.
+    #[must_use]
+    pub fn tree_update(&self) -> Option<TreeDragUpdate> {
+        self.tree_update.clone()
+    }
+
+    /// Returns the index of the dragged item if it was dropped on a drop zone outside the list
+    /// that took its payload (see [`Handle::payload`]). You should remove the item from the list.
+    #[must_use]
+    pub fn dropped_externally(&self) -> Option<usize> {
+        self.dropped_externally
+    }
+
+    /// Returns the index where the payload should be inserted and the payload, if an egui
+    /// [`DragAndDrop`] payload of type `P` was dropped in the list (see [`crate::Dnd::with_external_drop`]).
+    #[must_use]
+    pub fn external_payload<P: Any + Send + Sync>(&self) -> Option<(usize, Arc<P>)> {
+        let (idx, payload) = self.external_payload.clone()?;
+        Some((idx, payload.downcast().ok()?))
+    }
+
     /// Returns a [Option<&str>] with the reason if a drag & drop event was cancelled.
     #[must_use]
     pub fn cancellation_reason(&self) -> Option<&'static str> {
@@ -111,6 +265,45 @@ pub struct DragDropUi {
     mouse_config: DragDropConfig,
     pub(crate) swap_animation_time: f32,
     pub(crate) return_animation_time: f32,
+    pub(crate) group: Option<DndGroup>,
+    /// The indent per tree level, if the list is shown as a tree
+    pub(crate) tree_indent: Option<f32>,
+    pub(crate) multi_select: bool,
+    pub(crate) selection: Selection,
+    /// An item that was clicked this frame, applied to the selection after all items are shown
+    pub(crate) selection_click: Option<(Id, Modifiers)>,
+    pub(crate) keyboard_reordering: bool,
+    /// The keyboard commands of this frame and the handle they were sent from, applied in order
+    /// after all items are shown
+    pub(crate) keyboard_actions: Option<(Id, usize, Vec<KeyboardAction>)>,
+    /// The handle that has to get the focus back, because the arrow keys moved it away in the
+    /// frame the item was picked up
+    pub(crate) keyboard_refocus: Option<Id>,
+    /// Where the item that was picked up with the keyboard will be dropped
+    pub(crate) keyboard_gap_pos: Option<Pos2>,
+    /// The last message for screen readers
+    pub(crate) announcement: Option<String>,
+    /// Accepts payloads dragged from outside the list
+    pub(crate) external_drop: Option<ExternalDropZone>,
+    /// Where an external payload would be inserted
+    external_hover: Option<ExternalHover>,
+    /// True if the dragged item has set a payload via [`Handle::payload`]
+    pub(crate) has_payload: bool,
+    /// The index of the item that was dropped and the pass it was dropped in, if we don't know yet
+    /// whether a drop zone accepted its payload
+    pending_external_drop: Option<(usize, u64)>,
+    /// The rect of all items, from the last frame
+    items_rect: Rect,
+    pub(crate) drop_indicator: DropIndicator,
+    pub(crate) placeholder: Placeholder,
+    pub(crate) preview_style: PreviewStyle,
+    /// The shapes of the dragged item and the rect they were painted in, for [`Placeholder::Ghost`]
+    pub(crate) preview_shapes: Option<(Rect, Vec<ClippedShape>)>,
+    /// The space where the dragged item would be dropped
+    pub(crate) gap_rect: Option<Rect>,
+    /// The items other items can't be moved past, collected by the handles each frame
+    pub(crate) barriers: Vec<Barrier>,
+    pub(crate) axis_lock: bool,
 }
 
 impl Default for DragDropUi {
@@ -121,6 +314,28 @@ impl Default for DragDropUi {
             mouse_config: DragDropConfig::mouse(),
             swap_animation_time: 0.2,
             return_animation_time: 0.2,
+            group: None,
+            tree_indent: None,
+            multi_select: false,
+            selection: Selection::default(),
+            selection_click: None,
+            keyboard_reordering: false,
+            keyboard_actions: None,
+            keyboard_refocus: None,
+            keyboard_gap_pos: None,
+            announcement: None,
+            external_drop: None,
+            external_hover: None,
+            has_payload: false,
+            pending_external_drop: None,
+            items_rect: Rect::NOTHING,
+            drop_indicator: DropIndicator::default(),
+            placeholder: Placeholder::default(),
+            preview_style: PreviewStyle::default(),
+            preview_shapes: None,
+            gap_rect: None,
+            barriers: Vec::new(),
+            axis_lock: false,
         }
     }
 }
@@ -137,17 +352,23 @@ pub struct Handle<'a> {
     sense: Option<Sense>,
     show_drag_cursor_on_hover: bool,
     disable_selectable_labels: bool,
+    pinned: bool,
+    barrier: bool,
+    /// Sets the egui drag and drop payload, if the item is dragged
+    #[allow(clippy::type_complexity)]
+    payload: Option<Box<dyn FnOnce(&Context) + 'a>>,
 }
 
 #[derive(Debug, Default, Clone)]
 pub(crate) enum DragDetectionState {
     #[default]
     None,
+    /// `pressed_at` is the egui time (see [`egui::InputState::time`]) the pointer was pressed at
     PressedWaitingForDelay {
-        pressed_at: SystemTime,
+        pressed_at: f64,
     },
     WaitingForClickThreshold {
-        pressed_at: SystemTime,
+        pressed_at: f64,
     },
     CouldBeValidDrag,
     Cancelled(&'static str),
@@ -155,10 +376,14 @@ pub(crate) enum DragDetectionState {
         id: Id,
         source_idx: usize,
         offset: Vec2,
+        /// Position of the item when the drag started, see [`crate::Dnd::with_axis_lock`]
+        origin: Pos2,
         dragged_item_size: Vec2,
         closest_item: (Id, Pos2),
         last_pointer_pos: Pos2,
         hovering_last_item: bool,
+        /// All selected items that are dragged together, if more than one item is dragged
+        selection: Vec<Id>,
 
         // These should only be used for output, as to not cause issues when item indexes change
         hovering_idx: usize,
@@ -167,6 +392,17 @@ pub(crate) enum DragDetectionState {
         id: Id,
         dragged_item_size: Option<Vec2>,
     },
+    /// An item was picked up with the keyboard, see [`crate::Dnd::with_keyboard_reordering`]
+    KeyboardDragging {
+        id: Id,
+        /// Index of the item when it was picked up, used for announcements and to cancel
+        start_idx: usize,
+        /// Current index of the item
+        source_idx: usize,
+        /// The index the item will have after it is dropped
+        target_idx: usize,
+        dragged_item_size: Vec2,
+    },
 }
 
 impl DragDetectionState {
@@ -180,7 +416,7 @@ impl DragDetectionState {
         matches!(self, DragDetectionState::Dragging { .. })
     }
 
-    fn dragged_item(&self) -> Option<Id> {
+    pub(crate) fn dragged_item(&self) -> Option<Id> {
         match self {
             DragDetectionState::Dragging { id, .. } => Some(*id),
             _ => None,
@@ -191,6 +427,37 @@ impl DragDetectionState {
         self.dragged_item() == Some(id)
     }
 
+    pub(crate) fn is_keyboard_dragging(&self) -> bool {
+        matches!(self, DragDetectionState::KeyboardDragging { .. })
+    }
+
+    pub(crate) fn keyboard_item(&self) -> Option<Id> {
+        match self {
+            DragDetectionState::KeyboardDragging { id, .. } => Some(*id),
+            _ => None,
+        }
+    }
+
+    /// True if the item is selected and dragged along with the dragged item
+    pub(crate) fn is_dragging_along(&self, id: Id) -> bool {
+        match self {
+            DragDetectionState::Dragging {
+                id: dragged_id,
+                selection,
+                ..
+            } => *dragged_id != id && selection.contains(&id),
+            _ => false,
+        }
+    }
+
+    /// The number of items that are dragged
+    pub(crate) fn dragged_count(&self) -> usize {
+        match self {
+            DragDetectionState::Dragging { selection, .. } => selection.len().max(1),
+            _ => 0,
+        }
+    }
+
     pub(crate) fn dragged_item_size(&self) -> Option<Vec2> {
         match self {
             DragDetectionState::TransitioningBackAfterDragFinished {
@@ -199,11 +466,21 @@ impl DragDetectionState {
             }
             | DragDetectionState::Dragging {
                 dragged_item_size, ..
+            }
+            | DragDetectionState::KeyboardDragging {
+                dragged_item_size, ..
             } => Some(*dragged_item_size),
             _ => None,
         }
     }
 
+    fn source_idx(&self) -> Option<usize> {
+        match self {
+            DragDetectionState::Dragging { source_idx, .. } => Some(*source_idx),
+            _ => None,
+        }
+    }
+
     pub(crate) fn last_pointer_pos(&self) -> Option<Pos2> {
         match self {
             DragDetectionState::Dragging {
@@ -232,6 +509,9 @@ impl<'a> Handle<'a> {
             sense: None,
             show_drag_cursor_on_hover: true,
             disable_selectable_labels: true,
+            pinned: false,
+            barrier: false,
+            payload: None,
         }
     }
 
@@ -262,6 +542,34 @@ impl<'a> Handle<'a> {
         self
     }
 
+    /// Sets an egui [`egui::DragAndDrop`] payload while the item is dragged, so it can be dropped
+    /// on drop zones outside the list (e.g. via [`Ui::dnd_drop_zone`]) or on other lists that
+    /// accept it (see [`crate::Dnd::with_external_drop`]).
+    /// If a drop zone takes the payload, [`DragDropResponse::dropped_externally`] returns the
+    /// index of the item, so you can remove it from the list.
+    #[must_use]
+    pub fn payload<P: Any + Send + Sync>(mut self, payload: P) -> Self {
+        if self.state.detection_state.is_dragging_item(self.id) {
+            self.payload = Some(Box::new(move |ctx| DragAndDrop::set_payload(ctx, payload)));
+        }
+        self
+    }
+
+    /// Pins the item: it can't be dragged and other items can't be moved past it.
+    /// Useful to keep e.g. the first and last item of a list in place.
+    #[must_use]
+    pub fn pinned(mut self, pinned: bool) -> Self {
+        self.pinned = pinned;
+        self
+    }
+
+    /// Other items can't be moved past this item, but it can still be dragged itself.
+    #[must_use]
+    pub fn barrier(mut self, barrier: bool) -> Self {
+        self.barrier = barrier;
+        self
+    }
+
     /// Draw the drag handle. Use [`Handle::sense`] to add a click sense.
     /// You can also add buttons in the handle, but they won't be interactive if you pass `Sense::click`
     pub fn ui(mut self, ui: &mut Ui, contents: impl FnOnce(&mut Ui)) -> egui::Response {
@@ -307,6 +615,27 @@ impl<'a> Handle<'a> {
         self.handle_response(response.inner.response, ui)
     }
 
+    /// Register the item as barrier and prevent dragging pinned items
+    fn handle_constraints(&mut self, response: &egui::Response) {
+        if self.pinned || self.barrier {
+            self.state.barriers.push(Barrier {
+                idx: self.idx,
+                id: self.id,
+                pos: self.item_pos,
+            });
+        }
+
+        if self.pinned
+            && response.contains_pointer()
+            && matches!(
+                self.state.detection_state,
+                DragDetectionState::CouldBeValidDrag
+            )
+        {
+            self.state.detection_state = DragDetectionState::Cancelled("The item is pinned");
+        }
+    }
+
     fn handle_response(&mut self, response: egui::Response, ui: &mut Ui) -> egui::Response {
         let response = if let Some(sense) = self.sense {
             response.interact(sense)
@@ -315,7 +644,7 @@ impl<'a> Handle<'a> {
         };
 
         if response.contains_pointer() {
-            if self.show_drag_cursor_on_hover {
+            if self.show_drag_cursor_on_hover && !self.pinned {
                 ui.output_mut(|o| o.cursor_icon = CursorIcon::Grab);
             }
             *self.hovering_over_any_handle = true;
@@ -333,7 +662,32 @@ impl<'a> Handle<'a> {
             .length()
         });
 
-        let click_threshold = 1.0;
+        // The picked up item floats in a layer that can't have focus, so its focus is handled
+        // by the item at the drop position instead
+        if self.state.keyboard_reordering
+            && self.state.detection_state.keyboard_item() != Some(self.id)
+        {
+            handle_keyboard(self.state, ui, self.id, self.idx, response.rect);
+        }
+
+        // The payload isn't set again when the item is dropped, so the list can check whether a
+        // drop zone has taken it
+        if let Some(set_payload) = self.payload.take() {
+            if !ui.input(|i| i.pointer.any_released()) {
+                set_payload(ui.ctx());
+                self.state.has_payload = true;
+            }
+        }
+
+        if self.state.multi_select
+            && response.contains_pointer()
+            && !self.state.detection_state.is_dragging()
+            && ui.input(|i| i.pointer.primary_clicked())
+        {
+            self.state.selection_click = Some((self.id, ui.input(|i| i.modifiers)));
+        }
+
+        let click_threshold = self.state.config(ui).click_tolerance;
         let is_above_click_threshold = drag_distance > click_threshold;
 
         if response.contains_pointer()
@@ -348,7 +702,7 @@ impl<'a> Handle<'a> {
                 // This is important so any ScrollArea isn't being dragged while we wait for the click threshold
                 ui.ctx().stop_dragging();
                 if is_above_click_threshold
-                    || pressed_at.elapsed().unwrap_or_default()
+                    || elapsed(ui.input(|i| i.time), pressed_at)
                         > self.state.config(ui).click_tolerance_timeout
                 {
                     self.state.detection_state = DragDetectionState::CouldBeValidDrag;
@@ -356,15 +710,34 @@ impl<'a> Handle<'a> {
             }
         };
 
+        self.handle_constraints(&response);
+
         if response.contains_pointer()
             && matches!(
                 self.state.detection_state,
                 DragDetectionState::CouldBeValidDrag
             )
         {
+            let selection = if self.state.multi_select {
+                if !self.state.selection.contains(self.id) {
+                    self.state.selection.select_only(self.id);
+                }
+                if self.state.selection.ids.len() > 1 {
+                    self.state.selection.ids.clone()
+                } else {
+                    Vec::new()
+                }
+            } else {
+                Vec::new()
+            };
+
+            // The handle sets the payload again while the item is dragged
+            self.state.has_payload = false;
             self.state.detection_state = DragDetectionState::Dragging {
+                selection,
                 id: self.id,
                 offset,
+                origin: self.item_pos,
                 // We set this in the Item
                 dragged_item_size: Vec2::default(),
                 closest_item: (self.id, self.item_pos),
@@ -383,8 +756,18 @@ impl<'a> Handle<'a> {
     }
 }
 
+/// The duration between two egui times (see [`egui::InputState::time`])
+fn elapsed(now: f64, since: f64) -> Duration {
+    Duration::from_secs_f64((now - since).max(0.0))
+}
+
 /// Configuration for drag detection.
+///
+/// Start with a preset ([`DragDropConfig::mouse`], [`DragDropConfig::touch`] or
+/// [`DragDropConfig::touch_scroll`]) and adjust it with the `with_*` methods. The struct is
+/// `#[non_exhaustive]`, so new options can be added without breaking your code.
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct DragDropConfig {
     /// How long does the user have to keep pressing until a drag may begin?
     /// This is useful when dragging and dropping on a touch screen in a scrollable area.
@@ -398,6 +781,13 @@ pub struct DragDropConfig {
     pub click_tolerance: f32,
     /// If we have been holding longer than this duration, a drag will be started even if the pointer has not moved above [`DragDropConfig::click_tolerance`].
     pub click_tolerance_timeout: Duration,
+    /// How close to the edge of the surrounding `ScrollArea` does the pointer have to be
+    /// to scroll while dragging?
+    pub auto_scroll_margin: f32,
+    /// How fast (in points per second) should the `ScrollArea` scroll when the pointer is at its edge?
+    /// The speed decreases linearly until the pointer is [`DragDropConfig::auto_scroll_margin`]
+    /// away from the edge. Set to 0 to disable auto scrolling.
+    pub auto_scroll_speed: f32,
 }
 
 impl Default for DragDropConfig {
@@ -415,6 +805,8 @@ impl DragDropConfig {
             drag_delay: Duration::from_millis(0),
             scroll_tolerance: None,
             click_tolerance_timeout: Duration::from_millis(250),
+            auto_scroll_margin: 50.0,
+            auto_scroll_speed: 1000.0,
         }
     }
 
@@ -427,6 +819,8 @@ impl DragDropConfig {
             click_tolerance: 3.0,
             drag_delay: Duration::from_millis(0),
             click_tolerance_timeout: Duration::from_millis(250),
+            auto_scroll_margin: 50.0,
+            auto_scroll_speed: 1000.0,
         }
     }
 
@@ -438,8 +832,52 @@ impl DragDropConfig {
             click_tolerance: 3.0,
             drag_delay: Duration::from_millis(300),
             click_tolerance_timeout: Duration::from_millis(250),
+            auto_scroll_margin: 50.0,
+            auto_scroll_speed: 1000.0,
         }
     }
+
+    /// Sets [`DragDropConfig::drag_delay`]
+    #[must_use]
+    pub fn with_drag_delay(mut self, drag_delay: Duration) -> Self {
+        self.drag_delay = drag_delay;
+        self
+    }
+
+    /// Sets [`DragDropConfig::scroll_tolerance`]
+    #[must_use]
+    pub fn with_scroll_tolerance(mut self, scroll_tolerance: Option<f32>) -> Self {
+        self.scroll_tolerance = scroll_tolerance;
+        self
+    }
+
+    /// Sets [`DragDropConfig::click_tolerance`]
+    #[must_use]
+    pub fn with_click_tolerance(mut self, click_tolerance: f32) -> Self {
+        self.click_tolerance = click_tolerance;
+        self
+    }
+
+    /// Sets [`DragDropConfig::click_tolerance_timeout`]
+    #[must_use]
+    pub fn with_click_tolerance_timeout(mut self, click_tolerance_timeout: Duration) -> Self {
+        self.click_tolerance_timeout = click_tolerance_timeout;
+        self
+    }
+
+    /// Sets [`DragDropConfig::auto_scroll_margin`]
+    #[must_use]
+    pub fn with_auto_scroll_margin(mut self, auto_scroll_margin: f32) -> Self {
+        self.auto_scroll_margin = auto_scroll_margin;
+        self
+    }
+
+    /// Sets [`DragDropConfig::auto_scroll_speed`]
+    #[must_use]
+    pub fn with_auto_scroll_speed(mut self, auto_scroll_speed: f32) -> Self {
+        self.auto_scroll_speed = auto_scroll_speed;
+        self
+    }
 }
 
 /// [`DragDropUi`] stores the state of the Drag & Drop list.
@@ -460,6 +898,40 @@ impl DragDropUi {
         self
     }
 
+    /// If we are in a `ScrollArea`, scroll while the pointer is near its edge.
+    /// The closer the pointer is to the edge, the faster we scroll.
+    fn auto_scroll(&self, ui: &Ui, pointer_pos: Pos2) {
+        let config = self.config(ui);
+        let margin = config.auto_scroll_margin;
+        if config.auto_scroll_speed <= 0.0 || margin <= 0.0 {
+            return;
+        }
+
+        // Inside a ScrollArea the clip rect is the visible part of the content
+        let viewport = ui.clip_rect();
+        if !viewport.expand(margin).contains(pointer_pos) {
+            return;
+        }
+
+        let speed = |low: f32, high: f32, pos: f32| {
+            let towards_low = ((low + margin - pos) / margin).clamp(0.0, 1.0);
+            let towards_high = ((pos - (high - margin)) / margin).clamp(0.0, 1.0);
+            // Scrolling with a positive delta moves the content down, revealing what's above
+            (towards_low - towards_high) * config.auto_scroll_speed
+        };
+
+        let dt = ui.input(|i| i.stable_dt).min(0.1);
+        let delta = Vec2::new(
+            speed(viewport.left(), viewport.right(), pointer_pos.x),
+            speed(viewport.top(), viewport.bottom(), pointer_pos.y),
+        ) * dt;
+
+        if delta != Vec2::ZERO {
+            ui.scroll_with_delta_animation(delta, egui::style::ScrollAnimation::none());
+            ui.ctx().request_repaint();
+        }
+    }
+
     fn config(&self, ui: &Ui) -> &DragDropConfig {
         if ui.input(egui::InputState::any_touches) {
             self.touch_config.as_ref().unwrap_or(&self.mouse_config)
@@ -475,6 +947,19 @@ impl DragDropUi {
         ui: &mut Ui,
         callback: impl FnOnce(&mut Ui, &mut ItemIterator),
     ) -> DragDropResponse {
+        // A drop zone that was shown after the list accepted the payload of the dropped item
+        let mut dropped_externally = None;
+        if let Some((idx, pass)) = self.pending_external_drop.take() {
+            if was_accepted_in(ui.ctx(), pass) {
+                dropped_externally = Some(idx);
+                if let DragDetectionState::TransitioningBackAfterDragFinished { .. } =
+                    self.detection_state
+                {
+                    self.detection_state = DragDetectionState::None;
+                }
+            }
+        }
+
         // During the first frame, we check if the pointer is actually over any of the item handles and cancel the drag if it isn't
         let mut first_frame = false;
         let config = self.config(ui).clone();
@@ -488,9 +973,8 @@ impl DragDropUi {
                     )
                 {
                     first_frame = true;
-                    self.detection_state = DragDetectionState::PressedWaitingForDelay {
-                        pressed_at: SystemTime::now(),
-                    };
+                    self.detection_state =
+                        DragDetectionState::PressedWaitingForDelay { pressed_at: i.time };
                 }
 
                 let drag_distance = (i.pointer.hover_pos().unwrap_or_default()
@@ -502,7 +986,7 @@ impl DragDropUi {
                 if let DragDetectionState::PressedWaitingForDelay { pressed_at } =
                     self.detection_state
                 {
-                    if pressed_at.elapsed().unwrap_or_default() >= config.drag_delay {
+                    if elapsed(i.time, pressed_at) >= config.drag_delay {
                         if is_below_scroll_threshold {
                             self.detection_state =
                                 DragDetectionState::WaitingForClickThreshold { pressed_at };
@@ -520,7 +1004,7 @@ impl DragDropUi {
                 if let DragDetectionState::WaitingForClickThreshold { pressed_at } =
                     self.detection_state
                 {
-                    if pressed_at.elapsed().unwrap_or_default() >= config.click_tolerance_timeout {
+                    if elapsed(i.time, pressed_at) >= config.click_tolerance_timeout {
                         self.detection_state = DragDetectionState::CouldBeValidDrag;
                     }
                 }
@@ -532,22 +1016,89 @@ impl DragDropUi {
             .or_else(|| self.detection_state.last_pointer_pos());
 
         let dragged_item_rect = if let DragDetectionState::Dragging {
-            offset,
-            dragged_item_size,
-            ..
+            dragged_item_size, ..
         } = &self.detection_state
         {
-            Some(Rect::from_min_size(
-                pointer_pos.unwrap_or_default() + *offset,
-                *dragged_item_size,
-            ))
+            self.dragged_item_pos(pointer_pos.unwrap_or_default(), *ui.layout())
+                .map(|pos| Rect::from_min_size(pos, *dragged_item_size))
         } else {
             None
         };
 
-        let mut item_iter = ItemIterator::new(self, dragged_item_rect, *ui.layout());
+        let group = self.group;
+        let mut group_state = group.map(|group| {
+            let mut group_state = GroupState::load(ui.ctx(), group.id);
+            group_state.begin_list(
+                group.list_id,
+                &self.detection_state,
+                dragged_item_rect,
+                pointer_pos,
+            );
+            group_state
+        });
+
+        // An item of another list in the group is hovering this list
+        let foreign_drag = group_state.as_ref().zip(group).and_then(|(state, group)| {
+            state.foreign_target(group.list_id).map(|drag| {
+                (
+                    drag.dragged_item_rect,
+                    drag.target_item,
+                    drag.target_after_last,
+                )
+            })
+        });
+        // An item of this list is hovering another list in the group
+        let cross_list_target = group_state.as_ref().zip(group).and_then(|(state, group)| {
+            state
+                .drag
+                .as_ref()
+                .filter(|drag| drag.source_list == group.list_id)
+                .and_then(|drag| {
+                    drag.target_list.map(|to_list| CrossListUpdate {
+                        from_list: group.list_id,
+                        from: drag.source_idx,
+                        to_list,
+                        to: drag.target_idx,
+                    })
+                })
+        });
+
+        // A payload that was dragged from outside the list is hovering the list
+        let external_drop = self.external_drop.filter(|zone| {
+            foreign_drag.is_none()
+                && !self.detection_state.is_dragging()
+                && zone.has_payload(ui.ctx())
+        });
+        let external_rect = external_drop
+            .and(pointer_pos)
+            .filter(|pos| self.items_rect.contains(*pos))
+            .map(|pos| Rect::from_center_size(pos, Vec2::splat(ui.spacing().interact_size.y)));
+        if external_rect.is_none() {
+            self.external_hover = None;
+        }
+        let external_hover = external_rect.map(|rect| {
+            let hover = self
+                .external_hover
+                .get_or_insert_with(ExternalHover::default);
+            (rect, hover.target_item, hover.after_last)
+        });
+
+        self.barriers.clear();
+        let mut item_iter = if self.tree_indent.is_some() {
+            ItemIterator::new(self, None, *ui.layout()).with_tree()
+        } else if let Some((rect, target_item, after_last)) = foreign_drag.or(external_hover) {
+            ItemIterator::new(self, Some(rect), *ui.layout())
+                .with_foreign_drag(target_item, after_last)
+        } else {
+            ItemIterator::new(
+                self,
+                dragged_item_rect.filter(|_| cross_list_target.is_none()),
+                *ui.layout(),
+            )
+        };
         callback(ui, &mut item_iter);
 
+        let grid_target = item_iter.grid_target();
         let ItemIterator {
             source_item,
             hovering_over_any_handle,
@@ -555,11 +1106,97 @@ impl DragDropUi {
             mark_next_as_closest_item,
             last_item,
             hovering_last_item,
+            foreign_insert,
+            mut items_rect,
+            tree_rows,
+            item_ids,
+            dragged_indices,
+            keyboard_gap,
+            keyboard_source,
+            keyboard_action_rect,
+            line_target,
+            placeholder_rect,
             ..
         } = item_iter;
 
+        self.gap_rect = placeholder_rect;
+        let preview_shapes = self.preview_shapes.take();
+        if let Some(rect) = placeholder_rect {
+            self.placeholder.paint(ui, rect, preview_shapes.as_ref());
+        }
+        if let Some((_, before, after)) = line_target {
+            paint_insertion_line(ui, before, after, ui.layout().is_horizontal());
+        }
+
+        if let DragDetectionState::KeyboardDragging {
+            dragged_item_size, ..
+        } = self.detection_state
+        {
+            // The item will be dropped after the last item
+            let gap = keyboard_gap.unwrap_or_else(|| ui.allocate_space(dragged_item_size).1);
+            if self.keyboard_gap_pos != Some(gap.min) {
+                ui.scroll_to_rect(gap, None);
+            }
+            self.keyboard_gap_pos = Some(gap.min);
+        }
+
+        if let Some((id, modifiers)) = self.selection_click.take() {
+            self.selection.click(id, modifiers, &item_ids);
+            ui.ctx().request_repaint();
+        }
+
+        let tree_target = match (
+            self.tree_indent,
+            tree_rows,
+            self.detection_state.dragged_item(),
+            dragged_item_rect,
+            pointer_pos,
+        ) {
+            (Some(indent), Some(rows), Some(id), Some(rect), Some(pointer)) => {
+                tree_drop_target(&rows, id, rect, pointer, indent)
+            }
+            _ => None,
+        };
+        if let Some((_, indicator)) = &tree_target {
+            indicator.paint(ui);
+        }
+
+        if let (Some(group_state), Some(group)) = (&mut group_state, group) {
+            if let Some(drag) = &mut group_state.drag {
+                if drag.source_list != group.list_id {
+                    // Allocate some space in empty lists, so there is something to drop on
+                    if last_item.is_none() {
+                        let (_, rect) = ui.allocate_space(drag.dragged_item_rect.size());
+                        items_rect = items_rect.union(rect);
+                    }
+                    if foreign_drag.is_some() {
+                        let hover = ExternalHover::from_iterator(foreign_insert, last_item);
+                        drag.target_item = hover.target_item;
+                        drag.target_idx = hover.target_idx;
+                        drag.target_after_last = hover.after_last;
+                    }
+                }
+            }
+            group_state.list_rects.insert(group.list_id, items_rect);
+        }
+
+        if let Some(zone) = self.external_drop {
+            // Allocate some space in empty lists, so there is something to drop on
+            if last_item.is_none() && zone.has_payload(ui.ctx()) {
+                let (_, rect) = ui.allocate_space(Vec2::splat(ui.spacing().interact_size.y));
+                items_rect = items_rect.union(rect);
+            }
+        }
+        if self.external_hover.is_some() {
+            self.external_hover = Some(ExternalHover::from_iterator(foreign_insert, last_item));
+        }
+        self.items_rect = items_rect;
+
         // This is only some if we're hoving over the last item
-        let hovering_last_item = if mark_next_as_closest_item.is_some() {
+        let hovering_last_item = if let Some(insert_before) = grid_target {
+            closest_item = Some((0.0, insert_before.or(last_item)));
+            insert_before.is_none()
+        } else if mark_next_as_closest_item.is_some() {
             closest_item = Some((0.0, last_item));
             // We're only doing this once or we wouldn't be able to move back to the
             // second to last item
@@ -595,6 +1232,10 @@ impl DragDropUi {
                 *hovering_idx_out = hovering_idx;
                 *hovering_last_item_out = hovering_last_item;
             }
+            if let Some((target_idx, ..)) = line_target {
+                *hovering_idx_out = target_idx;
+                *hovering_last_item_out = false;
+            }
             if let Some(pointer_pos) = pointer_pos {
                 *last_pointer_pos_out = pointer_pos;
             }
@@ -602,18 +1243,25 @@ impl DragDropUi {
                 *source_idx_out = source_item.0;
             }
         }
+        self.clamp_to_barriers();
 
-        if self.detection_state.is_dragging() {
-            if let Some(pointer_pos) = pointer_pos {
-                // If we are in a ScrollArea, allow for scrolling while dragging
-                ui.scroll_to_rect(Rect::from_center_size(pointer_pos, Vec2::splat(50.0)), None);
-            }
+        // Scroll the list the item is hovering, which might be another list of the group
+        let is_drag_target = (self.detection_state.is_dragging() && cross_list_target.is_none())
+            || foreign_drag.is_some();
+        if let (true, Some(pointer_pos)) = (is_drag_target, pointer_pos) {
+            self.auto_scroll(ui, pointer_pos);
         }
 
         let mut response = if drag_phase_changed_this_frame {
             DragDropResponse {
                 finished: false,
                 update: None,
+                multi_update: None,
+                selection: Vec::new(),
+                cross_list_update: None,
+                tree_update: None,
+                dropped_externally: None,
+                external_payload: None,
                 state: self.detection_state.clone(),
                 cancellation_reason: None,
                 has_changed: false,
@@ -635,6 +1283,12 @@ impl DragDropUi {
                         hovering_idx
                     },
                 }),
+                multi_update: None,
+                selection: Vec::new(),
+                cross_list_update: None,
+                tree_update: None,
+                dropped_externally: None,
+                external_payload: None,
                 state: self.detection_state.clone(),
                 cancellation_reason: None,
                 has_changed: should_update,
@@ -643,28 +1297,111 @@ impl DragDropUi {
             DragDropResponse {
                 finished: false,
                 update: None,
+                multi_update: None,
+                selection: Vec::new(),
+                cross_list_update: None,
+                tree_update: None,
+                dropped_externally: None,
+                external_payload: None,
                 state: self.detection_state.clone(),
                 cancellation_reason: None,
                 has_changed: false,
             }
         };
 
+        if self.tree_indent.is_some() {
+            // The flat indices don't make sense for a tree
+            response.update = None;
+        }
+
+        if self.detection_state.dragged_count() > 1 {
+            if let Some(update) = response.update.take() {
+                response.multi_update = Some(MultiDragUpdate {
+                    from: dragged_indices,
+                    to: update.to,
+                });
+            }
+        }
+        if self.multi_select {
+            response.selection.clone_from(&self.selection.ids);
+        }
+
+        response.dropped_externally = dropped_externally;
         if pointer_released {
+            if let (Some(zone), Some(hover)) = (external_drop, self.external_hover.take()) {
+                if let Some(payload) = zone.take_payload(ui.ctx()) {
+                    response.external_payload = Some((hover.target_idx, payload));
+                }
+            }
+
             if let Some(dragged_item) = self.detection_state.dragged_item() {
                 response.finished = true;
-
-                self.detection_state = DragDetectionState::TransitioningBackAfterDragFinished {
-                    dragged_item_size: self.detection_state.dragged_item_size(),
-                    id: dragged_item,
-                };
+                response.tree_update = tree_target.map(|(update, _)| update);
+
+                // The payload is only taken if a drop zone shown before the list accepted it
+                let pass = ui.ctx().cumulative_pass_nr();
+                let accepted_externally = self.has_payload
+                    && (!DragAndDrop::has_any_payload(ui.ctx()) || was_accepted_in(ui.ctx(), pass));
+                if self.has_payload && !accepted_externally {
+                    // A drop zone shown after the list might still accept it
+                    let idx = response.update.as_ref().map_or(
+                        self.detection_state.source_idx().unwrap_or_default(),
+                        |update| {
+                            if update.to > update.from {
+                                update.to - 1
+                            } else {
+                                update.to
+                            }
+                        },
+                    );
+                    self.pending_external_drop = Some((idx, pass));
+                }
+                self.has_payload = false;
+
+                if accepted_externally {
+                    // The item was dropped outside the list, so there is nothing to transition back
+                    response.update = None;
+                    response.multi_update = None;
+                    response.dropped_externally = self.detection_state.source_idx();
+                    self.detection_state = DragDetectionState::None;
+                    if let Some(group_state) = &mut group_state {
+                        group_state.drag = None;
+                    }
+                } else if let Some(update) = cross_list_target {
+                    // The item was dropped in another list, so there is nothing to transition back
+                    response.update = None;
+                    response.cross_list_update = Some(update);
+                    self.detection_state = DragDetectionState::None;
+                    if let Some(group_state) = &mut group_state {
+                        group_state.drag = None;
+                    }
+                } else {
+                    self.detection_state = DragDetectionState::TransitioningBackAfterDragFinished {
+                        dragged_item_size: self.detection_state.dragged_item_size(),
+                        id: dragged_item,
+                    };
+                }
             }
         }
 
+        if let (Some(group_state), Some(group)) = (group_state, group) {
+            group_state.store(ui.ctx(), group.id);
+        }
+
+        self.apply_keyboard_action(
+            ui,
+            item_ids.len(),
+            keyboard_source,
+            keyboard_action_rect,
+            &mut response,
+        );
+
         ui.input(|input| {
             if !input.pointer.any_down()
                 && !matches!(
                     self.detection_state,
                     DragDetectionState::TransitioningBackAfterDragFinished { .. }
+                        | DragDetectionState::KeyboardDragging { .. }
                 )
             {
                 if let DragDetectionState::Cancelled(msg) = self.detection_state {
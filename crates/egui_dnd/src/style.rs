@@ -0,0 +1,131 @@
+use egui::epaint::ClippedShape;
+use egui::{lerp, Rect, Shadow, Stroke, Ui, Vec2};
+
+/// How the position the dragged item would be dropped at is shown, see [`crate::Dnd::with_drop_indicator`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DropIndicator {
+    /// The other items move apart, making space for the dragged item
+    #[default]
+    Gap,
+    /// The other items keep their position and a line is drawn where the dragged item would be
+    /// inserted. The update is only applied when the item is dropped.
+    Line,
+}
+
+/// What is drawn in the space the dragged item would be dropped at (or its original slot
+/// for [`DropIndicator::Line`]), see [`crate::Dnd::with_placeholder`]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Placeholder {
+    /// Nothing, just empty space
+    #[default]
+    Empty,
+    /// A faded copy of the dragged item, with the given opacity
+    Ghost(f32),
+    /// A faint outline of the dragged item
+    Outline,
+}
+
+/// How the floating preview of the dragged item is drawn, see [`crate::Dnd::with_preview_style`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PreviewStyle {
+    /// Opacity of the dragged item, between 0 and 1
+    pub opacity: f32,
+    /// Scale of the dragged item, around its center
+    pub scale: f32,
+    /// Shadow drawn behind the dragged item
+    pub shadow: Option<Shadow>,
+}
+
+impl Default for PreviewStyle {
+    fn default() -> Self {
+        Self {
+            opacity: 1.0,
+            scale: 1.0,
+            shadow: None,
+        }
+    }
+}
+
+impl PreviewStyle {
+    /// A slightly enlarged item with a drop shadow, as if it was lifted from the list
+    #[must_use]
+    pub fn lifted() -> Self {
+        Self {
+            opacity: 1.0,
+            scale: 1.03,
+            shadow: Some(Shadow {
+                offset: Vec2::new(0.0, 6.0),
+                blur: 16.0,
+                spread: 0.0,
+                color: egui::Color32::from_black_alpha(60),
+            }),
+        }
+    }
+}
+
+impl Placeholder {
+    /// Paint the placeholder in `rect`. `preview` contains the shapes of the dragged item
+    /// and the rect they were painted in.
+    pub(crate) fn paint(self, ui: &Ui, rect: Rect, preview: Option<&(Rect, Vec<ClippedShape>)>) {
+        match self {
+            Placeholder::Empty => {}
+            Placeholder::Ghost(opacity) => {
+                if let Some((preview_rect, shapes)) = preview {
+                    let mut painter = ui.painter().clone();
+                    painter.multiply_opacity(opacity);
+                    let delta = rect.min - preview_rect.min;
+                    for ClippedShape { shape, .. } in shapes {
+                        let mut shape = shape.clone();
+                        shape.translate(delta);
+                        painter.add(shape);
+                    }
+                }
+            }
+            Placeholder::Outline => {
+                let visuals = ui.visuals();
+                ui.painter().rect(
+                    rect,
+                    visuals.widgets.noninteractive.rounding,
+                    visuals.faint_bg_color,
+                    Stroke::new(1.0, visuals.weak_text_color()),
+                );
+            }
+        }
+    }
+}
+
+/// The line drawn by [`DropIndicator::Line`] between the items `before` and `after`
+pub(crate) fn paint_insertion_line(
+    ui: &Ui,
+    before: Option<Rect>,
+    after: Option<Rect>,
+    horizontal: bool,
+) {
+    let stroke = Stroke::new(2.0, ui.visuals().selection.bg_fill);
+    let spacing = ui.spacing().item_spacing;
+    if horizontal {
+        // In wrapping layouts the items might be in different rows, then the line is drawn in
+        // front of the item after
+        let (x, rect) = match (before, after) {
+            (Some(before), Some(after)) if before.y_range().intersects(after.y_range()) => (
+                lerp(before.right()..=after.left(), 0.5),
+                before.union(after),
+            ),
+            (_, Some(after)) => (after.left() - spacing.x / 2.0, after),
+            (Some(before), None) => (before.right() + spacing.x / 2.0, before),
+            (None, None) => return,
+        };
+        ui.painter().vline(x, rect.y_range(), stroke);
+    } else {
+        let (y, rect) = match (before, after) {
+            (Some(before), Some(after)) => (
+                lerp(before.bottom()..=after.top(), 0.5),
+                before.union(after),
+            ),
+            (None, Some(after)) => (after.top() - spacing.y / 2.0, after),
+            (Some(before), None) => (before.bottom() + spacing.y / 2.0, before),
+            (None, None) => return,
+        };
+        ui.painter().hline(rect.x_range(), y, stroke);
+    }
+}
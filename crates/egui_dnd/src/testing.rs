@@ -0,0 +1,249 @@
+//! Helpers to test drag and drop lists without a window.
+//!
+//! [`DndTester`] runs a headless [`egui::Context`] frame by frame and feeds it synthetic pointer
+//! and touch events, so you can check the [`crate::DragDropResponse`] of your list:
+//! ```rust
+//! use egui::pos2;
+//! use egui_dnd::{dnd, testing::DndTester};
+//!
+//! let mut items = vec!["a", "b", "c"];
+//! let mut tester = DndTester::new();
+//! let mut show = |ui: &mut egui::Ui| {
+//!     dnd(ui, "list").show_vec(&mut items, |ui, item, handle, _state| {
+//!         handle.ui(ui, |ui| {
+//!             ui.set_min_size(egui::vec2(100.0, 20.0));
+//!             ui.label(*item);
+//!         });
+//!     })
+//! };
+//!
+//! // Show the list once, so the items have a position
+//! tester.run(&mut show);
+//! let response = tester.drag(pos2(20.0, 15.0), pos2(20.0, 70.0), &mut show);
+//! assert!(response.is_drag_finished());
+//! assert_eq!(items, vec!["b", "c", "a"]);
+//! ```
+
+use std::time::Duration;
+
+use egui::{
+    CentralPanel, Context, Event, Key, Modifiers, PlatformOutput, PointerButton, Pos2, RawInput,
+    Rect, TouchDeviceId, TouchId, TouchPhase, Ui, Vec2,
+};
+
+/// The simulated time between two frames
+const FRAME_TIME: f32 = 1.0 / 60.0;
+
+/// Runs a headless [`egui::Context`] and feeds it synthetic events, see the [module docs](self).
+///
+/// Events are queued and sent with the next call to [`DndTester::run`].
+/// The time advances by one frame (1/60 s) per call, use [`DndTester::wait`] to advance it further,
+/// e.g. to get past [`crate::DragDropConfig::drag_delay`].
+pub struct DndTester {
+    ctx: Context,
+    screen_rect: Rect,
+    time: f64,
+    events: Vec<Event>,
+    pointer_pos: Pos2,
+    modifiers: Modifiers,
+    /// The id of the active touch, if the pointer is a finger
+    touch: Option<TouchId>,
+    next_touch_id: u64,
+    output: PlatformOutput,
+}
+
+impl Default for DndTester {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DndTester {
+    /// Create a tester with a 400x400 screen
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            ctx: Context::default(),
+            screen_rect: Rect::from_min_size(Pos2::ZERO, Vec2::splat(400.0)),
+            time: 0.0,
+            events: Vec::new(),
+            pointer_pos: Pos2::ZERO,
+            modifiers: Modifiers::NONE,
+            touch: None,
+            next_touch_id: 0,
+            output: PlatformOutput::default(),
+        }
+    }
+
+    /// Sets the size of the simulated screen
+    #[must_use]
+    pub fn with_screen_size(mut self, size: Vec2) -> Self {
+        self.screen_rect = Rect::from_min_size(Pos2::ZERO, size);
+        self
+    }
+
+    /// The context the frames are run with
+    pub fn ctx(&self) -> &Context {
+        &self.ctx
+    }
+
+    /// The current position of the pointer
+    pub fn pointer_pos(&self) -> Pos2 {
+        self.pointer_pos
+    }
+
+    /// The platform output of the last frame, e.g. to check the screen reader announcements of
+    /// [`crate::Dnd::with_keyboard_reordering`]
+    pub fn output(&self) -> &PlatformOutput {
+        &self.output
+    }
+
+    /// Run a frame with the queued events, showing `add_contents` in a [`CentralPanel`].
+    /// Returns the result of `add_contents`, e.g. the [`crate::DragDropResponse`].
+    pub fn run<R>(&mut self, mut add_contents: impl FnMut(&mut Ui) -> R) -> R {
+        let input = RawInput {
+            screen_rect: Some(self.screen_rect),
+            time: Some(self.time),
+            predicted_dt: FRAME_TIME,
+            modifiers: self.modifiers,
+            events: std::mem::take(&mut self.events),
+            ..RawInput::default()
+        };
+
+        // egui might run multiple passes, we return the result of the last one
+        let mut result = None;
+        let output = self.ctx.run(input, |ctx| {
+            CentralPanel::default().show(ctx, |ui| {
+                result = Some(add_contents(ui));
+            });
+        });
+        self.output = output.platform_output;
+        self.time += f64::from(FRAME_TIME);
+
+        result.expect("CentralPanel should always show its contents")
+    }
+
+    /// Advance the time of the next frame
+    pub fn wait(&mut self, duration: Duration) {
+        self.time += duration.as_secs_f64();
+    }
+
+    /// Set the modifier keys that are held down from the next frame on,
+    /// e.g. [`Modifiers::COMMAND`] to ctrl-click with [`DndTester::press`] and [`DndTester::release`]
+    pub fn set_modifiers(&mut self, modifiers: Modifiers) {
+        self.modifiers = modifiers;
+    }
+
+    /// Move the pointer (or the active touch) to `pos`
+    pub fn pointer_move(&mut self, pos: Pos2) {
+        self.pointer_pos = pos;
+        if let Some(id) = self.touch {
+            self.events.push(touch_event(id, TouchPhase::Move, pos));
+        }
+        self.events.push(Event::PointerMoved(pos));
+    }
+
+    /// Move the pointer to `pos` and press the primary button
+    pub fn press(&mut self, pos: Pos2) {
+        self.pointer_move(pos);
+        self.events.push(pointer_button(pos, true, self.modifiers));
+    }
+
+    /// Release the primary button at the current position
+    pub fn release(&mut self) {
+        self.events
+            .push(pointer_button(self.pointer_pos, false, self.modifiers));
+    }
+
+    /// Start touching the screen at `pos`.
+    /// Like egui integrations do, pointer events are sent as well.
+    pub fn touch_start(&mut self, pos: Pos2) {
+        let id = TouchId(self.next_touch_id);
+        self.next_touch_id += 1;
+        self.touch = Some(id);
+        self.events.push(touch_event(id, TouchPhase::Start, pos));
+        self.pointer_pos = pos;
+        self.events.push(Event::PointerMoved(pos));
+        self.events.push(pointer_button(pos, true, self.modifiers));
+    }
+
+    /// Move the active touch to `pos`
+    pub fn touch_move(&mut self, pos: Pos2) {
+        self.pointer_move(pos);
+    }
+
+    /// Lift the finger of the active touch
+    pub fn touch_end(&mut self) {
+        if let Some(id) = self.touch.take() {
+            self.events
+                .push(touch_event(id, TouchPhase::End, self.pointer_pos));
+            self.events
+                .push(pointer_button(self.pointer_pos, false, self.modifiers));
+            self.events.push(Event::PointerGone);
+        }
+    }
+
+    /// Press and release `key`, e.g. to reorder with [`crate::Dnd::with_keyboard_reordering`].
+    /// Several keys can be queued for the same frame.
+    pub fn key(&mut self, key: Key) {
+        for pressed in [true, false] {
+            self.events.push(Event::Key {
+                key,
+                physical_key: None,
+                pressed,
+                repeat: false,
+                modifiers: self.modifiers,
+            });
+        }
+    }
+
+    /// Drag with the mouse from `from` to `to` and release, running a frame for each step.
+    /// The pointer first moves a few points in small steps, so it's still over the handle when
+    /// the drag starts. Returns the result of the frame the pointer was released in.
+    pub fn drag<R>(
+        &mut self,
+        from: Pos2,
+        to: Pos2,
+        mut add_contents: impl FnMut(&mut Ui) -> R,
+    ) -> R {
+        self.press(from);
+        self.run(&mut add_contents);
+
+        let direction = (to - from).normalized();
+        for i in 1..=3 {
+            #[allow(clippy::cast_precision_loss)]
+            self.pointer_move(from + direction * i as f32);
+            self.run(&mut add_contents);
+        }
+
+        let start = self.pointer_pos;
+        let steps = 10;
+        for i in 1..=steps {
+            #[allow(clippy::cast_precision_loss)]
+            self.pointer_move(start + (to - start) * (i as f32 / steps as f32));
+            self.run(&mut add_contents);
+        }
+
+        self.release();
+        self.run(&mut add_contents)
+    }
+}
+
+fn pointer_button(pos: Pos2, pressed: bool, modifiers: Modifiers) -> Event {
+    Event::PointerButton {
+        pos,
+        button: PointerButton::Primary,
+        pressed,
+        modifiers,
+    }
+}
+
+fn touch_event(id: TouchId, phase: TouchPhase, pos: Pos2) -> Event {
+    Event::Touch {
+        device_id: TouchDeviceId(0),
+        id,
+        phase,
+        pos,
+        force: None,
+    }
+}
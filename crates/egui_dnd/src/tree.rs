@@ -0,0 +1,203 @@
+use egui::{Id, Pos2, Rect, Stroke, Ui};
+
+use crate::utils::move_tree_item;
+
+/// An instruction to move an item in a tree, returned by [`crate::Dnd::show_tree`].
+///
+/// Paths are the indices of the item and its ancestors, starting at the root level,
+/// e.g. `[2, 0]` is the first child of the third root item.
+/// The item at `from_path` should be removed from the tree and inserted at `to_path`.
+/// `to_path` is the path the item will have **after** it has been removed, so you can
+/// apply the update by removing and inserting the item, or use [`TreeDragUpdate::apply`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TreeDragUpdate {
+    /// Path of the item to move
+    pub from_path: Vec<usize>,
+    /// Path where the item should be inserted, after it has been removed from `from_path`
+    pub to_path: Vec<usize>,
+}
+
+impl TreeDragUpdate {
+    /// Utility function to apply the update to a tree of Vecs.
+    /// `children` should return the children of an item (e.g. `|item| &mut item.children`).
+    pub fn apply<T>(&self, roots: &mut Vec<T>, children: impl Fn(&mut T) -> &mut Vec<T>) {
+        move_tree_item(&self.from_path, &self.to_path, roots, children);
+    }
+}
+
+/// A row of a tree, as shown by [`crate::Dnd::show_tree`]
+#[derive(Debug, Clone)]
+pub(crate) struct TreeRow {
+    pub id: Id,
+    pub depth: usize,
+    pub rect: Rect,
+}
+
+/// Where the dragged item would be dropped, used to draw the indicator
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum TreeDropIndicator {
+    /// The item would become the first child of the row
+    Child(Rect),
+    /// The item would be inserted as a sibling, at the line
+    Line { y: f32, left: f32, right: f32 },
+}
+
+impl TreeDropIndicator {
+    pub fn paint(self, ui: &Ui) {
+        let stroke = Stroke::new(2.0, ui.visuals().selection.bg_fill);
+        match self {
+            TreeDropIndicator::Child(rect) => {
+                ui.painter()
+                    .rect_stroke(rect, ui.visuals().widgets.hovered.rounding, stroke);
+            }
+            TreeDropIndicator::Line { y, left, right } => {
+                ui.painter().hline(left..=right, y, stroke);
+            }
+        }
+    }
+}
+
+/// Calculate the paths of all rows from their depths
+fn paths<'a>(depths: impl Iterator<Item = &'a usize>) -> Vec<Vec<usize>> {
+    let mut current: Vec<usize> = Vec::new();
+    depths
+        .map(|&depth| {
+            if depth < current.len() {
+                current.truncate(depth + 1);
+                current[depth] += 1;
+            } else {
+                // Deeper than the previous row, so this is a first child.
+                // Missing levels (invalid input) are filled with 0.
+                current.resize(depth + 1, 0);
+            }
+            current.clone()
+        })
+        .collect()
+}
+
+/// Calculate where the item `source` would be dropped.
+///
+/// Dropping on the middle half of a row makes the item the first child of the row, dropping near
+/// the top or bottom edge inserts it as a sibling. When inserting between rows, the depth is
+/// chosen based on the horizontal position of the dragged item.
+pub(crate) fn tree_drop_target(
+    rows: &[TreeRow],
+    source: Id,
+    dragged_item_rect: Rect,
+    pointer: Pos2,
+    indent: f32,
+) -> Option<(TreeDragUpdate, TreeDropIndicator)> {
+    let source_idx = rows.iter().position(|row| row.id == source)?;
+    let source_depth = rows[source_idx].depth;
+    let subtree_end = rows[source_idx + 1..]
+        .iter()
+        .position(|row| row.depth <= source_depth)
+        .map_or(rows.len(), |i| source_idx + 1 + i);
+
+    let from_path = paths(rows.iter().map(|row| &row.depth)).swap_remove(source_idx);
+
+    // The tree without the dragged item and its children, the to_path is calculated in this tree
+    let remaining: Vec<&TreeRow> = rows[..source_idx]
+        .iter()
+        .chain(&rows[subtree_end..])
+        .collect();
+
+    let list_left = rows
+        .iter()
+        .map(|row| row.rect.left())
+        .fold(f32::INFINITY, f32::min);
+    let list_right = rows
+        .iter()
+        .map(|row| row.rect.right())
+        .fold(f32::NEG_INFINITY, f32::max);
+
+    let (hovered_idx, hovered_row) = rows.iter().enumerate().min_by(|(_, a), (_, b)| {
+        let distance = |row: &TreeRow| (pointer.y - row.rect.y_range().clamp(pointer.y)).abs();
+        distance(a).total_cmp(&distance(b))
+    })?;
+
+    // Can't drop the item on itself or its children
+    if (source_idx..subtree_end).contains(&hovered_idx) {
+        return None;
+    }
+
+    let remaining_paths = paths(remaining.iter().map(|row| &row.depth));
+    let hovered = remaining.iter().position(|row| row.id == hovered_row.id)?;
+
+    let rect = hovered_row.rect;
+    let fraction = ((pointer.y - rect.top()) / rect.height()).clamp(0.0, 1.0);
+
+    if (0.25..=0.75).contains(&fraction) {
+        let mut to_path = remaining_paths[hovered].clone();
+        to_path.push(0);
+        return Some((
+            TreeDragUpdate { from_path, to_path },
+            TreeDropIndicator::Child(rect),
+        ));
+    }
+
+    // Insert after this row
+    let after = if fraction < 0.25 {
+        if hovered == 0 {
+            return Some((
+                TreeDragUpdate {
+                    from_path,
+                    to_path: vec![0],
+                },
+                TreeDropIndicator::Line {
+                    y: rect.top(),
+                    left: list_left,
+                    right: list_right,
+                },
+            ));
+        }
+        hovered - 1
+    } else {
+        hovered
+    };
+
+    let after_row = remaining[after];
+    let mut to_path = remaining_paths[after].clone();
+    let next_depth = remaining.get(after + 1).map_or(0, |row| row.depth);
+
+    let depth = if next_depth > after_row.depth {
+        // The next row is a child of this row, so we have to become a child as well
+        to_path.push(0);
+        after_row.depth + 1
+    } else {
+        // Moving the item by one indent to the left or right changes the depth by one
+        #[allow(
+            clippy::cast_possible_truncation,
+            clippy::cast_sign_loss,
+            clippy::cast_precision_loss
+        )]
+        let desired = (source_depth as f32 + (dragged_item_rect.left() - list_left) / indent)
+            .round()
+            .max(0.0) as usize;
+        let depth = desired.clamp(next_depth, after_row.depth);
+        to_path.truncate(depth + 1);
+        to_path[depth] += 1;
+        depth
+    };
+
+    // The line is drawn between the row and the row that is shown below it
+    let y = rows
+        .iter()
+        .skip_while(|row| row.id != after_row.id)
+        .nth(1)
+        .map_or(after_row.rect.bottom(), |next| {
+            egui::lerp(after_row.rect.bottom()..=next.rect.top(), 0.5)
+        });
+
+    #[allow(clippy::cast_precision_loss)]
+    let left = list_left + depth as f32 * indent;
+
+    Some((
+        TreeDragUpdate { from_path, to_path },
+        TreeDropIndicator::Line {
+            y,
+            left,
+            right: list_right,
+        },
+    ))
+}
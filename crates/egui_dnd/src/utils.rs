@@ -41,3 +41,114 @@ pub fn shift_vec<T>(source_idx: usize, target_idx: usize, vec: &mut [T]) {
         );
     }
 }
+
+/// Move multiple items in a slice according to the drag and drop logic, e.g. to apply a
+/// [`crate::MultiDragUpdate`].
+///
+/// The items at `source_indices` are moved next to each other, keeping their order, so that they
+/// are inserted before the item that was at `target_idx` (like [`shift_vec`]).
+///
+/// # Example
+///
+/// ```rust
+/// use egui_dnd::utils::shift_vec_multi;
+///
+/// let mut v = vec![1, 2, 3, 4, 5];
+/// shift_vec_multi(&[0, 2], 4, &mut v);
+/// assert_eq!(v, [2, 4, 1, 3, 5]);
+/// shift_vec_multi(&[2, 4], 0, &mut v);
+/// assert_eq!(v, [1, 5, 2, 4, 3]);
+/// ```
+///
+/// # Panics
+/// Panics if any index in `source_indices` is `>= len()` or `target_idx > len()`
+pub fn shift_vec_multi<T>(source_indices: &[usize], target_idx: usize, vec: &mut [T]) {
+    let mut sources = source_indices.to_vec();
+    sources.sort_unstable();
+    sources.dedup();
+
+    // Move the items before the target to the end of the block, starting with the last one
+    let mut insert_idx = target_idx;
+    for &source_idx in sources.iter().rev().filter(|idx| **idx < target_idx) {
+        shift_vec(source_idx, insert_idx, vec);
+        insert_idx -= 1;
+    }
+
+    // Move the items after the target to the start of the block, starting with the first one
+    let after_target = sources.iter().filter(|idx| **idx >= target_idx);
+    for (insert_idx, &source_idx) in (target_idx..).zip(after_target) {
+        shift_vec(source_idx, insert_idx, vec);
+    }
+}
+
+/// Move an item from one vec to another, e.g. to apply a [`crate::CrossListUpdate`].
+///
+/// Removes the item at `source_idx` from `source` and inserts it at `target_idx` in `target`.
+///
+/// # Example
+///
+/// ```rust
+/// use egui_dnd::utils::move_between_vecs;
+///
+/// let mut todo = vec![1, 2, 3];
+/// let mut done = vec![4, 5];
+/// move_between_vecs(1, 1, &mut todo, &mut done);
+/// assert_eq!(todo, [1, 3]);
+/// assert_eq!(done, [4, 2, 5]);
+/// ```
+///
+/// # Panics
+/// Panics if `source_idx >= source.len()` or `target_idx > target.len()`
+pub fn move_between_vecs<T>(
+    source_idx: usize,
+    target_idx: usize,
+    source: &mut Vec<T>,
+    target: &mut Vec<T>,
+) {
+    let item = source.remove(source_idx);
+    target.insert(target_idx, item);
+}
+
+/// Move an item in a tree, e.g. to apply a [`crate::TreeDragUpdate`].
+///
+/// Removes the item at `source_path` and inserts it at `target_path`. `target_path` is the path
+/// in the tree after the item has been removed. `children` should return the children of an item.
+///
+/// # Example
+///
+/// ```rust
+/// use egui_dnd::utils::move_tree_item;
+///
+/// struct Node(u32, Vec<Node>);
+///
+/// let mut tree = vec![Node(1, vec![Node(2, vec![])]), Node(3, vec![])];
+/// // Move 2 to be the first child of 3
+/// move_tree_item(&[0, 0], &[1, 0], &mut tree, |node| &mut node.1);
+/// assert!(tree[0].1.is_empty());
+/// assert_eq!(tree[1].1[0].0, 2);
+/// ```
+///
+/// # Panics
+/// Panics if either path is empty or doesn't exist in the tree
+pub fn move_tree_item<T>(
+    source_path: &[usize],
+    target_path: &[usize],
+    roots: &mut Vec<T>,
+    children: impl Fn(&mut T) -> &mut Vec<T>,
+) {
+    fn siblings<'a, T>(
+        roots: &'a mut Vec<T>,
+        parent_path: &[usize],
+        children: &impl Fn(&mut T) -> &mut Vec<T>,
+    ) -> &'a mut Vec<T> {
+        parent_path
+            .iter()
+            .fold(roots, |vec, &idx| children(&mut vec[idx]))
+    }
+
+    let (source_idx, source_parent) = source_path.split_last().expect("source path is empty");
+    let (target_idx, target_parent) = target_path.split_last().expect("target path is empty");
+
+    let item = siblings(roots, source_parent, &children).remove(*source_idx);
+    siblings(roots, target_parent, &children).insert(*target_idx, item);
+}
@@ -0,0 +1,69 @@
+use egui::{pos2, vec2, Pos2, ScrollArea, Ui};
+use egui_dnd::testing::DndTester;
+use egui_dnd::{dnd, DragDropConfig};
+
+/// A list of 50x20 items in a 200 points high `ScrollArea`, starting at (8, 8).
+/// Returns the scroll offset.
+fn list_ui(ui: &mut Ui, items: &mut [u32], config: &DragDropConfig) -> f32 {
+    ScrollArea::vertical()
+        .max_height(200.0)
+        .show(ui, |ui| {
+            dnd(ui, "list").with_mouse_config(config.clone()).show_vec(
+                items,
+                |ui, item, handle, _state| {
+                    handle.ui(ui, |ui| {
+                        ui.set_min_size(vec2(50.0, 20.0));
+                        ui.label(item.to_string());
+                    });
+                },
+            );
+        })
+        .state
+        .offset
+        .y
+}
+
+const FIRST: Pos2 = pos2(20.0, 18.0);
+
+/// Start dragging the first item and hold it at the bottom edge of the `ScrollArea` for a second.
+/// Returns the scroll offset.
+fn hold_at_bottom_edge(tester: &mut DndTester, items: &mut [u32], config: &DragDropConfig) -> f32 {
+    tester.run(|ui| list_ui(ui, items, config));
+    tester.press(FIRST);
+    tester.run(|ui| list_ui(ui, items, config));
+    tester.pointer_move(FIRST + vec2(0.0, 2.0));
+    tester.run(|ui| list_ui(ui, items, config));
+
+    tester.pointer_move(pos2(20.0, 200.0));
+    let mut offset = 0.0;
+    for _ in 0..60 {
+        offset = tester.run(|ui| list_ui(ui, items, config));
+    }
+    offset
+}
+
+#[test]
+fn dragging_near_the_edge_scrolls() {
+    let mut items: Vec<u32> = (0..50).collect();
+    let config = DragDropConfig::mouse();
+    let mut tester = DndTester::new();
+
+    let offset = hold_at_bottom_edge(&mut tester, &mut items, &config);
+    assert!(offset > 100.0, "offset: {offset}");
+
+    // The item is dropped below the items that were scrolled past
+    tester.release();
+    tester.run(|ui| list_ui(ui, &mut items, &config));
+    let new_index = items.iter().position(|item| *item == 0).unwrap();
+    assert!(new_index > 8, "index: {new_index}");
+}
+
+#[test]
+fn auto_scroll_can_be_disabled() {
+    let mut items: Vec<u32> = (0..50).collect();
+    let config = DragDropConfig::mouse().with_auto_scroll_speed(0.0);
+    let mut tester = DndTester::new();
+
+    let offset = hold_at_bottom_edge(&mut tester, &mut items, &config);
+    assert!(offset.abs() < f32::EPSILON, "offset: {offset}");
+}
@@ -0,0 +1,89 @@
+use egui::{pos2, vec2, CursorIcon, Pos2, Ui};
+use egui_dnd::testing::DndTester;
+use egui_dnd::{dnd, DragDropResponse};
+
+/// A list of 50x20 items, starting at (8, 8), with 3 points of spacing.
+/// Items can only be dropped at indices below `max_to` and `barrier` is a barrier.
+fn list_ui(
+    ui: &mut Ui,
+    items: &mut [u32],
+    max_to: usize,
+    barrier: Option<u32>,
+) -> DragDropResponse {
+    dnd(ui, "list")
+        .with_can_drop(move |_from, to| to < max_to)
+        .show_vec(items, |ui, item, handle, _state| {
+            handle
+                .pinned(*item == 4)
+                .barrier(Some(*item) == barrier)
+                .ui(ui, |ui| {
+                    ui.set_min_size(vec2(50.0, 20.0));
+                    ui.label(item.to_string());
+                });
+        })
+}
+
+/// The center of the item at `idx`
+fn item(idx: u8) -> Pos2 {
+    pos2(20.0, 18.0 + f32::from(idx) * 23.0)
+}
+
+#[test]
+fn allowed_drop_updates_the_list() {
+    let mut items: Vec<u32> = (0..5).collect();
+    let mut tester = DndTester::new();
+    tester.run(|ui| list_ui(ui, &mut items, 3, None));
+
+    let response = tester.drag(item(0), item(1) + vec2(0.0, 5.0), |ui| {
+        list_ui(ui, &mut items, 3, None)
+    });
+    assert!(response.cancellation_reason().is_none());
+    assert_eq!(items, vec![1, 0, 2, 3, 4]);
+}
+
+#[test]
+fn rejected_drop_is_cancelled() {
+    let mut items: Vec<u32> = (0..5).collect();
+    let mut tester = DndTester::new();
+    tester.run(|ui| list_ui(ui, &mut items, 3, None));
+
+    tester.press(item(0));
+    tester.run(|ui| list_ui(ui, &mut items, 3, None));
+    for i in 1..=10_u8 {
+        let t = f32::from(i) / 10.0;
+        tester.pointer_move(item(0).lerp(item(3) + vec2(0.0, 5.0), t));
+        tester.run(|ui| list_ui(ui, &mut items, 3, None));
+    }
+    // The item was moved to the last allowed position, it isn't moved to the rejected position
+    assert_eq!(tester.output().cursor_icon, CursorIcon::NotAllowed);
+    assert_eq!(items, vec![1, 0, 2, 3, 4]);
+
+    tester.release();
+    let response = tester.run(|ui| list_ui(ui, &mut items, 3, None));
+    assert_eq!(
+        response.cancellation_reason(),
+        Some("The item can't be dropped at this position")
+    );
+    assert_eq!(items, vec![1, 0, 2, 3, 4]);
+}
+
+#[test]
+fn pinned_item_can_not_be_dragged() {
+    let mut items: Vec<u32> = (0..5).collect();
+    let mut tester = DndTester::new();
+    tester.run(|ui| list_ui(ui, &mut items, 5, None));
+
+    let response = tester.drag(item(4), item(0), |ui| list_ui(ui, &mut items, 5, None));
+    assert_eq!(response.cancellation_reason(), Some("The item is pinned"));
+    assert_eq!(items, vec![0, 1, 2, 3, 4]);
+}
+
+#[test]
+fn items_can_not_be_moved_past_a_barrier() {
+    let mut items: Vec<u32> = (0..5).collect();
+    let mut tester = DndTester::new();
+    tester.run(|ui| list_ui(ui, &mut items, 5, Some(2)));
+
+    tester.drag(item(0), item(3), |ui| list_ui(ui, &mut items, 5, Some(2)));
+    assert_eq!(items, vec![1, 0, 2, 3, 4]);
+}
@@ -0,0 +1,74 @@
+use egui::{pos2, vec2, Id, Ui};
+use egui_dnd::testing::DndTester;
+use egui_dnd::{dnd, CrossListUpdate};
+
+struct Column {
+    name: &'static str,
+    items: Vec<u32>,
+}
+
+/// Two columns of 50x20 items, the first one starting at (8, 8), the second one at (204, 8)
+fn board_ui(ui: &mut Ui, board: &mut [Column]) -> Option<CrossListUpdate> {
+    let mut update = None;
+    ui.columns(board.len(), |uis| {
+        for (ui, column) in uis.iter_mut().zip(board.iter_mut()) {
+            let response = dnd(ui, column.name).with_group("board").show_vec(
+                &mut column.items,
+                |ui, item, handle, _state| {
+                    handle.ui(ui, |ui| {
+                        ui.set_min_size(vec2(50.0, 20.0));
+                        ui.label(item.to_string());
+                    });
+                },
+            );
+            if let Some(cross_list_update) = response.cross_list_update() {
+                update = Some(cross_list_update);
+            }
+        }
+    });
+    update
+}
+
+#[test]
+fn drag_to_other_list_returns_cross_list_update() {
+    let mut board = vec![
+        Column {
+            name: "todo",
+            items: vec![0, 1, 2],
+        },
+        Column {
+            name: "done",
+            items: vec![10, 11, 12],
+        },
+    ];
+    let mut tester = DndTester::new();
+    tester.run(|ui| board_ui(ui, &mut board));
+
+    // Move right first, so the item isn't reordered in its own list on the way
+    tester.press(pos2(20.0, 18.0));
+    tester.run(|ui| board_ui(ui, &mut board));
+    for pos in [
+        pos2(22.0, 18.0),
+        pos2(24.0, 18.0),
+        pos2(120.0, 18.0),
+        pos2(220.0, 18.0),
+        pos2(220.0, 40.0),
+        pos2(220.0, 41.0),
+    ] {
+        tester.pointer_move(pos);
+        tester.run(|ui| board_ui(ui, &mut board));
+    }
+    tester.release();
+    let update = tester
+        .run(|ui| board_ui(ui, &mut board))
+        .expect("dropping on the other list should return a CrossListUpdate");
+
+    assert_eq!(update.from_list, Id::new("todo"));
+    assert_eq!(update.from, 0);
+    assert_eq!(update.to_list, Id::new("done"));
+    assert_eq!(update.to, 1);
+
+    assert!(update.apply_lists(&mut board, |c| Id::new(c.name), |c| &mut c.items));
+    assert_eq!(board[0].items, vec![1, 2]);
+    assert_eq!(board[1].items, vec![10, 0, 11, 12]);
+}
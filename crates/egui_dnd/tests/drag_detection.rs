@@ -0,0 +1,183 @@
+use std::time::Duration;
+
+use egui::{pos2, vec2, Pos2, Ui};
+use egui_dnd::testing::DndTester;
+use egui_dnd::{dnd, DragDropConfig, DragDropResponse};
+
+/// A list of 50x20 items, starting at (8, 8)
+struct List {
+    items: Vec<u32>,
+    config: DragDropConfig,
+}
+
+impl List {
+    fn new(config: DragDropConfig) -> Self {
+        Self {
+            items: (0..5).collect(),
+            config,
+        }
+    }
+
+    fn ui(&mut self, ui: &mut Ui) -> DragDropResponse {
+        dnd(ui, "list")
+            .with_mouse_config(self.config.clone())
+            .with_touch_config(Some(self.config.clone()))
+            .show_vec(&mut self.items, |ui, item, handle, _state| {
+                handle.ui(ui, |ui| {
+                    ui.set_min_size(vec2(50.0, 20.0));
+                    ui.label(item.to_string());
+                });
+            })
+    }
+}
+
+/// The center of the first item
+const FIRST: Pos2 = pos2(20.0, 18.0);
+
+#[test]
+fn mouse_drag_reorders_items() {
+    let mut tester = DndTester::new();
+    let mut list = List::new(DragDropConfig::mouse());
+    tester.run(|ui| list.ui(ui));
+
+    let response = tester.drag(FIRST, pos2(20.0, 70.0), |ui| list.ui(ui));
+
+    assert!(response.is_drag_finished());
+    assert!(response.cancellation_reason().is_none());
+    assert_eq!(list.items, vec![1, 2, 0, 3, 4]);
+}
+
+#[test]
+fn click_below_click_tolerance_does_not_drag() {
+    let mut tester = DndTester::new();
+    let mut list = List::new(DragDropConfig::mouse().with_click_tolerance(5.0));
+    tester.run(|ui| list.ui(ui));
+
+    tester.press(FIRST);
+    assert!(tester.run(|ui| list.ui(ui)).is_evaluating_drag());
+
+    tester.pointer_move(FIRST + vec2(0.0, 3.0));
+    let response = tester.run(|ui| list.ui(ui));
+    assert!(response.is_evaluating_drag());
+    assert!(!response.is_dragging());
+
+    tester.release();
+    let response = tester.run(|ui| list.ui(ui));
+    assert!(!response.is_dragging());
+    assert!(response.update.is_none());
+    assert_eq!(list.items, vec![0, 1, 2, 3, 4]);
+}
+
+#[test]
+fn drag_starts_above_click_tolerance() {
+    let mut tester = DndTester::new();
+    let mut list = List::new(DragDropConfig::mouse().with_click_tolerance(5.0));
+    tester.run(|ui| list.ui(ui));
+
+    tester.press(FIRST);
+    tester.run(|ui| list.ui(ui));
+    tester.pointer_move(FIRST + vec2(0.0, 6.0));
+    tester.run(|ui| list.ui(ui));
+    tester.pointer_move(FIRST + vec2(0.0, 7.0));
+    assert!(tester.run(|ui| list.ui(ui)).is_dragging());
+}
+
+#[test]
+fn drag_starts_after_click_tolerance_timeout() {
+    let mut tester = DndTester::new();
+    let mut list = List::new(DragDropConfig::mouse().with_click_tolerance(5.0));
+    tester.run(|ui| list.ui(ui));
+
+    tester.press(FIRST);
+    tester.run(|ui| list.ui(ui));
+    tester.wait(Duration::from_millis(300));
+    tester.run(|ui| list.ui(ui));
+    assert!(tester.run(|ui| list.ui(ui)).is_dragging());
+}
+
+#[test]
+fn touch_drag_waits_for_delay() {
+    let mut tester = DndTester::new();
+    let mut list = List::new(DragDropConfig::touch_scroll());
+    tester.run(|ui| list.ui(ui));
+
+    tester.touch_start(FIRST);
+    tester.run(|ui| list.ui(ui));
+    tester.touch_move(FIRST + vec2(0.0, 2.0));
+    let response = tester.run(|ui| list.ui(ui));
+    assert!(response.is_evaluating_drag());
+    assert!(!response.is_dragging());
+
+    tester.wait(Duration::from_millis(300));
+    tester.touch_move(FIRST + vec2(0.0, 4.0));
+    tester.run(|ui| list.ui(ui));
+    tester.touch_move(FIRST + vec2(0.0, 5.0));
+    assert!(tester.run(|ui| list.ui(ui)).is_dragging());
+
+    tester.touch_move(pos2(20.0, 70.0));
+    tester.run(|ui| list.ui(ui));
+    tester.touch_end();
+    let response = tester.run(|ui| list.ui(ui));
+    assert!(response.is_drag_finished());
+    assert_eq!(list.items, vec![1, 2, 0, 3, 4]);
+}
+
+#[test]
+fn touch_drag_waits_for_touch_click_tolerance() {
+    // The touch preset has a click tolerance of 3 points, more than the 1 point of the mouse
+    let mut tester = DndTester::new();
+    let mut list = List::new(DragDropConfig::touch());
+    tester.run(|ui| list.ui(ui));
+
+    tester.touch_start(FIRST);
+    tester.run(|ui| list.ui(ui));
+    tester.touch_move(FIRST + vec2(0.0, 2.0));
+    let response = tester.run(|ui| list.ui(ui));
+    assert!(response.is_evaluating_drag());
+    assert!(!response.is_dragging());
+
+    tester.touch_move(FIRST + vec2(0.0, 4.0));
+    tester.run(|ui| list.ui(ui));
+    tester.touch_move(FIRST + vec2(0.0, 5.0));
+    assert!(tester.run(|ui| list.ui(ui)).is_dragging());
+}
+
+#[test]
+fn touch_moving_beyond_scroll_tolerance_cancels() {
+    let mut tester = DndTester::new();
+    let mut list = List::new(DragDropConfig::touch_scroll());
+    tester.run(|ui| list.ui(ui));
+
+    tester.touch_start(FIRST);
+    tester.run(|ui| list.ui(ui));
+    // Scrolling before the drag delay has passed
+    tester.touch_move(FIRST + vec2(0.0, 20.0));
+    tester.run(|ui| list.ui(ui));
+    tester.wait(Duration::from_millis(300));
+    tester.touch_move(FIRST + vec2(0.0, 40.0));
+    assert!(!tester.run(|ui| list.ui(ui)).is_dragging());
+
+    tester.touch_end();
+    let response = tester.run(|ui| list.ui(ui));
+    assert_eq!(
+        response.cancellation_reason(),
+        Some("Drag distance exceeded scroll threshold")
+    );
+    assert_eq!(list.items, vec![0, 1, 2, 3, 4]);
+}
+
+#[test]
+fn release_outside_of_handles_cancels() {
+    let mut tester = DndTester::new();
+    let mut list = List::new(DragDropConfig::mouse());
+    tester.run(|ui| list.ui(ui));
+
+    tester.press(pos2(300.0, 300.0));
+    tester.run(|ui| list.ui(ui));
+    tester.release();
+    let response = tester.run(|ui| list.ui(ui));
+    assert_eq!(
+        response.cancellation_reason(),
+        Some("Cursor not hovering over any item handle")
+    );
+}
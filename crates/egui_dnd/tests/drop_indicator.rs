@@ -0,0 +1,69 @@
+use egui::{pos2, vec2, Pos2, Ui};
+use egui_dnd::testing::DndTester;
+use egui_dnd::{dnd, DragDropResponse, DropIndicator, Placeholder};
+
+/// A list of 50x20 items, starting at (8, 8), with 3 points of spacing
+fn list_ui(ui: &mut Ui, items: &mut [u32], indicator: DropIndicator) -> DragDropResponse {
+    dnd(ui, "list")
+        .with_drop_indicator(indicator)
+        .with_placeholder(Placeholder::Outline)
+        .show_vec(items, |ui, item, handle, _state| {
+            handle.ui(ui, |ui| {
+                ui.set_min_size(vec2(50.0, 20.0));
+                ui.label(item.to_string());
+            });
+        })
+}
+
+const FIRST: Pos2 = pos2(20.0, 18.0);
+/// Between the third and the fourth item
+const BETWEEN_THIRD_AND_FOURTH: Pos2 = pos2(20.0, 76.0);
+
+/// Drag the first item between the third and the fourth item, without releasing it
+fn drag_first_item(
+    tester: &mut DndTester,
+    items: &mut [u32],
+    indicator: DropIndicator,
+) -> DragDropResponse {
+    tester.run(|ui| list_ui(ui, items, indicator));
+    tester.press(FIRST);
+    tester.run(|ui| list_ui(ui, items, indicator));
+    let mut response = None;
+    for i in 1..=10_u8 {
+        let t = f32::from(i) / 10.0;
+        tester.pointer_move(FIRST.lerp(BETWEEN_THIRD_AND_FOURTH, t));
+        response = Some(tester.run(|ui| list_ui(ui, items, indicator)));
+    }
+    response.unwrap()
+}
+
+#[test]
+fn line_indicator_only_updates_on_drop() {
+    let mut items: Vec<u32> = (0..5).collect();
+    let mut tester = DndTester::new();
+
+    let response = drag_first_item(&mut tester, &mut items, DropIndicator::Line);
+    assert!(response.is_dragging());
+    assert_eq!(response.update.map(|u| (u.from, u.to)), Some((0, 3)));
+    assert_eq!(items, vec![0, 1, 2, 3, 4]);
+
+    tester.release();
+    let response = tester.run(|ui| list_ui(ui, &mut items, DropIndicator::Line));
+    assert!(response.is_drag_finished());
+    assert_eq!(items, vec![1, 2, 0, 3, 4]);
+}
+
+#[test]
+fn gap_indicator_updates_while_dragging() {
+    let mut items: Vec<u32> = (0..5).collect();
+    let mut tester = DndTester::new();
+
+    let response = drag_first_item(&mut tester, &mut items, DropIndicator::Gap);
+    assert!(response.is_dragging());
+    assert_eq!(items, vec![1, 2, 0, 3, 4]);
+
+    tester.release();
+    let response = tester.run(|ui| list_ui(ui, &mut items, DropIndicator::Gap));
+    assert!(response.is_drag_finished());
+    assert_eq!(items, vec![1, 2, 0, 3, 4]);
+}
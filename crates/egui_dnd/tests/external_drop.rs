@@ -0,0 +1,130 @@
+use egui::{pos2, vec2, Frame, Pos2, Rect, Ui};
+use egui_dnd::testing::DndTester;
+use egui_dnd::{dnd, DragDropResponse, Handle};
+
+/// Shows the item with a payload, if `payload` is true. Returns the rect the handle was laid out at.
+fn item_ui(ui: &mut Ui, item: &'static str, handle: Handle, payload: bool) -> Rect {
+    let handle = if payload {
+        handle.payload(item)
+    } else {
+        handle
+    };
+    handle
+        .ui(ui, |ui| {
+            ui.set_min_size(vec2(50.0, 20.0));
+            ui.label(item);
+        })
+        .rect
+}
+
+struct Board {
+    archive: Vec<&'static str>,
+    inbox: Vec<&'static str>,
+    /// The rects the handles of the inbox were laid out at in the last frame
+    inbox_rects: Vec<Rect>,
+}
+
+impl Board {
+    fn new() -> Self {
+        Self {
+            archive: vec!["report"],
+            inbox: vec!["invoice", "newsletter", "notes"],
+            inbox_rects: Vec::new(),
+        }
+    }
+
+    /// The archive in the left column, starting at (8, 8), the inbox in the right column,
+    /// starting at (204, 8). Items of the inbox have a payload if `payload` is true.
+    fn ui(&mut self, ui: &mut Ui, payload: bool) -> (DragDropResponse, DragDropResponse) {
+        let mut responses = None;
+        ui.columns(2, |uis| {
+            let archive = Frame::none()
+                .show(&mut uis[0], |ui| {
+                    ui.set_min_height(200.0);
+                    dnd(ui, "archive")
+                        .with_external_drop::<&'static str>()
+                        .show_vec(&mut self.archive, |ui, item, handle, _state| {
+                            item_ui(ui, item, handle, true);
+                        })
+                })
+                .inner;
+
+            self.inbox_rects.clear();
+            let inbox =
+                dnd(&mut uis[1], "inbox").show_vec(&mut self.inbox, |ui, item, handle, _state| {
+                    self.inbox_rects.push(item_ui(ui, item, handle, payload));
+                });
+            responses = Some((archive, inbox));
+        });
+        responses.unwrap()
+    }
+}
+
+const INBOX_FIRST: Pos2 = pos2(220.0, 18.0);
+
+#[test]
+fn payload_is_inserted_in_other_list() {
+    let mut board = Board::new();
+    let mut tester = DndTester::new();
+    tester.run(|ui| board.ui(ui, true));
+
+    let (archive, inbox) = tester.drag(INBOX_FIRST, pos2(20.0, 24.0), |ui| board.ui(ui, true));
+
+    let (idx, item) = archive
+        .external_payload::<&'static str>()
+        .expect("the archive should accept the payload");
+    assert_eq!((idx, *item), (1, "invoice"));
+    assert_eq!(inbox.dropped_externally(), Some(0));
+}
+
+/// The board with a drop zone below it, returns the index of the item the drop zone took
+fn board_with_drop_zone_ui(ui: &mut Ui, board: &mut Board) -> Option<usize> {
+    let (_, inbox) = board.ui(ui, true);
+    let (_, payload) = ui.dnd_drop_zone::<&'static str, ()>(Frame::none(), |ui| {
+        ui.set_min_size(vec2(300.0, 50.0));
+    });
+    if payload.is_some() {
+        egui_dnd::notify_external_drop(ui.ctx());
+    }
+    inbox.dropped_externally()
+}
+
+#[test]
+fn payload_is_taken_by_drop_zone_shown_after_the_list() {
+    let mut board = Board::new();
+    let mut tester = DndTester::new();
+    tester.run(|ui| board_with_drop_zone_ui(ui, &mut board));
+
+    // The drop zone is below the archive, which is 200 points high
+    tester.drag(INBOX_FIRST, pos2(100.0, 230.0), |ui| {
+        board_with_drop_zone_ui(ui, &mut board)
+    });
+    // The list is shown before the drop zone, so it notices the drop in the next frame
+    let idx = tester
+        .run(|ui| board_with_drop_zone_ui(ui, &mut board))
+        .expect("the drop zone should take the payload");
+    assert_eq!(board.inbox[idx], "invoice");
+}
+
+#[test]
+fn dragged_item_is_only_laid_out_offscreen_with_a_payload() {
+    for payload in [false, true] {
+        let mut board = Board::new();
+        let mut tester = DndTester::new();
+        tester.run(|ui| board.ui(ui, payload));
+
+        tester.press(INBOX_FIRST);
+        tester.run(|ui| board.ui(ui, payload));
+        for i in 1..=5_u8 {
+            tester.pointer_move(INBOX_FIRST + vec2(0.0, 2.0 * f32::from(i)));
+            tester.run(|ui| board.ui(ui, payload));
+        }
+
+        let dragged = board.inbox_rects[0];
+        assert_eq!(
+            dragged.top() < -1000.0,
+            payload,
+            "payload: {payload}, rect: {dragged:?}"
+        );
+    }
+}
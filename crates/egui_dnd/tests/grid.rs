@@ -0,0 +1,51 @@
+use egui::{pos2, vec2, Pos2, Ui};
+use egui_dnd::dnd;
+use egui_dnd::testing::DndTester;
+
+/// A wrapping grid of 50x50 items, starting at (8, 8), with 8 points of horizontal and 3 points
+/// of vertical spacing, so there are 6 items per row
+fn grid_ui(ui: &mut Ui, items: &mut [u32]) {
+    ui.horizontal_wrapped(|ui| {
+        dnd(ui, "grid").show_vec_sized(items, vec2(50.0, 50.0), |ui, item, handle, _state| {
+            handle.ui(ui, |ui| {
+                ui.set_min_size(vec2(50.0, 50.0));
+                ui.label(item.to_string());
+            });
+        });
+    });
+}
+
+/// The center of the item in `row` and `column`
+fn cell(row: u8, column: u8) -> Pos2 {
+    pos2(
+        33.0 + f32::from(column) * 58.0,
+        33.0 + f32::from(row) * 53.0,
+    )
+}
+
+fn drag(to: Pos2) -> Vec<u32> {
+    let mut items: Vec<u32> = (0..12).collect();
+    let mut tester = DndTester::new();
+    tester.run(|ui| grid_ui(ui, &mut items));
+    tester.drag(cell(0, 0), to, |ui| grid_ui(ui, &mut items));
+    items
+}
+
+#[test]
+fn dropping_on_the_right_half_inserts_after() {
+    // The third item of the second row
+    let items = drag(cell(1, 2) + vec2(15.0, 0.0));
+    assert_eq!(items, vec![1, 2, 3, 4, 5, 6, 7, 8, 0, 9, 10, 11]);
+}
+
+#[test]
+fn dropping_on_the_left_half_inserts_before() {
+    let items = drag(cell(1, 2) - vec2(15.0, 0.0));
+    assert_eq!(items, vec![1, 2, 3, 4, 5, 6, 7, 0, 8, 9, 10, 11]);
+}
+
+#[test]
+fn dropping_in_the_same_row_moves_horizontally() {
+    let items = drag(cell(0, 3) + vec2(15.0, 0.0));
+    assert_eq!(items, vec![1, 2, 3, 0, 4, 5, 6, 7, 8, 9, 10, 11]);
+}
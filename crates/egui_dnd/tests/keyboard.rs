@@ -0,0 +1,143 @@
+use egui::{output::OutputEvent, vec2, Key, Ui};
+use egui_dnd::testing::DndTester;
+use egui_dnd::{dnd, DragDropResponse};
+
+fn list_ui(ui: &mut Ui, items: &mut [u32]) -> DragDropResponse {
+    dnd(ui, "list")
+        .with_keyboard_reordering(true)
+        .show_vec(items, |ui, item, handle, _state| {
+            handle.ui(ui, |ui| {
+                ui.set_min_size(vec2(50.0, 20.0));
+                ui.label(item.to_string());
+            });
+        })
+}
+
+/// Show the list and focus the handle of the first item
+fn focused_list(items: &mut [u32]) -> DndTester {
+    let mut tester = DndTester::new();
+    tester.run(|ui| list_ui(ui, items));
+    tester.key(Key::Tab);
+    tester.run(|ui| list_ui(ui, items));
+    tester
+}
+
+#[test]
+fn arrow_keys_move_the_picked_up_item() {
+    let mut items: Vec<u32> = (0..5).collect();
+    let mut tester = focused_list(&mut items);
+
+    tester.key(Key::Space);
+    assert!(tester.run(|ui| list_ui(ui, &mut items)).is_dragging());
+    // show_vec moves the item while it is picked up
+    tester.key(Key::ArrowDown);
+    tester.run(|ui| list_ui(ui, &mut items));
+    assert_eq!(items, vec![1, 0, 2, 3, 4]);
+    tester.key(Key::ArrowDown);
+    tester.run(|ui| list_ui(ui, &mut items));
+    assert_eq!(items, vec![1, 2, 0, 3, 4]);
+
+    tester.key(Key::Space);
+    let response = tester.run(|ui| list_ui(ui, &mut items));
+    assert!(response.is_drag_finished());
+    assert!(!response.is_dragging());
+    assert_eq!(items, vec![1, 2, 0, 3, 4]);
+}
+
+#[test]
+fn item_can_be_picked_up_and_moved_in_the_same_frame() {
+    let mut items: Vec<u32> = (0..5).collect();
+    let mut tester = focused_list(&mut items);
+
+    tester.key(Key::Space);
+    tester.key(Key::ArrowDown);
+    let response = tester.run(|ui| list_ui(ui, &mut items));
+    assert!(response.is_dragging());
+    assert_eq!(response.update.map(|u| (u.from, u.to)), Some((0, 2)));
+
+    tester.key(Key::Space);
+    assert!(tester.run(|ui| list_ui(ui, &mut items)).is_drag_finished());
+    assert_eq!(items, vec![1, 0, 2, 3, 4]);
+}
+
+#[test]
+fn escape_moves_the_item_back() {
+    let mut items: Vec<u32> = (0..5).collect();
+    let mut tester = focused_list(&mut items);
+
+    tester.key(Key::Space);
+    tester.run(|ui| list_ui(ui, &mut items));
+    tester.key(Key::ArrowDown);
+    tester.run(|ui| list_ui(ui, &mut items));
+
+    tester.key(Key::Escape);
+    let response = tester.run(|ui| list_ui(ui, &mut items));
+    assert_eq!(
+        response.cancellation_reason(),
+        Some("Cancelled with the escape key")
+    );
+    assert!(!response.is_dragging());
+    assert_eq!(items, vec![0, 1, 2, 3, 4]);
+}
+
+#[test]
+fn moves_are_announced() {
+    let mut items: Vec<u32> = (0..5).collect();
+    let mut tester = focused_list(&mut items);
+
+    let announcement = |tester: &DndTester| {
+        tester.output().events.iter().find_map(|event| match event {
+            OutputEvent::ValueChanged(info) => info.label.clone(),
+            _ => None,
+        })
+    };
+
+    tester.key(Key::Space);
+    tester.run(|ui| list_ui(ui, &mut items));
+    assert_eq!(
+        announcement(&tester).as_deref(),
+        Some("Picked up item 1. Use the arrow keys to move it, space to drop it and escape to cancel.")
+    );
+
+    tester.key(Key::ArrowDown);
+    tester.run(|ui| list_ui(ui, &mut items));
+    assert_eq!(
+        announcement(&tester).as_deref(),
+        Some("Item 1 moved to position 2")
+    );
+
+    tester.key(Key::Space);
+    tester.run(|ui| list_ui(ui, &mut items));
+    assert_eq!(
+        announcement(&tester).as_deref(),
+        Some("Item 1 dropped at position 2")
+    );
+}
+
+#[cfg(feature = "accesskit")]
+#[test]
+fn announcements_are_added_as_live_region() {
+    use egui::accesskit::{Live, Role};
+
+    let mut items: Vec<u32> = (0..5).collect();
+    let mut tester = focused_list(&mut items);
+    tester.ctx().enable_accesskit();
+
+    tester.key(Key::Space);
+    tester.run(|ui| list_ui(ui, &mut items));
+    tester.key(Key::ArrowDown);
+    tester.run(|ui| list_ui(ui, &mut items));
+
+    let update = tester
+        .output()
+        .accesskit_update
+        .as_ref()
+        .expect("accesskit is enabled");
+    let (_, live_region) = update
+        .nodes
+        .iter()
+        .find(|(_, node)| node.role() == Role::Status)
+        .expect("the announcement should be in the accessibility tree");
+    assert_eq!(live_region.live(), Some(Live::Assertive));
+    assert_eq!(live_region.name(), Some("Item 1 moved to position 2"));
+}
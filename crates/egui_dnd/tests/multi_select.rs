@@ -0,0 +1,73 @@
+use egui::{pos2, vec2, Modifiers, Pos2, Ui};
+use egui_dnd::testing::DndTester;
+use egui_dnd::utils::shift_vec_multi;
+use egui_dnd::{dnd, DragDropResponse};
+
+/// A list of 50x20 items, starting at (8, 8), with 3 points of spacing
+/// The items aren't updated, so the updates can be checked
+fn list_ui(ui: &mut Ui, items: &[u32]) -> DragDropResponse {
+    dnd(ui, "list")
+        .with_multi_select(true)
+        .show(items.iter(), |ui, item, handle, _state| {
+            handle.ui(ui, |ui| {
+                ui.set_min_size(vec2(50.0, 20.0));
+                ui.label(item.to_string());
+            });
+        })
+}
+
+/// The center of the item at `idx`
+fn item(idx: u8) -> Pos2 {
+    pos2(20.0, 18.0 + f32::from(idx) * 23.0)
+}
+
+fn click(tester: &mut DndTester, pos: Pos2, modifiers: Modifiers, items: &[u32]) {
+    tester.set_modifiers(modifiers);
+    tester.press(pos);
+    tester.run(|ui| list_ui(ui, items));
+    tester.release();
+    tester.run(|ui| list_ui(ui, items));
+    tester.set_modifiers(Modifiers::NONE);
+}
+
+#[test]
+fn dragging_selected_items_moves_them_together() {
+    let mut items: Vec<u32> = (0..6).collect();
+    let mut tester = DndTester::new();
+    tester.run(|ui| list_ui(ui, &items));
+
+    click(&mut tester, item(1), Modifiers::NONE, &items);
+    click(&mut tester, item(3), Modifiers::COMMAND, &items);
+    assert_eq!(tester.run(|ui| list_ui(ui, &items)).selected_ids().len(), 2);
+
+    let update = tester
+        .drag(item(3), item(5) + vec2(0.0, 8.0), |ui| list_ui(ui, &items))
+        .multi_update()
+        .expect("dragging two selected items should return a MultiDragUpdate");
+
+    assert_eq!(update.from, vec![1, 3]);
+    shift_vec_multi(&update.from, update.to, &mut items);
+    assert_eq!(items, vec![0, 2, 4, 5, 1, 3]);
+}
+
+#[test]
+fn selected_items_before_the_dragged_item_are_moved_from_the_first_frame() {
+    let items: Vec<u32> = (0..6).collect();
+    let mut tester = DndTester::new();
+    tester.run(|ui| list_ui(ui, &items));
+
+    click(&mut tester, item(0), Modifiers::NONE, &items);
+    click(&mut tester, item(2), Modifiers::SHIFT, &items);
+
+    // The drag starts on the last selected item, in the same frame the pointer moves
+    tester.press(item(2));
+    tester.run(|ui| list_ui(ui, &items));
+    tester.pointer_move(item(2) + vec2(0.0, 2.0));
+    let response = tester.run(|ui| list_ui(ui, &items));
+
+    assert!(response.is_dragging());
+    let update = response
+        .multi_update()
+        .expect("the first frame of the drag should return a MultiDragUpdate");
+    assert_eq!(update.from, vec![0, 1, 2]);
+}
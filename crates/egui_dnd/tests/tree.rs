@@ -0,0 +1,98 @@
+use egui::{pos2, vec2, Id, Ui};
+use egui_dnd::testing::DndTester;
+use egui_dnd::{dnd, DragDropItem, TreeDragUpdate};
+
+struct Node {
+    name: &'static str,
+    children: Vec<Node>,
+}
+
+impl DragDropItem for &Node {
+    fn id(&self) -> Id {
+        Id::new(self.name)
+    }
+}
+
+fn node(name: &'static str, children: Vec<Node>) -> Node {
+    Node { name, children }
+}
+
+fn flatten<'a>(nodes: &'a [Node], depth: usize, rows: &mut Vec<(&'a Node, usize)>) {
+    for node in nodes {
+        rows.push((node, depth));
+        flatten(&node.children, depth + 1, rows);
+    }
+}
+
+fn names(nodes: &[Node]) -> Vec<String> {
+    let mut rows = Vec::new();
+    flatten(nodes, 0, &mut rows);
+    rows.iter()
+        .map(|(node, depth)| format!("{}{}", " ".repeat(*depth), node.name))
+        .collect()
+}
+
+/// Rows of 50x20 items, starting at (8, 8)
+fn tree_ui(ui: &mut Ui, tree: &[Node]) -> Option<TreeDragUpdate> {
+    let mut rows = Vec::new();
+    flatten(tree, 0, &mut rows);
+    dnd(ui, "tree")
+        .show_tree(rows.into_iter(), |ui, node, handle, _state| {
+            handle.ui(ui, |ui| {
+                ui.set_min_size(vec2(50.0, 20.0));
+                ui.label(node.name);
+            });
+        })
+        .tree_update()
+}
+
+#[test]
+fn dropping_on_an_item_makes_it_a_child() {
+    let mut tree = vec![
+        node("a", vec![node("a1", vec![])]),
+        node("b", vec![]),
+        node("c", vec![]),
+    ];
+    let mut tester = DndTester::new();
+    tester.run(|ui| tree_ui(ui, &tree));
+
+    // Drag the last row ("c") onto the middle of the first row ("a")
+    let update = tester
+        .drag(pos2(20.0, 87.0), pos2(20.0, 18.0), |ui| tree_ui(ui, &tree))
+        .expect("dropping on a row should return a TreeDragUpdate");
+
+    assert_eq!(
+        update,
+        TreeDragUpdate {
+            from_path: vec![2],
+            to_path: vec![0, 0],
+        }
+    );
+    update.apply(&mut tree, |node| &mut node.children);
+    assert_eq!(names(&tree), ["a", " c", " a1", "b"]);
+}
+
+#[test]
+fn dropping_between_items_moves_to_other_parent() {
+    let mut tree = vec![
+        node("a", vec![node("a1", vec![]), node("a2", vec![])]),
+        node("b", vec![]),
+    ];
+    let mut tester = DndTester::new();
+    tester.run(|ui| tree_ui(ui, &tree));
+
+    // Drag "a1" onto the top edge of "b" and one indent to the left
+    let update = tester
+        .drag(pos2(40.0, 41.0), pos2(22.0, 79.0), |ui| tree_ui(ui, &tree))
+        .expect("dropping between rows should return a TreeDragUpdate");
+
+    assert_eq!(
+        update,
+        TreeDragUpdate {
+            from_path: vec![0, 0],
+            to_path: vec![1],
+        }
+    );
+    update.apply(&mut tree, |node| &mut node.children);
+    assert_eq!(names(&tree), ["a", " a2", "a1", "b"]);
+}
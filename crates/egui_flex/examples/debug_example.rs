@@ -1,13 +1,16 @@
 use eframe::emath::Align2;
 use eframe::NativeOptions;
 use egui::{Area, Frame, Id, Vec2};
-use egui_flex::{Flex, FlexItem};
+use egui_flex::{Flex, FlexDebug, FlexItem};
 
 fn main() -> eframe::Result {
     eframe::run_simple_native(
         "flex debug example",
         NativeOptions::default(),
         |ctx, _frame| {
+            // Show the debug overlay for all flex containers, use `Flex::debug` for a single one
+            FlexDebug::set_enabled(ctx, true);
+
             Area::new(Id::new("area"))
                 .anchor(egui::Align2::LEFT_TOP, [0.0, 0.0])
                 .show(ctx, |ui| {
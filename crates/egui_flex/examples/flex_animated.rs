@@ -0,0 +1,38 @@
+use eframe::NativeOptions;
+use egui::{Button, CentralPanel, TextEdit};
+use egui_flex::{item, Flex};
+
+fn main() -> eframe::Result {
+    let mut tags: Vec<String> = ["egui", "flex", "layout", "animation", "rust", "gui"]
+        .into_iter()
+        .map(String::from)
+        .collect();
+    let mut new_tag = String::new();
+    eframe::run_simple_native(file!(), NativeOptions::default(), move |ctx, _frame| {
+        CentralPanel::default().show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.add(TextEdit::singleline(&mut new_tag).hint_text("New tag"));
+                if ui.button("Add").clicked() && !new_tag.is_empty() && !tags.contains(&new_tag) {
+                    tags.insert(0, std::mem::take(&mut new_tag));
+                }
+            });
+            ui.label("Click a tag to remove it");
+
+            let mut removed = None;
+            Flex::horizontal()
+                .wrap(true)
+                .animate(true)
+                .show(ui, |flex| {
+                    for tag in &tags {
+                        // The id keeps the animation state of the tag when tags before it are removed
+                        if flex.add(item().id(tag), Button::new(tag)).inner.clicked() {
+                            removed = Some(tag.clone());
+                        }
+                    }
+                });
+            if let Some(removed) = removed {
+                tags.retain(|tag| *tag != removed);
+            }
+        });
+    })
+}
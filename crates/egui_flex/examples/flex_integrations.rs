@@ -0,0 +1,72 @@
+use chrono::NaiveDate;
+use eframe::NativeOptions;
+use egui::{CentralPanel, Frame, Ui};
+use egui_extras::{Column, DatePickerButton};
+use egui_flex::{item, Flex, FlexFrameWidget, FlexTable};
+use egui_material_icons::icons::{ICON_ADD, ICON_DELETE, ICON_EDIT};
+use egui_material_icons::IconButton;
+
+/// A custom frame-based widget, the frame stretches while the text stays centered
+struct Card<'a> {
+    title: &'a str,
+}
+
+impl FlexFrameWidget for Card<'_> {
+    type Response = ();
+
+    fn frame(&self, ui: &Ui) -> Frame {
+        Frame::group(ui.style()).fill(ui.visuals().faint_bg_color)
+    }
+
+    fn content(self, ui: &mut Ui) {
+        ui.heading(self.title);
+    }
+}
+
+fn main() -> eframe::Result {
+    let mut date = NaiveDate::from_ymd_opt(2024, 10, 1).unwrap_or_default();
+    let mut initialized = false;
+    eframe::run_simple_native(file!(), NativeOptions::default(), move |ctx, _frame| {
+        if !initialized {
+            egui_material_icons::initialize(ctx);
+            initialized = true;
+        }
+        CentralPanel::default().show(ctx, |ui| {
+            Flex::horizontal().show(ui, |flex| {
+                flex.add(item(), IconButton::new(ICON_ADD));
+                flex.add(item(), IconButton::new(ICON_EDIT));
+                flex.add(item(), IconButton::new(ICON_DELETE));
+                flex.add(item().grow(1.0), DatePickerButton::new(&mut date));
+            });
+
+            Flex::horizontal().show(ui, |flex| {
+                flex.add(item().grow(1.0), Card { title: "Orders" });
+                flex.add(item().grow(1.0), Card { title: "Revenue" });
+            });
+
+            Flex::horizontal().show(ui, |flex| {
+                flex.add(
+                    item().grow(1.0).basis(200.0),
+                    FlexTable::new(|table| {
+                        table
+                            .column(Column::auto())
+                            .column(Column::remainder())
+                            .body(|mut body| {
+                                for i in 0..5 {
+                                    body.row(20.0, |mut row| {
+                                        row.col(|ui| {
+                                            ui.label(format!("#{i}"));
+                                        });
+                                        row.col(|ui| {
+                                            ui.label(format!("Order {i}"));
+                                        });
+                                    });
+                                }
+                            });
+                    }),
+                );
+                flex.add(item().grow(1.0), Card { title: "Details" });
+            });
+        });
+    })
+}
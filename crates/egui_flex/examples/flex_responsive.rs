@@ -0,0 +1,33 @@
+use eframe::NativeOptions;
+use egui::{Button, CentralPanel, Label, TextEdit};
+use egui_flex::{item, Flex, FlexBreakpoint};
+
+fn main() -> eframe::Result {
+    let mut name = String::new();
+    let mut email = String::new();
+    eframe::run_simple_native(file!(), NativeOptions::default(), move |ctx, _frame| {
+        CentralPanel::default().show(ctx, |ui| {
+            ui.label("Resize the window: the form switches to a vertical layout on small screens");
+
+            Flex::responsive(ui, |width| {
+                if FlexBreakpoint::from_width(width) == FlexBreakpoint::Compact {
+                    Flex::vertical().grow_items(1.0)
+                } else {
+                    Flex::horizontal().grow_items(1.0)
+                }
+            })
+            .show(ui, |flex| {
+                let compact = flex.breakpoint() == FlexBreakpoint::Compact;
+
+                // The submit button is shown first on small screens, so it's always visible
+                flex.add(item().order(i32::from(!compact)), Button::new("Submit"));
+                flex.add(
+                    item().visible(flex.breakpoint() >= FlexBreakpoint::Expanded),
+                    Label::new("Sign up for the newsletter:"),
+                );
+                flex.add(item(), TextEdit::singleline(&mut name).hint_text("Name"));
+                flex.add(item(), TextEdit::singleline(&mut email).hint_text("Email"));
+            });
+        });
+    })
+}
@@ -0,0 +1,35 @@
+use eframe::NativeOptions;
+use egui::{CentralPanel, Frame, Label};
+use egui_flex::{item, Flex, FlexEdge};
+
+fn main() -> eframe::Result {
+    eframe::run_simple_native(file!(), NativeOptions::default(), move |ctx, _frame| {
+        CentralPanel::default().show(ctx, |ui| {
+            Flex::vertical()
+                .scroll(true)
+                .grow_items(1.0)
+                .show(ui, |flex| {
+                    let header_frame = Frame::none()
+                        .fill(flex.ui().visuals().panel_fill)
+                        .inner_margin(4.0);
+                    for section in 0..20 {
+                        // Each header stays on top until the next one pushes it out
+                        flex.add_ui_frame(item().sticky(FlexEdge::Start), header_frame, |ui| {
+                            ui.heading(format!("Section {section}"))
+                        });
+                        for row in 0..50 {
+                            // Only the visible rows are shown
+                            flex.add_virtual(
+                                item(),
+                                Label::new(format!("Row {row} of section {section}")),
+                            );
+                        }
+                    }
+
+                    flex.add_ui_frame(item().sticky(FlexEdge::End), header_frame, |ui| {
+                        ui.label("1000 rows")
+                    });
+                });
+        });
+    })
+}
@@ -0,0 +1,31 @@
+use eframe::NativeOptions;
+use egui::{Button, CentralPanel, TextEdit};
+use egui_flex::{item, Flex};
+
+fn main() -> eframe::Result {
+    let mut search = String::new();
+    let mut filter = String::new();
+    eframe::run_simple_native(file!(), NativeOptions::default(), move |ctx, _frame| {
+        CentralPanel::default().show(ctx, |ui| {
+            ui.label("Resize the window: the text fields shrink before the buttons get cut off");
+
+            // A toolbar that doesn't wrap, the text fields shrink down to their min size
+            Flex::horizontal().wrap(false).show(ui, |flex| {
+                flex.add(item(), Button::new("Back"));
+                flex.add(
+                    item().basis(300.0).shrink(1.0).min_size(80.0),
+                    TextEdit::singleline(&mut search).hint_text("Search"),
+                );
+                flex.add(
+                    item().basis(150.0).shrink(1.0).min_size(60.0),
+                    TextEdit::singleline(&mut filter).hint_text("Filter"),
+                );
+                flex.add(
+                    item().grow(1.0).max_size(200.0),
+                    Button::new("Grows up to 200"),
+                );
+                flex.add(item(), Button::new("Settings"));
+            });
+        });
+    })
+}
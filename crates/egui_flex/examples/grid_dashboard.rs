@@ -0,0 +1,50 @@
+use eframe::NativeOptions;
+use egui::{Button, CentralPanel, Frame, ProgressBar};
+use egui_flex::{grid_item, item, Flex, Grid, GridTrack};
+
+fn main() -> eframe::Result {
+    eframe::run_simple_native(file!(), NativeOptions::default(), move |ctx, _frame| {
+        CentralPanel::default().show(ctx, |ui| {
+            let card = Frame::group(ui.style());
+
+            Grid::new()
+                .columns([GridTrack::px(160.0), GridTrack::fr(1.0), GridTrack::fr(1.0)])
+                .areas([
+                    "sidebar header header",
+                    "sidebar stats  chart",
+                    "sidebar stats  log",
+                ])
+                .show(ui, |grid| {
+                    grid.add_ui_frame(grid_item().area("header"), card, |ui| {
+                        ui.heading("Dashboard");
+                    });
+                    grid.add_flex_frame(
+                        grid_item().area("sidebar"),
+                        Flex::vertical().grow_items(1.0),
+                        card,
+                        |flex| {
+                            flex.add(item(), Button::new("Overview"));
+                            flex.add(item(), Button::new("Reports"));
+                            flex.add(item(), Button::new("Settings"));
+                        },
+                    );
+                    grid.add_ui_frame(grid_item().area("stats"), card, |ui| {
+                        ui.label("Spans two rows");
+                    });
+                    grid.add(
+                        grid_item().area("chart"),
+                        ProgressBar::new(0.7).text("Storage"),
+                    );
+                    grid.add_ui_frame(grid_item().area("log"), card, |ui| {
+                        ui.label("Nothing happened yet");
+                    });
+
+                    // Items without an area are placed in the next free cells
+                    for i in 0..3 {
+                        grid.add(grid_item(), Button::new(format!("Auto placed {i}")));
+                    }
+                    grid.add(grid_item().column_span(3), Button::new("Spans all columns"));
+                });
+        });
+    })
+}
@@ -0,0 +1,133 @@
+use egui::epaint::ClippedShape;
+use egui::{Id, Ui};
+use egui_animation::easing;
+use std::collections::HashMap;
+
+/// The maximum number of shapes copied per item to fade it out once it's removed
+const MAX_RECORDED_SHAPES: usize = 32;
+
+/// The animation state of a flex container with [`crate::Flex::animate`].
+/// This is stored separately from the layout state, so animating doesn't cause discards.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct FlexAnimation {
+    /// `false` until all items were shown once, so the items don't fade in on startup
+    initialized: bool,
+    /// Some items weren't placed in this pass, because they are new and have to be measured first
+    measuring: bool,
+    /// The shapes each item painted in the last pass, to fade them out once the item is removed
+    shapes: HashMap<Id, Vec<ClippedShape>>,
+    /// The shapes of the items painted in this pass
+    next_shapes: HashMap<Id, Vec<ClippedShape>>,
+    /// When new items appeared
+    appeared: HashMap<Id, f64>,
+    /// Removed items that are still fading out
+    leaving: Vec<LeavingItem>,
+}
+
+#[derive(Debug, Clone)]
+struct LeavingItem {
+    shapes: Vec<ClippedShape>,
+    removed_at: f64,
+}
+
+impl FlexAnimation {
+    fn id(flex_id: Id) -> Id {
+        flex_id.with("flex_animation")
+    }
+
+    pub(crate) fn load(ui: &Ui, flex_id: Id) -> Self {
+        ui.ctx()
+            .data(|data| data.get_temp(Self::id(flex_id)))
+            .unwrap_or_default()
+    }
+
+    /// The progress of an item fading in, from 0.0 to 1.0
+    pub(crate) fn appear(&mut self, ui: &Ui, key: Id, time: f32) -> f32 {
+        let now = ui.input(|i| i.time);
+        if self.initialized && !self.shapes.contains_key(&key) {
+            self.appeared.entry(key).or_insert(now);
+        }
+        let Some(appeared) = self.appeared.get(&key) else {
+            return 1.0;
+        };
+        let t = progress(now, *appeared, time);
+        if t >= 1.0 {
+            self.appeared.remove(&key);
+        } else {
+            ui.ctx().request_repaint();
+        }
+        easing::cubic_out(t)
+    }
+
+    /// Mark that an item is measured before it's shown
+    pub(crate) fn measure(&mut self) {
+        self.measuring = true;
+    }
+
+    /// Keep the shapes of an item that wasn't shown in this pass, because it's outside of the
+    /// viewport, so it doesn't fade out
+    pub(crate) fn keep(&mut self, key: Id) {
+        if let Some(shapes) = self.shapes.remove(&key) {
+            self.next_shapes.insert(key, shapes);
+        }
+    }
+
+    /// Remember the shapes the item painted, starting at the shape `first_idx` in the ui's layer.
+    /// This runs for every item in every pass, so items that paint more than
+    /// [`MAX_RECORDED_SHAPES`] shapes aren't copied and won't fade out.
+    pub(crate) fn record(&mut self, ui: &Ui, key: Id, first_idx: usize) {
+        let shapes = ui.ctx().graphics(|graphics| {
+            graphics
+                .get(ui.layer_id())
+                .filter(|list| list.next_idx().0 - first_idx <= MAX_RECORDED_SHAPES)
+                .map(|list| list.all_entries().skip(first_idx).cloned().collect())
+                .unwrap_or_default()
+        });
+        // The item is recorded even without shapes, so it doesn't fade in again
+        self.next_shapes.insert(key, shapes);
+    }
+
+    /// Start fading out the items that weren't shown in this pass, paint the fading items
+    /// and store the state.
+    pub(crate) fn finish(mut self, ui: &Ui, flex_id: Id, time: f32) {
+        let now = ui.input(|i| i.time);
+
+        let shapes = std::mem::take(&mut self.next_shapes);
+        for (key, removed) in std::mem::replace(&mut self.shapes, shapes) {
+            if !self.shapes.contains_key(&key) {
+                self.leaving.push(LeavingItem {
+                    shapes: removed,
+                    removed_at: now,
+                });
+            }
+        }
+
+        self.leaving
+            .retain(|item| progress(now, item.removed_at, time) < 1.0);
+        for item in &self.leaving {
+            let mut painter = ui.painter().clone();
+            painter.multiply_opacity(1.0 - easing::cubic_out(progress(now, item.removed_at, time)));
+            for shape in &item.shapes {
+                painter
+                    .with_clip_rect(shape.clip_rect)
+                    .add(shape.shape.clone());
+            }
+        }
+        if !self.leaving.is_empty() {
+            ui.ctx().request_repaint();
+        }
+
+        self.initialized |= !self.measuring;
+        self.measuring = false;
+        ui.ctx()
+            .data_mut(|data| data.insert_temp(Self::id(flex_id), self));
+    }
+}
+
+fn progress(now: f64, start: f64, time: f32) -> f32 {
+    if time <= 0.0 {
+        1.0
+    } else {
+        ((now - start) as f32 / time).clamp(0.0, 1.0)
+    }
+}
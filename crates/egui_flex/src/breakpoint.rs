@@ -0,0 +1,32 @@
+/// Named breakpoints for responsive layouts, based on the available width
+/// (these match the window size classes of material design).
+///
+/// The breakpoints are ordered, so you can check e.g. `breakpoint >= FlexBreakpoint::Medium`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum FlexBreakpoint {
+    /// Less than [`FlexBreakpoint::MEDIUM_WIDTH`], e.g. a phone in portrait mode.
+    Compact,
+    /// Between [`FlexBreakpoint::MEDIUM_WIDTH`] and [`FlexBreakpoint::EXPANDED_WIDTH`],
+    /// e.g. a tablet in portrait mode.
+    Medium,
+    /// At least [`FlexBreakpoint::EXPANDED_WIDTH`], e.g. a desktop window.
+    Expanded,
+}
+
+impl FlexBreakpoint {
+    /// The min width of [`FlexBreakpoint::Medium`].
+    pub const MEDIUM_WIDTH: f32 = 600.0;
+    /// The min width of [`FlexBreakpoint::Expanded`].
+    pub const EXPANDED_WIDTH: f32 = 840.0;
+
+    /// Get the breakpoint for the available width.
+    pub fn from_width(width: f32) -> Self {
+        if width >= Self::EXPANDED_WIDTH {
+            Self::Expanded
+        } else if width >= Self::MEDIUM_WIDTH {
+            Self::Medium
+        } else {
+            Self::Compact
+        }
+    }
+}
@@ -0,0 +1,161 @@
+use crate::{FlexInstance, ItemState, RowData};
+use egui::{Align2, Color32, Context, FontId, Id, Margin, Painter, Rect, Stroke, Ui};
+
+const ROW_COLOR: Color32 = Color32::from_rgb(0, 140, 255);
+const ITEM_COLOR: Color32 = Color32::from_rgb(0, 200, 80);
+const MARGIN_COLOR: Color32 = Color32::from_rgba_premultiplied(80, 50, 0, 80);
+const FREE_SPACE_COLOR: Color32 = Color32::from_rgba_premultiplied(60, 0, 60, 60);
+const GROW_COLOR: Color32 = Color32::from_rgb(80, 255, 120);
+const SHRINK_COLOR: Color32 = Color32::from_rgb(255, 100, 100);
+
+/// A debug overlay for flex containers, showing how the items were laid out.
+///
+/// It shows the rect of each row (blue) and item (green), the margin of the items (orange),
+/// the free space in the rows (purple) and how much each item grew or shrunk. Hover an item to
+/// see its computed basis, min size and final size.
+///
+/// Enable it for a single container with [`crate::Flex::debug`], or for all containers with
+/// [`FlexDebug::set_enabled`].
+pub struct FlexDebug;
+
+impl FlexDebug {
+    fn id() -> Id {
+        Id::new("egui_flex_debug")
+    }
+
+    /// Show the debug overlay for all flex containers
+    pub fn set_enabled(ctx: &Context, enabled: bool) {
+        ctx.data_mut(|data| data.insert_temp(Self::id(), enabled));
+    }
+
+    /// Is the debug overlay enabled for all flex containers?
+    pub fn is_enabled(ctx: &Context) -> bool {
+        ctx.data(|data| data.get_temp(Self::id()).unwrap_or_default())
+    }
+
+    /// Paint the overlay for the rows of the flex container
+    pub(crate) fn paint(instance: &FlexInstance) {
+        let ui = &*instance.ui;
+        let painter = ui.ctx().debug_painter();
+        let direction = instance.direction;
+        let reverse = instance.flex.direction.is_reverse();
+
+        for row in &instance.rows {
+            let Some(row_rect) = row.rect else {
+                continue;
+            };
+            painter.rect_stroke(row_rect, 0.0, Stroke::new(1.0, ROW_COLOR));
+            paint_free_space(&painter, row, direction, reverse);
+
+            for (index_in_row, index) in row.item_indices.iter().enumerate() {
+                let item = &row.items[index_in_row];
+                let rect = instance.frame_rects.get(*index).copied().flatten();
+                let rect = rect.unwrap_or_else(|| row.item_rect(index_in_row, direction, reverse));
+                let size = row.item_sizes[index_in_row];
+
+                for margin_rect in margin_rects(rect, item.margin) {
+                    painter.rect_filled(margin_rect, 0.0, MARGIN_COLOR);
+                }
+                painter.rect_stroke(rect, 0.0, Stroke::new(1.0, ITEM_COLOR));
+
+                // How much the item grew or shrunk from its basis
+                let delta = size - item.hypothetical_size(direction);
+                if delta.abs() >= 0.5 {
+                    let (text, color) = if delta > 0.0 {
+                        (format!("+{delta:.0}"), GROW_COLOR)
+                    } else {
+                        (format!("{delta:.0}"), SHRINK_COLOR)
+                    };
+                    painter.text(
+                        rect.right_top(),
+                        Align2::RIGHT_TOP,
+                        text,
+                        FontId::monospace(10.0),
+                        color,
+                    );
+                }
+
+                if ui.rect_contains_pointer(rect) {
+                    egui::show_tooltip_at_pointer(
+                        ui.ctx(),
+                        ui.layer_id(),
+                        instance.id.with(("flex_debug", index)),
+                        |ui| item_tooltip(ui, *index, item, size, direction),
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Show the computed sizes of the item in the main direction
+fn item_tooltip(ui: &mut Ui, index: usize, item: &ItemState, size: f32, direction: usize) {
+    let config = &item.config;
+    let optional = |value: Option<f32>| value.map_or_else(|| "-".to_owned(), |v| format!("{v:.1}"));
+
+    ui.strong(format!("Flex item {index}"));
+    egui::Grid::new("flex_debug_tooltip").show(ui, |ui| {
+        let mut row = |label: &str, value: String| {
+            ui.label(label);
+            ui.monospace(value);
+            ui.end_row();
+        };
+        row(
+            "basis",
+            format!(
+                "{:.1} ({})",
+                item.base_size(direction),
+                if config.basis.is_some() {
+                    "set"
+                } else {
+                    "content"
+                }
+            ),
+        );
+        row(
+            "content min size",
+            format!("{:.1}", item.inner_min_size[direction]),
+        );
+        row("min size", optional(config.min_size));
+        row("max size", optional(config.max_size));
+        row(
+            "hypothetical size",
+            format!("{:.1}", item.hypothetical_size(direction)),
+        );
+        row("grow", optional(config.grow));
+        row("shrink", optional(config.shrink));
+        row("margin", format!("{:.1}", item.margin.sum()[direction]));
+        row("final size", format!("{size:.1}"));
+    });
+}
+
+/// Paint the space in the row that isn't taken up by items
+fn paint_free_space(painter: &Painter, row: &RowData, direction: usize, reverse: bool) {
+    let (Some(row_rect), Some(last)) = (row.rect, row.items.len().checked_sub(1)) else {
+        return;
+    };
+    let content_end = row.item_rect(last, direction, reverse);
+    let mut free_space = row_rect;
+    if reverse {
+        free_space.max[direction] = content_end.min[direction];
+    } else {
+        free_space.min[direction] = content_end.max[direction];
+    }
+    if free_space.size()[direction] > 0.0 {
+        painter.rect_filled(free_space, 0.0, FREE_SPACE_COLOR);
+    }
+}
+
+/// The rects of the margin around the inner rect of an item
+fn margin_rects(rect: Rect, margin: Margin) -> [Rect; 4] {
+    let inner = Rect::from_min_max(
+        rect.min + margin.left_top(),
+        rect.max - margin.right_bottom(),
+    );
+    [
+        Rect::from_x_y_ranges(rect.x_range(), rect.top()..=inner.top()),
+        Rect::from_x_y_ranges(rect.x_range(), inner.bottom()..=rect.bottom()),
+        Rect::from_x_y_ranges(rect.left()..=inner.left(), inner.y_range()),
+        Rect::from_x_y_ranges(inner.right()..=rect.right(), inner.y_range()),
+    ]
+}
@@ -1,5 +1,5 @@
 use crate::{FlexContainerResponse, FlexContainerUi};
-use egui::Ui;
+use egui::{Frame, Ui};
 
 /// Implement this trait for a widget to make it usable in a flex container.
 ///
@@ -23,6 +23,112 @@ pub trait FlexWidget {
     ) -> FlexContainerResponse<Self::Response>;
 }
 
+/// A widget that consists of a [`Frame`] and some content, e.g. a card or a custom button.
+///
+/// Implementing this trait implements [`FlexWidget`] for the widget: the frame will be stretched
+/// according to the flex layout, while the content keeps its size and is positioned based on
+/// [`crate::FlexItem::align_self_content`].
+///
+/// ```
+/// # use egui::{Frame, Ui};
+/// # use egui_flex::{item, Flex, FlexFrameWidget};
+/// struct Card<'a>(&'a str);
+///
+/// impl FlexFrameWidget for Card<'_> {
+///     type Response = ();
+///
+///     fn frame(&self, ui: &Ui) -> Frame {
+///         Frame::group(ui.style())
+///     }
+///
+///     fn content(self, ui: &mut Ui) {
+///         ui.label(self.0);
+///     }
+/// }
+///
+/// # egui::__run_test_ui(|ui| {
+/// Flex::horizontal().show(ui, |flex| {
+///     flex.add(item().grow(1.0), Card("Hello"));
+/// });
+/// # });
+/// ```
+pub trait FlexFrameWidget {
+    /// The response type of the widget
+    type Response;
+    /// The frame around the content, e.g. based on the style of the [`Ui`].
+    fn frame(&self, ui: &Ui) -> Frame;
+    /// Show the content inside the frame.
+    fn content(self, ui: &mut Ui) -> Self::Response;
+}
+
+impl<T: FlexFrameWidget> FlexWidget for T {
+    type Response = T::Response;
+
+    fn flex_ui(
+        self,
+        ui: &mut Ui,
+        container: FlexContainerUi,
+    ) -> FlexContainerResponse<Self::Response> {
+        self.frame(ui)
+            .show(ui, |ui| container.content(ui, |ui| self.content(ui)))
+            .inner
+    }
+}
+
+/// Show an [`egui_extras::TableBuilder`] table as a flex item.
+///
+/// The table is built in the item's content [`Ui`], so columns like
+/// [`egui_extras::Column::remainder`] fill the item. Use [`crate::FlexItem::basis`] or
+/// [`crate::FlexItem::shrink`] to limit its size.
+///
+/// ```
+/// # use egui_extras::Column;
+/// # use egui_flex::{item, Flex, FlexTable};
+/// # egui::__run_test_ui(|ui| {
+/// Flex::horizontal().show(ui, |flex| {
+///     flex.add(
+///         item().grow(1.0).basis(200.0),
+///         FlexTable::new(|table| {
+///             table.column(Column::remainder()).body(|mut body| {
+///                 body.row(20.0, |mut row| {
+///                     row.col(|ui| {
+///                         ui.label("Cell");
+///                     });
+///                 });
+///             });
+///         }),
+///     );
+/// });
+/// # });
+/// ```
+#[cfg(feature = "egui_extras")]
+pub struct FlexTable<F> {
+    build: F,
+}
+
+#[cfg(feature = "egui_extras")]
+impl<F, R> FlexTable<F>
+where
+    F: FnOnce(egui_extras::TableBuilder<'_>) -> R,
+{
+    /// Create a table, configure the [`egui_extras::TableBuilder`] and show the table in `build`.
+    pub fn new(build: F) -> Self {
+        Self { build }
+    }
+}
+
+#[cfg(feature = "egui_extras")]
+impl<F, R> FlexWidget for FlexTable<F>
+where
+    F: FnOnce(egui_extras::TableBuilder<'_>) -> R,
+{
+    type Response = R;
+
+    fn flex_ui(self, ui: &mut Ui, container: FlexContainerUi) -> FlexContainerResponse<R> {
+        container.content(ui, |ui| (self.build)(egui_extras::TableBuilder::new(ui)))
+    }
+}
+
 mod egui_widgets {
     use super::{FlexContainerResponse, FlexContainerUi, FlexWidget, Ui};
     use egui::widgets::{
@@ -59,3 +165,110 @@ mod egui_widgets {
         Spinner
     );
 }
+
+#[cfg(feature = "datepicker")]
+impl FlexWidget for egui_extras::DatePickerButton<'_> {
+    type Response = egui::Response;
+
+    fn flex_ui(
+        self,
+        ui: &mut Ui,
+        container: FlexContainerUi,
+    ) -> FlexContainerResponse<Self::Response> {
+        container.content_widget(ui, self)
+    }
+}
+
+#[cfg(feature = "egui_thumbhash")]
+impl FlexWidget for egui_thumbhash::ThumbhashImage<'_, '_> {
+    type Response = egui::Response;
+
+    fn flex_ui(
+        self,
+        ui: &mut Ui,
+        container: FlexContainerUi,
+    ) -> FlexContainerResponse<Self::Response> {
+        container.content_widget(ui, self)
+    }
+}
+
+#[cfg(feature = "egui_material_icons")]
+impl FlexWidget for egui_material_icons::IconButton<'_> {
+    type Response = egui::Response;
+
+    fn flex_ui(
+        self,
+        ui: &mut Ui,
+        container: FlexContainerUi,
+    ) -> FlexContainerResponse<Self::Response> {
+        container.content_widget(ui, self)
+    }
+}
+
+/// A [`egui_form::FormField`] with the widget it validates. The widget fills the width of the
+/// item, the label and error message are shown above and below it.
+///
+/// The item is measured as wide as the widget's intrinsic size (or
+/// [`egui::style::Spacing::interact_size`] for widgets that don't report one, like with
+/// [`FlexContainerUi::content_widget`]) and as high as the whole field, including the label and
+/// the space reserved for the error message.
+///
+/// ```
+/// # use egui_flex::{item, Flex};
+/// # use egui_form::garde::GardeReport;
+/// # use egui_form::{Form, FormField};
+/// # use garde::Validate;
+/// #[derive(Validate)]
+/// struct User {
+///     #[garde(length(min = 1))]
+///     name: String,
+/// }
+///
+/// # egui::__run_test_ui(|ui| {
+/// # let mut user = User { name: String::new() };
+/// let mut form = Form::new().add_report(GardeReport::new(user.validate()));
+/// Flex::horizontal().show(ui, |flex| {
+///     flex.add(
+///         item().grow(1.0),
+///         (
+///             FormField::new(&mut form, "name").label("Name"),
+///             egui::TextEdit::singleline(&mut user.name),
+///         ),
+///     );
+/// });
+/// # });
+/// ```
+#[cfg(feature = "egui_form")]
+impl<Errors, W> FlexWidget for (egui_form::FormField<'_, '_, Errors>, W)
+where
+    Errors: egui_form::EguiValidationReport,
+    W: egui::Widget,
+{
+    type Response = egui::Response;
+
+    fn flex_ui(
+        self,
+        ui: &mut Ui,
+        container: FlexContainerUi,
+    ) -> FlexContainerResponse<Self::Response> {
+        let (field, widget) = self;
+        container.content_widget(ui, |ui: &mut Ui| {
+            let field = ui.scope(|ui| {
+                // The form field doesn't justify its content, so the widget is justified here
+                field.ui(ui, |ui: &mut Ui| {
+                    let layout = egui::Layout::top_down_justified(egui::Align::Min);
+                    ui.with_layout(layout, |ui| ui.add(widget)).inner
+                })
+            });
+            // The field is as wide as the widget, but also includes the label and error message.
+            // Without an intrinsic size the width falls back to `interact_size`, like in
+            // `content_widget`, but the height is always the field's own height.
+            let mut response = field.inner;
+            let width = response
+                .intrinsic_size
+                .map_or(ui.spacing().interact_size.x, |size| size.x);
+            response.intrinsic_size = Some(egui::vec2(width, field.response.rect.height()));
+            response
+        })
+    }
+}
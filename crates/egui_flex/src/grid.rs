@@ -0,0 +1,955 @@
+use crate::{
+    round, round_rect, round_vec2, sizing, Flex, FlexContainerResponse, FlexContainerUi,
+    FlexInstance, FlexWidget,
+};
+use egui::{
+    Align, Align2, Frame, Id, InnerResponse, Layout, Margin, Pos2, Rect, Response, Sense, Ui,
+    UiBuilder, Vec2, Widget,
+};
+
+/// The min or max size of a [`GridTrack`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GridTrackSize {
+    /// A fixed size in points (css `px`).
+    Px(f32),
+    /// A fraction of the free space (css `fr`). Only allowed as the max size of a track,
+    /// as min size it's the same as [`GridTrackSize::Auto`].
+    Fr(f32),
+    /// The size of the largest item in the track (css `auto`).
+    Auto,
+}
+
+/// The size of a column or row of a [`Grid`] (like the values of css `grid-template-columns`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GridTrack {
+    min: GridTrackSize,
+    max: GridTrackSize,
+}
+
+impl GridTrack {
+    /// A track with a fixed size (css `100px`).
+    pub fn px(size: f32) -> Self {
+        Self::minmax(GridTrackSize::Px(size), GridTrackSize::Px(size))
+    }
+
+    /// A track that takes a fraction of the free space (css `1fr`).
+    /// It's at least as big as its content, like in css.
+    pub fn fr(fraction: f32) -> Self {
+        Self::minmax(GridTrackSize::Auto, GridTrackSize::Fr(fraction))
+    }
+
+    /// A track that is as big as its largest item (css `auto`).
+    /// If there are no [`GridTrack::fr`] tracks, auto tracks are stretched to fill the grid.
+    pub fn auto() -> Self {
+        Self::minmax(GridTrackSize::Auto, GridTrackSize::Auto)
+    }
+
+    /// A track that is at least `min` and at most `max` big (css `minmax(min, max)`).
+    pub fn minmax(min: GridTrackSize, max: GridTrackSize) -> Self {
+        let min = match min {
+            GridTrackSize::Fr(_) => GridTrackSize::Auto,
+            min => min,
+        };
+        Self { min, max }
+    }
+
+    fn fraction(self) -> Option<f32> {
+        match self.max {
+            GridTrackSize::Fr(fraction) => Some(fraction),
+            _ => None,
+        }
+    }
+}
+
+/// Configuration for a grid item.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GridItem {
+    column: Option<usize>,
+    row: Option<usize>,
+    column_span: Option<usize>,
+    row_span: Option<usize>,
+    area: Option<String>,
+    align_content: Option<Align2>,
+}
+
+/// Create a new grid item. Shorthand for [`GridItem::default`].
+pub fn grid_item() -> GridItem {
+    GridItem::default()
+}
+
+impl GridItem {
+    /// Create a new grid item. You can also use the [`grid_item`] function.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Place the item in this column (starting at 0).
+    /// If this is not set, the item is placed in the next free cell.
+    pub fn column(mut self, column: usize) -> Self {
+        self.column = Some(column);
+        self
+    }
+
+    /// Place the item in this row (starting at 0).
+    /// If this is not set, the item is placed in the next free cell.
+    pub fn row(mut self, row: usize) -> Self {
+        self.row = Some(row);
+        self
+    }
+
+    /// How many columns the item spans.
+    ///
+    /// Default is 1.
+    pub fn column_span(mut self, column_span: usize) -> Self {
+        self.column_span = Some(column_span);
+        self
+    }
+
+    /// How many rows the item spans.
+    ///
+    /// Default is 1.
+    pub fn row_span(mut self, row_span: usize) -> Self {
+        self.row_span = Some(row_span);
+        self
+    }
+
+    /// Place the item in a named area of [`Grid::areas`] (css `grid-area`).
+    /// This overrides the column, row and spans.
+    pub fn area(mut self, area: impl Into<String>) -> Self {
+        self.area = Some(area.into());
+        self
+    }
+
+    /// How do we align the content within the cell?
+    ///
+    /// Default is `center`.
+    pub fn align_self_content(mut self, align_self_content: Align2) -> Self {
+        self.align_content = Some(align_self_content);
+        self
+    }
+}
+
+/// A grid container, laying out the items in columns and rows (like css grid).
+///
+/// Like [`Flex`], the sizes of the items are remembered from the previous frame.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Grid {
+    id_salt: Option<Id>,
+    columns: Vec<GridTrack>,
+    rows: Vec<GridTrack>,
+    auto_rows: GridTrack,
+    areas: Vec<String>,
+    gap: Option<Vec2>,
+    align_items_content: Option<Align2>,
+    fill_height: bool,
+    max_sizing_passes: usize,
+}
+
+impl Default for Grid {
+    fn default() -> Self {
+        Self {
+            id_salt: None,
+            columns: vec![],
+            rows: vec![],
+            auto_rows: GridTrack::auto(),
+            areas: vec![],
+            gap: None,
+            align_items_content: None,
+            fill_height: false,
+            max_sizing_passes: 3,
+        }
+    }
+}
+
+/// Where an item is placed in the grid
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Placement {
+    column: usize,
+    row: usize,
+    column_span: usize,
+    row_span: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct GridItemState {
+    config: GridItem,
+    inner_size: Vec2,
+    inner_min_size: Vec2,
+    margin: Margin,
+    remeasure_widget: bool,
+}
+
+impl GridItemState {
+    fn min_size_with_margin(&self) -> Vec2 {
+        self.inner_min_size + self.margin.sum()
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+struct GridState {
+    items: Vec<GridItemState>,
+}
+
+/// The size an item needs along one axis, to size the tracks it spans
+struct TrackItem {
+    start: usize,
+    span: usize,
+    size: f32,
+}
+
+impl Grid {
+    /// Create a new grid container.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the columns of the grid (css `grid-template-columns`).
+    /// Items placed outside of these columns get implicit [`GridTrack::auto`] columns.
+    ///
+    /// Default is a single implicit column.
+    pub fn columns(mut self, columns: impl IntoIterator<Item = GridTrack>) -> Self {
+        self.columns = columns.into_iter().collect();
+        self
+    }
+
+    /// Set the rows of the grid (css `grid-template-rows`).
+    /// Additional rows are added as needed, see [`Self::auto_rows`].
+    pub fn rows(mut self, rows: impl IntoIterator<Item = GridTrack>) -> Self {
+        self.rows = rows.into_iter().collect();
+        self
+    }
+
+    /// The size of the rows that are added when the items don't fit in [`Self::rows`]
+    /// (css `grid-auto-rows`).
+    ///
+    /// Default is [`GridTrack::auto`].
+    pub fn auto_rows(mut self, auto_rows: GridTrack) -> Self {
+        self.auto_rows = auto_rows;
+        self
+    }
+
+    /// Name areas of the grid (css `grid-template-areas`), to place items with [`GridItem::area`].
+    /// Each string is a row, with the names of the cells separated by whitespace.
+    /// Use `.` for cells without a name. Areas have to be rectangular.
+    ///
+    /// ```
+    /// # use egui_flex::Grid;
+    /// Grid::new().areas(["header header", "sidebar main"]);
+    /// ```
+    pub fn areas(mut self, areas: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.areas = areas.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Set the gap between the columns (x) and rows (y).
+    ///
+    /// Default is `item_spacing` of the [`Ui`].
+    pub fn gap(mut self, gap: Vec2) -> Self {
+        self.gap = Some(gap);
+        self
+    }
+
+    /// How do we align the content of the items within their cells?
+    ///
+    /// Default is `center`.
+    pub fn align_items_content(mut self, align_items_content: Align2) -> Self {
+        self.align_items_content = Some(align_items_content);
+        self
+    }
+
+    /// Should the rows fill the available height of the [`Ui`]?
+    /// If this is `false`, [`GridTrack::fr`] rows share the height of their content.
+    ///
+    /// Default is `false`.
+    pub fn fill_height(mut self, fill_height: bool) -> Self {
+        self.fill_height = fill_height;
+        self
+    }
+
+    /// Customize the id of the grid container to prevent conflicts with other grid containers.
+    pub fn id_salt(mut self, id_salt: impl Into<Id>) -> Self {
+        self.id_salt = Some(id_salt.into());
+        self
+    }
+
+    /// The maximum number of sizing passes [`Self::show_measured`] runs to measure the items.
+    ///
+    /// Default is 3.
+    pub fn max_sizing_passes(mut self, max_sizing_passes: usize) -> Self {
+        self.max_sizing_passes = max_sizing_passes;
+        self
+    }
+
+    /// Show the grid. It will take up the available width of the [`Ui`].
+    #[track_caller]
+    pub fn show<R>(self, ui: &mut Ui, f: impl FnOnce(&mut GridInstance) -> R) -> InnerResponse<R> {
+        let mut f = Some(f);
+        self.show_inside(ui, 0, |instance| {
+            f.take().expect("show only runs a single pass")(instance)
+        })
+    }
+
+    /// Like [`Self::show`], but the items are measured in invisible sizing passes before they
+    /// are shown, instead of discarding the frame when the size of an item changed.
+    /// `f` runs up to [`Self::max_sizing_passes`] + 1 times per frame, so it must not have side
+    /// effects of its own, see [`Flex::show_measured`].
+    #[track_caller]
+    pub fn show_measured<R>(
+        self,
+        ui: &mut Ui,
+        f: impl FnMut(&mut GridInstance) -> R,
+    ) -> InnerResponse<R> {
+        let sizing_passes = self.max_sizing_passes;
+        self.show_inside(ui, sizing_passes, f)
+    }
+
+    #[track_caller]
+    fn show_inside<R>(
+        self,
+        ui: &mut Ui,
+        sizing_passes: usize,
+        mut f: impl FnMut(&mut GridInstance) -> R,
+    ) -> InnerResponse<R> {
+        let id = if let Some(id_salt) = self.id_salt {
+            ui.id().with(id_salt)
+        } else {
+            ui.auto_id_with("grid")
+        };
+        let builder = UiBuilder::new().max_rect(round_rect(ui.available_rect_before_wrap()));
+
+        sizing::show_measured(
+            ui,
+            id,
+            builder,
+            sizing_passes,
+            "Grid item added / removed / size changed",
+            |ui, previous_state: &GridState| self.layout_items(ui, previous_state, &mut f),
+        )
+    }
+
+    /// Add the items to the ui, placed in the cells computed from the previous pass.
+    fn layout_items<R>(
+        &self,
+        ui: &mut Ui,
+        previous_state: &GridState,
+        f: &mut impl FnMut(&mut GridInstance) -> R,
+    ) -> (R, GridState) {
+        let gap = self.gap.unwrap_or(ui.spacing().item_spacing);
+
+        // We ceil in order to prevent rounding errors from overflowing the ui
+        let mut available_size = ui.available_size().ceil();
+        if !self.fill_height {
+            available_size.y = f32::INFINITY;
+        }
+
+        let origin = ui.max_rect().min;
+        let (cells, size) = self.layout_cells(previous_state, available_size, gap, origin);
+
+        let mut instance = GridInstance {
+            grid: self,
+            previous_items: &previous_state.items,
+            cells,
+            state: GridState::default(),
+            max_item_size: round_vec2(ui.available_size()),
+            ui,
+        };
+
+        let r = f(&mut instance);
+
+        instance
+            .ui
+            .allocate_rect(Rect::from_min_size(origin, size), Sense::hover());
+
+        (r, instance.state)
+    }
+
+    /// Compute the cell of each item and the size of the grid
+    fn layout_cells(
+        &self,
+        state: &GridState,
+        available_size: Vec2,
+        gap: Vec2,
+        origin: Pos2,
+    ) -> (Vec<Rect>, Vec2) {
+        let configs: Vec<&GridItem> = state.items.iter().map(|item| &item.config).collect();
+        let (placements, column_count, row_count) = self.place_items(&configs);
+
+        let column_tracks: Vec<GridTrack> = (0..column_count)
+            .map(|i| self.columns.get(i).copied().unwrap_or_else(GridTrack::auto))
+            .collect();
+        let row_tracks: Vec<GridTrack> = (0..row_count)
+            .map(|i| self.rows.get(i).copied().unwrap_or(self.auto_rows))
+            .collect();
+
+        let track_items = |axis: usize| -> Vec<TrackItem> {
+            placements
+                .iter()
+                .zip(&state.items)
+                .map(|(placement, item)| {
+                    let (start, span) = if axis == 0 {
+                        (placement.column, placement.column_span)
+                    } else {
+                        (placement.row, placement.row_span)
+                    };
+                    TrackItem {
+                        start,
+                        span,
+                        size: item.min_size_with_margin()[axis],
+                    }
+                })
+                .collect()
+        };
+
+        let columns = resolve_tracks(&column_tracks, &track_items(0), available_size.x, gap.x);
+        let rows = resolve_tracks(&row_tracks, &track_items(1), available_size.y, gap.y);
+
+        // The position of each track and the position after the last track
+        let positions = |sizes: &[f32], gap: f32, start: f32| -> Vec<f32> {
+            let mut position = start;
+            let mut positions = vec![position];
+            for size in sizes {
+                position += size + gap;
+                positions.push(position);
+            }
+            positions
+        };
+        let column_positions = positions(&columns, gap.x, origin.x);
+        let row_positions = positions(&rows, gap.y, origin.y);
+
+        let cells = placements
+            .iter()
+            .map(|placement| {
+                let column_end = placement.column + placement.column_span;
+                let row_end = placement.row + placement.row_span;
+                round_rect(Rect::from_min_max(
+                    Pos2::new(
+                        column_positions[placement.column],
+                        row_positions[placement.row],
+                    ),
+                    Pos2::new(
+                        column_positions[column_end] - gap.x,
+                        row_positions[row_end] - gap.y,
+                    ),
+                ))
+            })
+            .collect();
+
+        let size = Vec2::new(track_length(&columns, gap.x), track_length(&rows, gap.y));
+
+        (cells, round_vec2(size))
+    }
+
+    /// Find the named area in [`Self::areas`]
+    fn area(&self, name: &str) -> Option<Placement> {
+        let mut area: Option<(usize, usize, usize, usize)> = None;
+        for (row, cells) in self.areas.iter().enumerate() {
+            for (column, cell) in cells.split_whitespace().enumerate() {
+                if cell == name {
+                    area = Some(match area {
+                        Some((min_row, min_column, max_row, max_column)) => (
+                            min_row.min(row),
+                            min_column.min(column),
+                            max_row.max(row),
+                            max_column.max(column),
+                        ),
+                        None => (row, column, row, column),
+                    });
+                }
+            }
+        }
+        area.map(|(min_row, min_column, max_row, max_column)| Placement {
+            column: min_column,
+            row: min_row,
+            column_span: max_column - min_column + 1,
+            row_span: max_row - min_row + 1,
+        })
+    }
+
+    /// The requested column and row of the item (if they are set) and its column and row span
+    fn requested(&self, item: &GridItem) -> (Option<usize>, Option<usize>, usize, usize) {
+        if let Some(area) = item.area.as_deref().and_then(|name| self.area(name)) {
+            (
+                Some(area.column),
+                Some(area.row),
+                area.column_span,
+                area.row_span,
+            )
+        } else {
+            (
+                item.column,
+                item.row,
+                item.column_span.unwrap_or(1).max(1),
+                item.row_span.unwrap_or(1).max(1),
+            )
+        }
+    }
+
+    /// Place the items following the css grid auto placement algorithm (with `grid-auto-flow: row`):
+    /// items with a row and column are placed first, then items with only a row, then the rest
+    /// in the next free cell.
+    /// Returns the placement of each item and the number of columns and rows.
+    fn place_items(&self, items: &[&GridItem]) -> (Vec<Placement>, usize, usize) {
+        let requested: Vec<_> = items.iter().map(|item| self.requested(item)).collect();
+
+        let area_columns = self
+            .areas
+            .iter()
+            .map(|row| row.split_whitespace().count())
+            .max()
+            .unwrap_or(0);
+        let column_count = requested
+            .iter()
+            .map(|(column, _, column_span, _)| column.unwrap_or(0) + column_span)
+            .chain([self.columns.len(), area_columns, 1])
+            .max()
+            .unwrap_or(1);
+
+        let mut grid = Occupancy {
+            cells: vec![],
+            column_count,
+        };
+        let mut placements: Vec<Option<Placement>> = vec![None; items.len()];
+
+        for (i, (column, row, column_span, row_span)) in requested.iter().copied().enumerate() {
+            if let (Some(column), Some(row)) = (column, row) {
+                let placement = Placement {
+                    column,
+                    row,
+                    column_span,
+                    row_span,
+                };
+                grid.occupy(placement);
+                placements[i] = Some(placement);
+            }
+        }
+
+        for (i, (column, row, column_span, row_span)) in requested.iter().copied().enumerate() {
+            if let (None, Some(row)) = (column, row) {
+                let column = (0..column_count)
+                    .find(|column| grid.fits(row, *column, row_span, column_span))
+                    .unwrap_or(0);
+                let placement = Placement {
+                    column,
+                    row,
+                    column_span,
+                    row_span,
+                };
+                grid.occupy(placement);
+                placements[i] = Some(placement);
+            }
+        }
+
+        let mut cursor = (0, 0);
+        for (i, (column, row, column_span, row_span)) in requested.iter().copied().enumerate() {
+            if row.is_some() {
+                continue;
+            }
+            let (row, column) = if let Some(column) = column {
+                let mut row = cursor.0;
+                if column < cursor.1 {
+                    row += 1;
+                }
+                while !grid.fits(row, column, row_span, column_span) {
+                    row += 1;
+                }
+                (row, column)
+            } else {
+                let (mut row, mut column) = cursor;
+                while !grid.fits(row, column, row_span, column_span) {
+                    column += 1;
+                    if column + column_span > column_count {
+                        column = 0;
+                        row += 1;
+                    }
+                }
+                (row, column)
+            };
+            let placement = Placement {
+                column,
+                row,
+                column_span,
+                row_span,
+            };
+            grid.occupy(placement);
+            placements[i] = Some(placement);
+            cursor = (row, column + column_span);
+        }
+
+        let row_count = usize::max(grid.cells.len(), self.rows.len());
+        (
+            placements.into_iter().map(Option::unwrap).collect(),
+            column_count,
+            row_count,
+        )
+    }
+}
+
+/// The occupied cells of the grid, used for auto placement
+struct Occupancy {
+    cells: Vec<Vec<bool>>,
+    column_count: usize,
+}
+
+impl Occupancy {
+    fn fits(&self, row: usize, column: usize, row_span: usize, column_span: usize) -> bool {
+        // Items that span more columns than the grid has are placed in the first column
+        if column + column_span > self.column_count && column > 0 {
+            return false;
+        }
+        (row..row + row_span).all(|row| {
+            !self.cells.get(row).is_some_and(|cells| {
+                (column..column + column_span).any(|column| cells.get(column) == Some(&true))
+            })
+        })
+    }
+
+    fn occupy(&mut self, placement: Placement) {
+        let row_end = placement.row + placement.row_span;
+        if self.cells.len() < row_end {
+            self.cells.resize(row_end, vec![false; self.column_count]);
+        }
+        for row in &mut self.cells[placement.row..row_end] {
+            for column in placement.column..placement.column + placement.column_span {
+                if let Some(cell) = row.get_mut(column) {
+                    *cell = true;
+                }
+            }
+        }
+    }
+}
+
+/// The total length of the tracks, including the gaps
+fn track_length(sizes: &[f32], gap: f32) -> f32 {
+    sizes.iter().sum::<f32>() + gap * sizes.len().saturating_sub(1) as f32
+}
+
+/// Compute the size of the tracks along one axis, following a simplified version of the
+/// css [grid track sizing algorithm](https://www.w3.org/TR/css-grid-1/#algo-track-sizing).
+///
+/// `available` may be infinite, in which case the tracks are sized to their content.
+fn resolve_tracks(tracks: &[GridTrack], items: &[TrackItem], available: f32, gap: f32) -> Vec<f32> {
+    let gaps = gap * tracks.len().saturating_sub(1) as f32;
+
+    let mut base: Vec<f32> = tracks
+        .iter()
+        .map(|track| match track.min {
+            GridTrackSize::Px(size) => size,
+            _ => 0.0,
+        })
+        .collect();
+    let mut limit: Vec<f32> = tracks
+        .iter()
+        .map(|track| match track.max {
+            GridTrackSize::Px(size) => size,
+            GridTrackSize::Auto => 0.0,
+            GridTrackSize::Fr(_) => f32::INFINITY,
+        })
+        .collect();
+
+    // Items in a single track size the track directly
+    for item in items.iter().filter(|item| item.span == 1) {
+        let track = tracks[item.start];
+        if track.min == GridTrackSize::Auto {
+            base[item.start] = f32::max(base[item.start], item.size);
+        }
+        if track.max == GridTrackSize::Auto {
+            limit[item.start] = f32::max(limit[item.start], item.size);
+        }
+    }
+
+    // Spanning items distribute the missing size to the auto tracks they span, smaller spans first
+    let mut spanning: Vec<&TrackItem> = items.iter().filter(|item| item.span > 1).collect();
+    spanning.sort_by_key(|item| item.span);
+    for item in spanning {
+        let range = item.start..item.start + item.span;
+        let auto_tracks: Vec<usize> = range
+            .clone()
+            .filter(|i| tracks[*i].min == GridTrackSize::Auto)
+            .collect();
+        let current = range.map(|i| base[i]).sum::<f32>() + gap * (item.span - 1) as f32;
+        let missing = item.size - current;
+        if missing > 0.0 && !auto_tracks.is_empty() {
+            let extra = missing / auto_tracks.len() as f32;
+            for i in auto_tracks {
+                base[i] += extra;
+            }
+        }
+    }
+
+    for (base, limit) in base.iter().zip(&mut limit) {
+        *limit = f32::max(*limit, *base);
+    }
+
+    let flexible: Vec<usize> = (0..tracks.len())
+        .filter(|i| tracks[*i].fraction().is_some())
+        .collect();
+
+    // Grow the tracks up to their limit
+    if available.is_finite() {
+        let mut free = available - gaps - base.iter().sum::<f32>();
+        while free > 0.001 {
+            let growing: Vec<usize> = (0..tracks.len())
+                .filter(|i| !flexible.contains(i) && base[*i] < limit[*i])
+                .collect();
+            if growing.is_empty() {
+                break;
+            }
+            let share = free / growing.len() as f32;
+            for i in growing {
+                let grow = f32::min(share, limit[i] - base[i]);
+                base[i] += grow;
+                free -= grow;
+            }
+        }
+    } else {
+        for i in (0..tracks.len()).filter(|i| !flexible.contains(i)) {
+            base[i] = limit[i];
+        }
+    }
+
+    if flexible.is_empty() {
+        // Stretch the auto tracks to fill the available space (css `justify-content: normal`)
+        let auto_tracks: Vec<usize> = (0..tracks.len())
+            .filter(|i| tracks[*i].max == GridTrackSize::Auto)
+            .collect();
+        let free = available - gaps - base.iter().sum::<f32>();
+        if available.is_finite() && free > 0.0 && !auto_tracks.is_empty() {
+            let extra = free / auto_tracks.len() as f32;
+            for i in auto_tracks {
+                base[i] += extra;
+            }
+        }
+    } else {
+        let fraction_size = fraction_size(tracks, &base, &flexible, available - gaps);
+        for i in flexible {
+            let fraction = tracks[i].fraction().unwrap_or(0.0);
+            base[i] = f32::max(base[i], fraction_size * fraction);
+        }
+    }
+
+    base.into_iter().map(round).collect()
+}
+
+/// Find the size of `1fr`. Tracks whose content is bigger than their share are treated as
+/// inflexible, until the remaining tracks fit.
+fn fraction_size(tracks: &[GridTrack], base: &[f32], flexible: &[usize], available: f32) -> f32 {
+    let fraction = |i: usize| tracks[i].fraction().unwrap_or(0.0);
+
+    if !available.is_finite() {
+        return flexible
+            .iter()
+            .map(|i| {
+                if fraction(*i) > 1.0 {
+                    base[*i] / fraction(*i)
+                } else {
+                    base[*i]
+                }
+            })
+            .fold(0.0, f32::max);
+    }
+
+    let mut inflexible: Vec<bool> = (0..tracks.len()).map(|i| !flexible.contains(&i)).collect();
+    loop {
+        let leftover = available
+            - (0..tracks.len())
+                .filter(|i| inflexible[*i])
+                .map(|i| base[i])
+                .sum::<f32>();
+        let fractions = flexible
+            .iter()
+            .filter(|i| !inflexible[**i])
+            .map(|i| fraction(*i))
+            .sum::<f32>();
+        let size = f32::max(leftover / f32::max(fractions, 1.0), 0.0);
+
+        let mut changed = false;
+        for i in flexible {
+            if !inflexible[*i] && size * fraction(*i) < base[*i] {
+                inflexible[*i] = true;
+                changed = true;
+            }
+        }
+        if !changed {
+            return size;
+        }
+    }
+}
+
+/// An instance of a grid container, used to add items to the container.
+pub struct GridInstance<'a> {
+    grid: &'a Grid,
+    ui: &'a mut Ui,
+    previous_items: &'a [GridItemState],
+    /// The cell of each item, from the previous pass
+    cells: Vec<Rect>,
+    state: GridState,
+    max_item_size: Vec2,
+}
+
+impl GridInstance<'_> {
+    /// Get the ui of the grid container (e.g. to read the style or access the context).
+    pub fn ui(&self) -> &Ui {
+        self.ui
+    }
+
+    /// Show a grid item. The outer [Ui] is stretched to fill the cell, while the inner [Ui]
+    /// will be positioned based on [`GridItem::align_self_content`].
+    /// Use the [`FlexContainerUi`] to show your content in the inner [Ui].
+    pub fn add_container<R>(
+        &mut self,
+        item: GridItem,
+        container_ui: impl FnOnce(&mut Ui, FlexContainerUi) -> FlexContainerResponse<R>,
+    ) -> InnerResponse<R> {
+        let index = self.state.items.len();
+        let previous = self
+            .previous_items
+            .get(index)
+            .zip(self.cells.get(index).copied());
+
+        let res = if let Some((item_state, cell)) = previous {
+            let frame_rect = cell;
+            let frame_without_margin = Rect {
+                min: frame_rect.min + item_state.margin.left_top(),
+                max: frame_rect.max - item_state.margin.right_bottom(),
+            };
+
+            let content_align = item
+                .align_content
+                .or(self.grid.align_items_content)
+                .unwrap_or(Align2::CENTER_CENTER);
+            let mut content_rect =
+                content_align.align_size_within_rect(item_state.inner_size, frame_without_margin);
+
+            // The content may grow (e.g. if the text gets longer), so it gets the full size
+            let max_content_size = self.max_item_size - item_state.margin.sum();
+            content_rect.set_width(max_content_size.x);
+            content_rect.set_height(max_content_size.y);
+
+            let mut child_ui = self.ui.new_child(
+                UiBuilder::new()
+                    .max_rect(frame_rect)
+                    .layout(Layout::left_to_right(Align::Min)),
+            );
+
+            let res = container_ui(
+                &mut child_ui,
+                FlexContainerUi {
+                    direction: 0,
+                    content_rect,
+                    frame_rect,
+                    margin: item_state.margin,
+                    max_item_size: max_content_size,
+                    remeasure_widget: item_state.remeasure_widget,
+                    last_inner_size: Some(item_state.inner_size),
+                },
+            );
+            let response = self.ui.allocate_rect(child_ui.min_rect(), Sense::hover());
+            (res, child_ui.min_rect(), response)
+        } else {
+            // New items are measured in an invisible ui
+            let rect = self.ui.available_rect_before_wrap();
+            let mut child_ui = self
+                .ui
+                .new_child(UiBuilder::new().max_rect(rect).invisible());
+
+            let res = container_ui(
+                &mut child_ui,
+                FlexContainerUi {
+                    direction: 0,
+                    content_rect: rect,
+                    frame_rect: rect,
+                    margin: Margin::ZERO,
+                    max_item_size: self.max_item_size,
+                    remeasure_widget: false,
+                    last_inner_size: None,
+                },
+            );
+            let response = child_ui.interact(child_ui.min_rect(), child_ui.id(), Sense::hover());
+            (res, child_ui.min_rect(), response)
+        };
+        let (res, outer_rect, response) = res;
+
+        // Like in flex, we assume the margin is symmetrical
+        let margin_top_left = res.container_min_rect.min - outer_rect.min;
+        let margin = Margin {
+            left: round(res.margin_top_left.x),
+            top: round(res.margin_top_left.y),
+            right: round(margin_top_left.x),
+            bottom: round(margin_top_left.y),
+        };
+
+        self.state.items.push(GridItemState {
+            config: item,
+            inner_size: round_vec2(res.child_rect.size()),
+            inner_min_size: round_vec2(Vec2::max(res.min_size, res.child_rect.size())),
+            margin,
+            remeasure_widget: res.remeasure_widget,
+        });
+
+        InnerResponse::new(res.inner, response)
+    }
+
+    /// Add a child ui to the grid container.
+    /// It will be positioned based on [`GridItem::align_self_content`].
+    pub fn add_ui<R>(
+        &mut self,
+        item: GridItem,
+        content: impl FnOnce(&mut Ui) -> R,
+    ) -> InnerResponse<R> {
+        self.add_container(item, |ui, container| container.content(ui, content))
+    }
+
+    /// Add a [`FlexWidget`] to the grid container.
+    /// [`FlexWidget`] is implemented for all default egui widgets.
+    pub fn add<W: FlexWidget>(&mut self, item: GridItem, widget: W) -> InnerResponse<W::Response> {
+        self.add_container(item, |ui, container| widget.flex_ui(ui, container))
+    }
+
+    /// Add a [`egui::Widget`] to the grid container.
+    /// See [`FlexInstance::add_widget`].
+    pub fn add_widget<W: Widget>(&mut self, item: GridItem, widget: W) -> InnerResponse<Response> {
+        self.add_container(item, |ui, container| container.content_widget(ui, widget))
+    }
+
+    /// Add some content with a frame. The frame will be stretched to fill the cell.
+    /// The content will be positioned based on [`GridItem::align_self_content`].
+    pub fn add_ui_frame<R>(
+        &mut self,
+        item: GridItem,
+        frame: Frame,
+        content: impl FnOnce(&mut Ui) -> R,
+    ) -> InnerResponse<R> {
+        self.add_container(item, |ui, container| {
+            frame.show(ui, |ui| container.content(ui, content)).inner
+        })
+    }
+
+    /// Add a flex container to a cell of the grid.
+    #[track_caller]
+    pub fn add_flex<R>(
+        &mut self,
+        item: GridItem,
+        flex: Flex,
+        content: impl FnOnce(&mut FlexInstance) -> R,
+    ) -> InnerResponse<R> {
+        self.add_container(item, |ui, container| {
+            container.content_flex(ui, flex, content)
+        })
+    }
+
+    /// Add a flex container with a frame to a cell of the grid.
+    #[track_caller]
+    pub fn add_flex_frame<R>(
+        &mut self,
+        item: GridItem,
+        flex: Flex,
+        frame: Frame,
+        content: impl FnOnce(&mut FlexInstance) -> R,
+    ) -> InnerResponse<R> {
+        self.add_container(item, |ui, container| {
+            frame
+                .show(ui, |ui| container.content_flex(ui, flex, content))
+                .inner
+        })
+    }
+}
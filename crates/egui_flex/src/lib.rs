@@ -2,14 +2,32 @@
 #![forbid(unsafe_code)]
 #![warn(missing_docs)]
 
+#[cfg(feature = "animate")]
+mod animation;
+mod breakpoint;
+mod debug;
 mod flex_widget;
-
-pub use crate::flex_widget::FlexWidget;
-
+mod grid;
+mod sizing;
+mod stats;
+
+pub use crate::breakpoint::FlexBreakpoint;
+pub use crate::debug::FlexDebug;
+#[cfg(feature = "egui_extras")]
+pub use crate::flex_widget::FlexTable;
+pub use crate::flex_widget::{FlexFrameWidget, FlexWidget};
+pub use crate::grid::{grid_item, Grid, GridInstance, GridItem, GridTrack, GridTrackSize};
+pub use crate::stats::FlexStats;
+
+#[cfg(feature = "animate")]
+use crate::animation::FlexAnimation;
 use egui::{
-    Align, Align2, Direction, Frame, Id, InnerResponse, Layout, Margin, Pos2, Rect, Response,
-    Sense, Ui, UiBuilder, Vec2, Widget,
+    Align, Align2, Direction, Frame, Id, InnerResponse, Layout, Margin, Pos2, Rangef, Rect,
+    Response, ScrollArea, Sense, Shape, Ui, UiBuilder, Vec2, Widget,
 };
+#[cfg(feature = "animate")]
+use egui_animation::{animate_position, easing};
+use std::hash::Hash;
 use std::mem;
 
 /// The direction in which the flex container should lay out its children.
@@ -19,6 +37,38 @@ pub enum FlexDirection {
     #[default]
     Horizontal,
     Vertical,
+    /// Like [`FlexDirection::Horizontal`], but the items are laid out from right to left
+    /// (css `row-reverse`).
+    HorizontalReverse,
+    /// Like [`FlexDirection::Vertical`], but the items are laid out from bottom to top
+    /// (css `column-reverse`).
+    VerticalReverse,
+}
+
+impl FlexDirection {
+    fn is_horizontal(self) -> bool {
+        matches!(
+            self,
+            FlexDirection::Horizontal | FlexDirection::HorizontalReverse
+        )
+    }
+
+    fn is_reverse(self) -> bool {
+        matches!(
+            self,
+            FlexDirection::HorizontalReverse | FlexDirection::VerticalReverse
+        )
+    }
+
+    /// The layout the items are added with
+    fn layout(self) -> Layout {
+        match self {
+            FlexDirection::Horizontal => Layout::left_to_right(Align::Min),
+            FlexDirection::Vertical => Layout::top_down(Align::Min),
+            FlexDirection::HorizontalReverse => Layout::right_to_left(Align::Min),
+            FlexDirection::VerticalReverse => Layout::bottom_up(Align::Min),
+        }
+    }
 }
 
 /// How to justify the content (alignment in the main axis).
@@ -43,11 +93,29 @@ pub enum FlexAlign {
     Center,
     #[default]
     Stretch,
+    /// Align the baseline of the first row of text of the items (only in horizontal flex
+    /// containers, in vertical ones this is the same as [`FlexAlign::Start`]).
+    ///
+    /// The baseline is taken from the first text the item paints, or can be set with
+    /// [`FlexContainerResponse::with_baseline`]. Items without text are aligned by their bottom edge.
+    Baseline,
+}
+
+/// The edge of the viewport a [`FlexItem::sticky`] item is pinned to, in the main direction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlexEdge {
+    /// The top edge in vertical and the left edge in horizontal containers (e.g. for headers).
+    Start,
+    /// The bottom edge in vertical and the right edge in horizontal containers (e.g. for footers).
+    End,
 }
 
 /// How to align the content in the cross axis across the whole container.
 ///
-/// NOTE: Currently only [`FlexAlignContent::Normal`] and [`FlexAlignContent::Stretch`] are implemented.
+/// This only has an effect if the content wraps into multiple rows (or if it's
+/// [`FlexAlignContent::Stretch`]). [`FlexAlignContent::Normal`] packs the rows at the start,
+/// without taking up the available cross size, the other aligns distribute the available
+/// cross size of the [`Ui`].
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 #[allow(missing_docs)]
 pub enum FlexAlignContent {
@@ -71,6 +139,14 @@ pub struct Flex {
     gap: Option<Vec2>,
     default_item: FlexItem,
     wrap: bool,
+    wrap_reverse: bool,
+    max_sizing_passes: usize,
+    #[cfg(feature = "animate")]
+    animate: bool,
+    #[cfg(feature = "animate")]
+    animation_time: f32,
+    scroll: bool,
+    debug: bool,
 }
 
 impl Default for Flex {
@@ -83,6 +159,14 @@ impl Default for Flex {
             gap: None,
             default_item: FlexItem::default(),
             wrap: true,
+            wrap_reverse: false,
+            max_sizing_passes: 3,
+            #[cfg(feature = "animate")]
+            animate: false,
+            #[cfg(feature = "animate")]
+            animation_time: 0.25,
+            scroll: false,
+            debug: false,
         }
     }
 }
@@ -91,9 +175,16 @@ impl Default for Flex {
 #[derive(Debug, Clone, Copy, Default, PartialEq)]
 pub struct FlexItem {
     grow: Option<f32>,
+    shrink: Option<f32>,
     basis: Option<f32>,
+    min_size: Option<f32>,
+    max_size: Option<f32>,
     align_self: Option<FlexAlign>,
     align_content: Option<Align2>,
+    order: Option<i32>,
+    visible: Option<bool>,
+    id: Option<Id>,
+    sticky: Option<FlexEdge>,
 }
 
 /// Create a new flex item. Shorthand for [`FlexItem::default`].
@@ -115,6 +206,17 @@ impl FlexItem {
         self
     }
 
+    /// How much should this item shrink compared to the other items, if the items don't fit
+    /// in the row (like css `flex-shrink`, items with a larger basis shrink more).
+    ///
+    /// By default items don't shrink.
+    /// Items can shrink down to [`Self::min_size`] (or 0, if it's not set), so make sure
+    /// the content can handle getting smaller than its intrinsic size (e.g. a [`egui::TextEdit`]).
+    pub fn shrink(mut self, shrink: f32) -> Self {
+        self.shrink = Some(shrink);
+        self
+    }
+
     /// Set the default size of the item, before it grows.
     /// If this is not set, the items "intrinsic size" will be used.
     pub fn basis(mut self, basis: f32) -> Self {
@@ -122,6 +224,21 @@ impl FlexItem {
         self
     }
 
+    /// The minimum size of the item in the main direction (without the frame's margin).
+    /// The item won't shrink below this size and will grow to it if its basis is smaller.
+    pub fn min_size(mut self, min_size: f32) -> Self {
+        self.min_size = Some(min_size);
+        self
+    }
+
+    /// The maximum size of the item in the main direction (without the frame's margin).
+    /// The item won't grow above this size, the remaining space is distributed to the
+    /// other growing items. If both are set, [`Self::min_size`] wins.
+    pub fn max_size(mut self, max_size: f32) -> Self {
+        self.max_size = Some(max_size);
+        self
+    }
+
     /// How do we align the item in the cross axis?
     ///
     /// Default is `stretch`.
@@ -137,6 +254,51 @@ impl FlexItem {
         self.align_content = Some(align_self_content);
         self
     }
+
+    /// Change the position of the item, without changing the order in the code (like css `order`).
+    /// Items are laid out by ascending order, items with the same order in the order they were added.
+    ///
+    /// Default is 0.
+    pub fn order(mut self, order: i32) -> Self {
+        self.order = Some(order);
+        self
+    }
+
+    /// Should the item be shown? Hidden items don't take up any space (like css `display: none`).
+    /// Their content is still added to an invisible [`Ui`], so its state is kept.
+    ///
+    /// Default is `true`.
+    pub fn visible(mut self, visible: bool) -> Self {
+        self.visible = Some(visible);
+        self
+    }
+
+    /// Identify the item with a stable id, so it keeps its animation state when items are
+    /// added or removed before it (see `Flex::animate`).
+    ///
+    /// By default items are identified by the order they are added in.
+    pub fn id(mut self, id: impl Hash) -> Self {
+        self.id = Some(Id::new(id));
+        self
+    }
+
+    /// Keep the item pinned to an edge of the viewport while the rest of the row scrolls
+    /// (like css `position: sticky`), e.g. for headers and footers of a scrolling list.
+    ///
+    /// The item stays in the row, so it only sticks while the row is visible. Sticky items are
+    /// pushed out by the next sticky item at the same edge. The other items of the row are
+    /// clipped below pinned items, so give sticky items a background
+    /// (e.g. with [`FlexInstance::add_ui_frame`]).
+    ///
+    /// See [`Flex::scroll`] and [`FlexInstance::viewport`].
+    pub fn sticky(mut self, edge: FlexEdge) -> Self {
+        self.sticky = Some(edge);
+        self
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible.unwrap_or(true)
+    }
 }
 
 impl Flex {
@@ -155,6 +317,26 @@ impl Flex {
         Self::default().direction(FlexDirection::Vertical)
     }
 
+    /// Create a flex container based on the available width of the [`Ui`], e.g. to switch from a
+    /// vertical layout on phones to a horizontal one on desktops.
+    /// Use [`FlexBreakpoint::from_width`] for named breakpoints.
+    ///
+    /// ```
+    /// # use egui_flex::{Flex, FlexBreakpoint};
+    /// # egui::__run_test_ui(|ui| {
+    /// Flex::responsive(ui, |width| match FlexBreakpoint::from_width(width) {
+    ///     FlexBreakpoint::Compact => Flex::vertical(),
+    ///     _ => Flex::horizontal(),
+    /// })
+    /// .show(ui, |flex| {
+    ///     // ...
+    /// });
+    /// # });
+    /// ```
+    pub fn responsive(ui: &Ui, f: impl FnOnce(f32) -> Self) -> Self {
+        f(ui.available_width().ceil())
+    }
+
     /// Set the direction of the flex container.
     pub fn direction(mut self, direction: FlexDirection) -> Self {
         self.direction = direction;
@@ -191,6 +373,12 @@ impl Flex {
         self
     }
 
+    /// Set the default shrink factor for the items in the flex container.
+    pub fn shrink_items(mut self, shrink: f32) -> Self {
+        self.default_item.shrink = Some(shrink);
+        self
+    }
+
     /// Set the gap between the items in the flex container.
     ///
     /// Default is `item_spacing` of the [`Ui`].
@@ -206,127 +394,225 @@ impl Flex {
         self
     }
 
+    /// Wrap the content, with new rows being added before the previous ones
+    /// (above them in a horizontal flex, css `flex-wrap: wrap-reverse`).
+    /// This also swaps [`FlexAlign::Start`] and [`FlexAlign::End`] in the cross axis.
+    pub fn wrap_reverse(mut self, wrap_reverse: bool) -> Self {
+        self.wrap_reverse = wrap_reverse;
+        if wrap_reverse {
+            self.wrap = true;
+        }
+        self
+    }
+
     /// Customize the id of the flex container to prevent conflicts with other flex containers.
     pub fn id_salt(mut self, id_salt: impl Into<Id>) -> Self {
         self.id_salt = Some(id_salt.into());
         self
     }
 
+    /// Animate the items when the layout changes: they move to their new position, new items
+    /// fade in and removed items fade out. Use [`FlexItem::id`] to identify items that can be
+    /// added or removed in the middle of the list.
+    ///
+    /// To fade out removed items, the shapes each item painted are copied in every frame.
+    /// Items with more than 32 shapes aren't copied, they disappear without fading out.
+    ///
+    /// Default is `false`.
+    #[cfg(feature = "animate")]
+    pub fn animate(mut self, animate: bool) -> Self {
+        self.animate = animate;
+        self
+    }
+
+    /// Show the container in a [`egui::ScrollArea`] that scrolls in the main direction.
+    /// The items don't wrap and keep their min size, so they overflow the viewport and can be
+    /// scrolled. Use [`FlexItem::sticky`] to pin headers or footers and
+    /// [`FlexInstance::add_virtual`] to skip items outside of the viewport.
+    ///
+    /// This only applies to [`Self::show`] and [`Self::show_measured`], nested containers can
+    /// be wrapped in a [`egui::ScrollArea`] instead.
+    ///
+    /// Default is `false`.
+    pub fn scroll(mut self, scroll: bool) -> Self {
+        self.scroll = scroll;
+        self
+    }
+
+    /// Show a debug overlay with the rects of the rows and items, the margins, the free space
+    /// and how much each item grew or shrunk. Hover an item to see its computed sizes.
+    /// See [`FlexDebug::set_enabled`] to enable it for all containers.
+    ///
+    /// Default is `false`.
+    pub fn debug(mut self, debug: bool) -> Self {
+        self.debug = debug;
+        self
+    }
+
+    /// How long the animations of [`Self::animate`] take, in seconds.
+    ///
+    /// Default is 0.25.
+    #[cfg(feature = "animate")]
+    pub fn animation_time(mut self, animation_time: f32) -> Self {
+        self.animation_time = animation_time;
+        self
+    }
+
+    /// The maximum number of sizing passes [`Self::show_measured`] runs to measure the items.
+    /// If the sizes still change after that, the frame is discarded, like with [`Self::show`].
+    ///
+    /// Default is 3.
+    pub fn max_sizing_passes(mut self, max_sizing_passes: usize) -> Self {
+        self.max_sizing_passes = max_sizing_passes;
+        self
+    }
+
     #[track_caller]
     fn show_inside<R>(
         self,
         ui: &mut Ui,
         target_size: Option<Vec2>,
         max_item_size: Option<Vec2>,
-        f: impl FnOnce(&mut FlexInstance) -> R,
+        sizing_passes: usize,
+        mut f: impl FnMut(&mut FlexInstance) -> R,
     ) -> (Vec2, InnerResponse<R>) {
         let id = if let Some(id_salt) = self.id_salt {
             ui.id().with(id_salt)
         } else {
             ui.auto_id_with("flex")
         };
-        let previous_state: FlexState = ui
-            .ctx()
-            .memory(|mem| mem.data.get_temp(id).clone().unwrap_or_default());
 
-        let layout = match self.direction {
-            FlexDirection::Horizontal => Layout::left_to_right(Align::Min),
-            FlexDirection::Vertical => Layout::top_down(Align::Min),
-        };
+        let builder = UiBuilder::new()
+            .layout(self.direction.layout())
+            .max_rect(round_rect(ui.available_rect_before_wrap()));
 
-        let mut state_changed = false;
-
-        let r = ui.scope_builder(
-            UiBuilder::new()
-                .layout(layout)
-                .max_rect(round_rect(ui.available_rect_before_wrap())),
-            |ui| {
-                let gap = self.gap.unwrap_or(ui.spacing_mut().item_spacing);
-                let original_item_spacing = mem::replace(&mut ui.spacing_mut().item_spacing, gap);
-
-                // We ceil in order to prevent rounding errors to wrap the layout unexpectedly
-                let available_size = target_size.unwrap_or(ui.available_size()).ceil();
-                let direction = usize::from(!ui.layout().main_dir().is_horizontal());
-                let cross_direction = 1 - direction;
-
-                let rows = self.layout_rows(
-                    &previous_state,
-                    available_size,
-                    gap,
-                    direction,
-                    ui.min_rect().min,
-                );
+        let r = sizing::show_measured(
+            ui,
+            id,
+            builder,
+            sizing_passes,
+            "Flex item added / removed / size changed",
+            |ui, previous_state: &FlexState| {
+                let (min_size, inner, state) =
+                    self.layout_items(ui, id, previous_state, target_size, max_item_size, &mut f);
+                ((min_size, inner), state)
+            },
+        );
+        let (min_size, inner) = r.inner;
+        (min_size, InnerResponse::new(inner, r.response))
+    }
 
-                let max_item_size = round_vec2(max_item_size.unwrap_or(available_size));
+    /// Add the items to the ui, based on the sizes from the previous pass.
+    /// Returns the min size of the content and the state with the new sizes.
+    fn layout_items<R>(
+        &self,
+        ui: &mut Ui,
+        id: Id,
+        previous_state: &FlexState,
+        target_size: Option<Vec2>,
+        max_item_size: Option<Vec2>,
+        f: &mut impl FnMut(&mut FlexInstance) -> R,
+    ) -> (Vec2, R, FlexState) {
+        let gap = self.gap.unwrap_or(ui.spacing_mut().item_spacing);
+        let original_item_spacing = mem::replace(&mut ui.spacing_mut().item_spacing, gap);
+
+        // We ceil in order to prevent rounding errors to wrap the layout unexpectedly
+        let available_size = target_size.unwrap_or(ui.available_size()).ceil();
+        let direction = usize::from(!ui.layout().main_dir().is_horizontal());
+        let cross_direction = 1 - direction;
 
-                let mut instance = FlexInstance {
-                    current_row: 0,
-                    current_row_index: 0,
-                    flex: &self,
-                    state: FlexState {
-                        items: vec![],
-                        max_item_size,
-                    },
-                    direction,
-                    row_ui: FlexInstance::row_ui(ui, rows.first()),
-                    ui,
-                    rows,
-                    max_item_size,
-                    last_max_item_size: previous_state.max_item_size,
-                    item_spacing: original_item_spacing,
-                };
+        let mut rows = self.layout_rows(
+            previous_state,
+            available_size,
+            gap,
+            direction,
+            ui.max_rect().min,
+        );
+        for row in &mut rows {
+            row.stick_to_viewport(direction, self.direction.is_reverse(), ui.clip_rect());
+        }
 
-                let r = f(&mut instance);
-
-                let mut min_size =
-                    instance
-                        .state
-                        .items
-                        .iter()
-                        .fold(Vec2::ZERO, |mut current, item| {
-                            current[direction] += item.min_size_with_margin()[direction];
-                            current[cross_direction] = f32::max(
-                                current[cross_direction],
-                                item.min_size_with_margin()[cross_direction],
-                            );
-                            current
-                        });
-                min_size[direction] += gap[direction] * (instance.state.items.len() as f32 - 1.0);
-
-                // TODO: We should be able to calculate the min_size by looking at the rows at the
-                // max item size, but form some reason this doesn't work correctly
-                // This would fix wrapping in nested flexes
-                // let min_size = min_size_rows.iter().fold(Vec2::ZERO, |mut current, row| {
-                //     current[direction] = f32::max(current[direction], row.total_size);
-                //     current[cross_direction] += row.cross_size;
-                //     current
-                // });
-
-                if previous_state != instance.state {
-                    state_changed = true;
-                }
+        let max_item_size = round_vec2(max_item_size.unwrap_or(available_size));
 
-                instance.ui.ctx().memory_mut(|mem| {
-                    mem.data.insert_temp(id, instance.state);
-                });
+        // The position of each item (row and index in the row), in the order they were added
+        let mut slots = vec![None; previous_state.items.len()];
+        for (row_index, row) in rows.iter().enumerate() {
+            for (index_in_row, index) in row.item_indices.iter().enumerate() {
+                slots[*index] = Some((row_index, index_in_row));
+            }
+        }
 
-                instance.rows.iter().for_each(|row| {
-                    if let Some(final_rect) = row.final_rect {
-                        instance.ui.allocate_rect(final_rect, Sense::hover());
-                    }
-                });
-                (min_size, r)
+        // Sizing passes and invisible uis don't paint anything, so there is nothing to animate
+        #[cfg(feature = "animate")]
+        let animation = (self.animate && !ui.is_sizing_pass() && ui.is_visible())
+            .then(|| FlexAnimation::load(ui, id));
+
+        let mut instance = FlexInstance {
+            id,
+            #[cfg(feature = "animate")]
+            animation,
+            flex: self,
+            state: FlexState {
+                items: vec![],
+                max_item_size,
             },
-        );
+            direction,
+            row_uis: rows
+                .iter()
+                .map(|row| FlexInstance::row_ui(ui, row))
+                .collect(),
+            slots,
+            ui,
+            rows,
+            available_size,
+            max_item_size,
+            last_max_item_size: previous_state.max_item_size,
+            item_spacing: original_item_spacing,
+            frame_rects: Vec::new(),
+        };
 
-        // We move this down here because `#[track_caller]` doesn't work with closures
-        if state_changed {
-            ui.ctx()
-                .request_discard("Flex item added / removed / size changed");
-            ui.ctx().request_repaint();
+        let r = f(&mut instance);
+
+        let visible_items = instance
+            .state
+            .items
+            .iter()
+            .filter(|item| item.config.is_visible());
+        let mut min_size = visible_items.clone().fold(Vec2::ZERO, |mut current, item| {
+            current[direction] += item.min_size_with_margin()[direction];
+            current[cross_direction] = f32::max(
+                current[cross_direction],
+                item.min_size_with_margin()[cross_direction],
+            );
+            current
+        });
+        min_size[direction] += gap[direction] * (visible_items.count() as f32 - 1.0);
+
+        // TODO: We should be able to calculate the min_size by looking at the rows at the
+        // max item size, but form some reason this doesn't work correctly
+        // This would fix wrapping in nested flexes
+        // let min_size = min_size_rows.iter().fold(Vec2::ZERO, |mut current, row| {
+        //     current[direction] = f32::max(current[direction], row.total_size);
+        //     current[cross_direction] += row.cross_size;
+        //     current
+        // });
+
+        instance.rows.iter().for_each(|row| {
+            if let Some(final_rect) = row.final_rect {
+                instance.ui.allocate_rect(final_rect, Sense::hover());
+            }
+        });
+        if (self.debug || FlexDebug::is_enabled(instance.ui.ctx()))
+            && !instance.ui.is_sizing_pass()
+            && instance.ui.is_visible()
+        {
+            FlexDebug::paint(&instance);
         }
-
-        (r.inner.0, InnerResponse::new(r.inner.1, r.response))
+        #[cfg(feature = "animate")]
+        if let Some(animation) = instance.animation.take() {
+            animation.finish(instance.ui, id, self.animation_time);
+        }
+        (min_size, r, instance.state)
     }
 
     fn layout_rows(
@@ -342,15 +628,17 @@ impl Flex {
         let available_length = available_size[direction];
         let gap_direction = gap[direction];
 
+        // Hidden items are skipped, the others are sorted by their order (the sort is stable)
+        let mut indices: Vec<usize> = (0..state.items.len())
+            .filter(|i| state.items[*i].config.is_visible())
+            .collect();
+        indices.sort_by_key(|i| state.items[*i].config.order.unwrap_or(0));
+
         let mut rows = vec![];
         let mut current_row = RowData::default();
-        for item in &state.items {
-            let item_length = item
-                .config
-                .basis
-                .map_or(item.min_size_with_margin()[direction], |basis| {
-                    basis + item.margin.sum()[direction]
-                });
+        for index in indices {
+            let item = &state.items[index];
+            let item_length = item.hypothetical_size(direction) + item.margin.sum()[direction];
 
             if item_length + gap_direction + current_row.total_size > available_length
                 && !current_row.items.is_empty()
@@ -363,8 +651,8 @@ impl Flex {
             if !current_row.items.is_empty() {
                 current_row.total_size += gap_direction;
             }
-            current_row.total_grow += item.config.grow.unwrap_or(0.0);
             current_row.items.push(item.clone());
+            current_row.item_indices.push(index);
             if item.min_size_with_margin()[cross_direction] > current_row.cross_size {
                 current_row.cross_size = item.min_size_with_margin()[cross_direction];
             }
@@ -374,26 +662,66 @@ impl Flex {
             rows.push(current_row);
         }
 
+        // Baseline aligned items need enough space above and below the row's baseline
+        if self.direction.is_horizontal() {
+            for row in &mut rows {
+                let (above, below) = row
+                    .items
+                    .iter()
+                    .filter(|item| item.config.align_self == Some(FlexAlign::Baseline))
+                    .fold((0.0, 0.0), |(above, below), item| {
+                        let baseline = item.baseline_or_bottom();
+                        (
+                            f32::max(above, baseline),
+                            f32::max(below, item.min_size_with_margin().y - baseline),
+                        )
+                    });
+                row.baseline = above;
+                row.cross_size = f32::max(row.cross_size, above + below);
+            }
+        }
+
         let available_cross_size = available_size[cross_direction];
-        let total_row_cross_size = rows.iter().map(|row| row.cross_size).sum::<f32>();
-        let extra_cross_space_per_row = match self.align_content {
-            #[allow(clippy::match_same_arms)]
-            FlexAlignContent::Normal => 0.0,
-            FlexAlignContent::Stretch => {
-                let extra_cross_space = f32::max(
-                    available_cross_size
-                        - total_row_cross_size
-                        - (rows.len().max(1) - 1) as f32 * gap[cross_direction],
-                    0.0,
-                );
+        let total_row_cross_size = rows.iter().map(|row| row.cross_size).sum::<f32>()
+            + (rows.len().max(1) - 1) as f32 * gap[cross_direction];
+        let free_cross_space = available_cross_size - total_row_cross_size;
+        // We don't move the rows out of the container if there is not enough space
+        let free_cross_space = if free_cross_space.is_finite() {
+            f32::max(free_cross_space, 0.0)
+        } else {
+            0.0
+        };
+        let row_count = rows.len() as f32;
 
-                extra_cross_space / rows.len() as f32
+        // The space before the first row, the extra space between the rows and the extra
+        // cross size of each row
+        let (cross_offset, extra_gap, extra_cross_space_per_row) = match self.align_content {
+            FlexAlignContent::SpaceBetween if rows.len() > 1 => {
+                (0.0, free_cross_space / (row_count - 1.0), 0.0)
+            }
+            // Space between with a single row behaves like start
+            FlexAlignContent::Normal | FlexAlignContent::Start | FlexAlignContent::SpaceBetween => {
+                (0.0, 0.0, 0.0)
             }
-            // TODO: Implement the other aligns
-            _ => 0.0,
+            FlexAlignContent::End => (free_cross_space, 0.0, 0.0),
+            FlexAlignContent::Center => (free_cross_space / 2.0, 0.0, 0.0),
+            FlexAlignContent::Stretch => (0.0, 0.0, free_cross_space / row_count),
+            FlexAlignContent::SpaceAround => (
+                free_cross_space / row_count / 2.0,
+                free_cross_space / row_count,
+                0.0,
+            ),
+        };
+
+        // With wrap reverse, the rows are mirrored within the container's cross size
+        let container_cross_size = if self.align_content == FlexAlignContent::Normal {
+            total_row_cross_size
+        } else {
+            total_row_cross_size + free_cross_space
         };
 
         let mut row_position = min_position;
+        row_position[cross_direction] += cross_offset;
 
         for row in &mut rows {
             let mut row_size = Vec2::ZERO;
@@ -403,12 +731,22 @@ impl Flex {
             // row_size[cross_direction] =
             //     f32::min(row_size[cross_direction], available_size[cross_direction]);
 
+            let mut row_min = row_position;
+            if self.wrap_reverse {
+                row_min[cross_direction] = min_position[cross_direction] + container_cross_size
+                    - (row_position[cross_direction] - min_position[cross_direction])
+                    - row_size[cross_direction];
+            }
+
             row.cross_size_with_extra_space = row_size[cross_direction];
-            row.rect = Some(Rect::from_min_size(row_position, row_size));
+            row.rect = Some(Rect::from_min_size(row_min, row_size));
 
-            row_position[cross_direction] += row_size[cross_direction] + gap[cross_direction];
+            row_position[cross_direction] +=
+                row_size[cross_direction] + gap[cross_direction] + extra_gap;
 
-            row.extra_space = available_length - row.total_size;
+            row.item_sizes =
+                resolve_item_sizes(&row.items, available_length, gap_direction, direction);
+            row.item_offsets = item_offsets(&row.items, &row.item_sizes, gap_direction, direction);
         }
         rows
     }
@@ -419,20 +757,180 @@ impl Flex {
     /// since it limits the `max_rect` to some small value. Use `Ui::horizontal_top` instead.
     #[track_caller]
     pub fn show<R>(self, ui: &mut Ui, f: impl FnOnce(&mut FlexInstance) -> R) -> InnerResponse<R> {
-        self.show_inside(ui, None, None, f).1
+        let mut f = Some(f);
+        self.show_scrolled(ui, 0, |instance| {
+            f.take().expect("show only runs a single pass")(instance)
+        })
+    }
+
+    /// Like [`Self::show`], but the items are measured in invisible sizing passes before they
+    /// are shown, instead of discarding the frame when the size of an item changed.
+    /// The sizing passes are repeated until the sizes don't change anymore
+    /// (up to [`Self::max_sizing_passes`]), so the layout is correct in the same frame and
+    /// dynamic content doesn't cause extra passes and jitter.
+    ///
+    /// # `f` runs more than once per frame
+    ///
+    /// `f` is called once per sizing pass and once more for the visible pass, so up to
+    /// [`Self::max_sizing_passes`] + 1 times per frame, and the result of the visible pass is
+    /// returned. It must add the same items in every pass and must not have side effects of its
+    /// own (like pushing to a list or counting). Widgets are disabled in the sizing passes
+    /// (check [`Ui::is_sizing_pass`] via [`FlexInstance::ui`]), so reacting to their responses
+    /// is fine: they only report clicks and edits in the visible pass. This also means that
+    /// a size change caused by an edit (like typing into a [`egui::TextEdit`]) is only measured
+    /// after the visible pass and discards the frame, like [`Self::show`] would.
+    ///
+    /// ```
+    /// # use egui_flex::{item, Flex};
+    /// # egui::__run_test_ui(|ui| {
+    /// let mut name = String::from("World");
+    /// let mut greetings = Vec::new();
+    /// Flex::horizontal().show_measured(ui, |flex| {
+    ///     // The label is measured with the current name in every pass
+    ///     flex.add(item().grow(1.0), egui::TextEdit::singleline(&mut name));
+    ///     flex.add(item(), egui::Label::new(format!("Hello {name}!")));
+    ///
+    ///     // Only in the visible pass, since the button is disabled in the sizing passes.
+    ///     // Don't push unconditionally here, it would push once per pass.
+    ///     if flex.add(item(), egui::Button::new("Greet")).inner.clicked() {
+    ///         greetings.push(name.clone());
+    ///     }
+    /// });
+    /// # });
+    /// ```
+    #[track_caller]
+    pub fn show_measured<R>(
+        self,
+        ui: &mut Ui,
+        f: impl FnMut(&mut FlexInstance) -> R,
+    ) -> InnerResponse<R> {
+        let sizing_passes = self.max_sizing_passes;
+        self.show_scrolled(ui, sizing_passes, f)
+    }
+
+    /// Show the container, in a [`ScrollArea`] if [`Self::scroll`] is enabled.
+    #[track_caller]
+    fn show_scrolled<R>(
+        self,
+        ui: &mut Ui,
+        sizing_passes: usize,
+        f: impl FnMut(&mut FlexInstance) -> R,
+    ) -> InnerResponse<R> {
+        if !self.scroll {
+            return self.show_inside(ui, None, None, sizing_passes, f).1;
+        }
+
+        let horizontal = self.direction.is_horizontal();
+        let mut scroll_area = ScrollArea::new([horizontal, !horizontal]);
+        if let Some(id_salt) = self.id_salt {
+            scroll_area = scroll_area.id_salt(id_salt);
+        }
+        // The items overflow the viewport instead of wrapping
+        let flex = Self {
+            wrap: false,
+            wrap_reverse: false,
+            ..self
+        };
+        scroll_area
+            .show(ui, |ui| {
+                flex.show_inside(ui, None, None, sizing_passes, f).1
+            })
+            .inner
     }
 }
 
 #[derive(Debug, Clone, Default)]
 struct RowData {
     items: Vec<ItemState>,
+    /// The index of each item in the order they were added
+    item_indices: Vec<usize>,
     total_size: f32,
-    total_grow: f32,
-    extra_space: f32,
+    /// The final size of each item in the main direction, without margin
+    item_sizes: Vec<f32>,
+    /// The distance of each item from the start of the row in the main direction
+    item_offsets: Vec<f32>,
     cross_size: f32,
     cross_size_with_extra_space: f32,
+    /// The offset of the baseline of [`FlexAlign::Baseline`] items from the top of the row
+    baseline: f32,
     rect: Option<Rect>,
     final_rect: Option<Rect>,
+    /// How far each item is moved in the main direction to stay within the viewport,
+    /// only non-zero for [`FlexItem::sticky`] items
+    sticky_offsets: Vec<f32>,
+    /// The range in the main direction that isn't covered by pinned sticky items,
+    /// the other items are clipped to it
+    unpinned_range: Option<Rangef>,
+}
+
+impl RowData {
+    /// The rect of the item at its position in the row, before moving sticky items
+    fn item_rect(&self, index: usize, direction: usize, reverse: bool) -> Rect {
+        let row_rect = self.rect.unwrap_or(Rect::NOTHING);
+        let length = self.item_sizes[index] + self.items[index].margin.sum()[direction];
+        let mut rect = row_rect;
+        if reverse {
+            rect.max[direction] -= self.item_offsets[index];
+            rect.min[direction] = rect.max[direction] - length;
+        } else {
+            rect.min[direction] += self.item_offsets[index];
+            rect.max[direction] = rect.min[direction] + length;
+        }
+        rect
+    }
+
+    /// Move the sticky items so they stay within the viewport, but not past the next sticky
+    /// item at the same edge or the end of the row.
+    fn stick_to_viewport(&mut self, direction: usize, reverse: bool, viewport: Rect) {
+        let ranges: Vec<Rangef> = (0..self.items.len())
+            .map(|index| range(self.item_rect(index, direction, reverse), direction))
+            .collect();
+        self.sticky_offsets = vec![0.0; ranges.len()];
+        let Some(content) = ranges
+            .iter()
+            .copied()
+            .reduce(|a, b| Rangef::new(f32::min(a.min, b.min), f32::max(a.max, b.max)))
+        else {
+            return;
+        };
+        let sticky = |edge: FlexEdge| {
+            let mut indices: Vec<usize> = (0..ranges.len())
+                .filter(|index| self.items[*index].config.sticky == Some(edge))
+                .collect();
+            indices.sort_by(|a, b| ranges[*a].min.total_cmp(&ranges[*b].min));
+            indices
+        };
+        let viewport = range(viewport, direction);
+        let mut unpinned = viewport;
+
+        let start_items = sticky(FlexEdge::Start);
+        for (i, index) in start_items.iter().enumerate() {
+            let limit = start_items
+                .get(i + 1)
+                .map_or(content.max, |next| ranges[*next].min);
+            let range = ranges[*index];
+            let offset = f32::max(f32::min(viewport.min - range.min, limit - range.max), 0.0);
+            if offset > 0.0 {
+                unpinned.min = f32::max(unpinned.min, range.max + offset);
+            }
+            self.sticky_offsets[*index] = offset;
+        }
+
+        let end_items = sticky(FlexEdge::End);
+        for (i, index) in end_items.iter().enumerate().rev() {
+            let limit = i
+                .checked_sub(1)
+                .map_or(content.min, |previous| ranges[end_items[previous]].max);
+            let range = ranges[*index];
+            let offset = f32::min(f32::max(viewport.max - range.max, limit - range.min), 0.0);
+            if offset < 0.0 {
+                unpinned.max = f32::min(unpinned.max, range.min + offset);
+            }
+            self.sticky_offsets[*index] = offset;
+        }
+
+        self.unpinned_range = (unpinned != viewport).then_some(unpinned);
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -443,12 +941,153 @@ struct ItemState {
     inner_min_size: Vec2,
     margin: Margin,
     remeasure_widget: bool,
+    /// The offset of the item's baseline from its top (including the margin)
+    baseline: Option<f32>,
 }
 
 impl ItemState {
     fn min_size_with_margin(&self) -> Vec2 {
         self.inner_min_size + self.margin.sum()
     }
+
+    /// The baseline, or the bottom edge if the item has no text (like css)
+    fn baseline_or_bottom(&self) -> f32 {
+        self.baseline.unwrap_or(self.min_size_with_margin().y)
+    }
+
+    /// The size in the main direction before growing or shrinking, without margin
+    fn base_size(&self, direction: usize) -> f32 {
+        self.config.basis.unwrap_or(self.inner_min_size[direction])
+    }
+
+    /// The base size, limited by the min and max size
+    fn hypothetical_size(&self, direction: usize) -> f32 {
+        self.config.clamp_size(self.base_size(direction))
+    }
+}
+
+impl FlexItem {
+    fn clamp_size(&self, size: f32) -> f32 {
+        let size = self.max_size.map_or(size, |max| f32::min(size, max));
+        self.min_size
+            .map_or(size, |min| f32::max(size, min))
+            .max(0.0)
+    }
+}
+
+/// Grow or shrink the items of a row to fill `available_length`, respecting their min and max size.
+/// This follows the css flexbox algorithm to [resolve flexible lengths](https://www.w3.org/TR/css-flexbox-1/#resolve-flexible-lengths):
+/// items that hit their min or max size are frozen and the remaining space is distributed
+/// between the other items, until no item violates its constraints.
+///
+/// Returns the size of each item in the main direction, without margin.
+fn resolve_item_sizes(
+    items: &[ItemState],
+    available_length: f32,
+    gap: f32,
+    direction: usize,
+) -> Vec<f32> {
+    let outer_length = items
+        .iter()
+        .map(|item| item.margin.sum()[direction])
+        .sum::<f32>()
+        + gap * items.len().saturating_sub(1) as f32;
+    let available_length = available_length - outer_length;
+
+    let base_sizes: Vec<f32> = items.iter().map(|item| item.base_size(direction)).collect();
+    let mut sizes: Vec<f32> = items
+        .iter()
+        .map(|item| item.hypothetical_size(direction))
+        .collect();
+
+    // In an unbounded container (e.g. a horizontal scroll area) there is no space to grow into
+    if !available_length.is_finite() {
+        return sizes;
+    }
+
+    let growing = sizes.iter().sum::<f32>() < available_length;
+    let flex_factor = |item: &ItemState| {
+        if growing {
+            item.config.grow
+        } else {
+            item.config.shrink
+        }
+        .unwrap_or(0.0)
+    };
+
+    // Items that can't flex keep their hypothetical size
+    let mut frozen: Vec<bool> = items
+        .iter()
+        .enumerate()
+        .map(|(i, item)| {
+            flex_factor(item) <= 0.0
+                || (growing && base_sizes[i] > sizes[i])
+                || (!growing && base_sizes[i] < sizes[i])
+        })
+        .collect();
+
+    while frozen.contains(&false) {
+        let free_space = available_length
+            - (0..items.len())
+                .map(|i| if frozen[i] { sizes[i] } else { base_sizes[i] })
+                .sum::<f32>();
+
+        // Items shrink relative to their base size, so small items don't disappear first
+        let weights: Vec<(usize, f32)> = (0..items.len())
+            .filter(|i| !frozen[*i])
+            .map(|i| {
+                let factor = flex_factor(&items[i]);
+                (
+                    i,
+                    if growing {
+                        factor
+                    } else {
+                        factor * base_sizes[i]
+                    },
+                )
+            })
+            .collect();
+        let total_weight = weights.iter().map(|(_, weight)| weight).sum::<f32>();
+
+        let mut targets = Vec::with_capacity(weights.len());
+        let mut total_violation = 0.0;
+        for (i, weight) in weights {
+            let target = if total_weight > 0.0 {
+                base_sizes[i] + free_space * weight / total_weight
+            } else {
+                base_sizes[i]
+            };
+            sizes[i] = items[i].config.clamp_size(target);
+            total_violation += sizes[i] - target;
+            targets.push((i, target));
+        }
+
+        for (i, target) in targets {
+            frozen[i] = if total_violation > 0.0 {
+                sizes[i] > target
+            } else if total_violation < 0.0 {
+                sizes[i] < target
+            } else {
+                true
+            };
+        }
+    }
+
+    sizes
+}
+
+/// The distance of each item from the start of the row in the main direction
+fn item_offsets(items: &[ItemState], sizes: &[f32], gap: f32, direction: usize) -> Vec<f32> {
+    let mut offset = 0.0;
+    items
+        .iter()
+        .zip(sizes)
+        .map(|(item, size)| {
+            let item_offset = offset;
+            offset += size + item.margin.sum()[direction] + gap;
+            item_offset
+        })
+        .collect()
 }
 
 #[derive(Debug, Clone, Default, PartialEq)]
@@ -459,25 +1098,29 @@ struct FlexState {
 
 /// An instance of a flex container, used to add items to the container.
 pub struct FlexInstance<'a> {
+    id: Id,
     flex: &'a Flex,
-    current_row: usize,
-    current_row_index: usize,
     state: FlexState,
     ui: &'a mut Ui,
     rows: Vec<RowData>,
+    /// The row and the index in the row of each item, `None` for new or hidden items
+    slots: Vec<Option<(usize, usize)>>,
     direction: usize,
-    row_ui: Ui,
+    row_uis: Vec<Ui>,
+    available_size: Vec2,
+    #[cfg(feature = "animate")]
+    animation: Option<FlexAnimation>,
     max_item_size: Vec2,
     last_max_item_size: Vec2,
     // Original item spacing to store when showing children
     item_spacing: Vec2,
+    /// The rect of each added item, `None` if it wasn't placed in a row
+    frame_rects: Vec<Option<Rect>>,
 }
 
 impl<'a> FlexInstance<'a> {
-    fn row_ui(parent: &mut Ui, row: Option<&RowData>) -> Ui {
-        let rect = row.map_or(parent.max_rect(), |row| row.rect.unwrap());
-
-        parent.new_child(UiBuilder::new().max_rect(rect))
+    fn row_ui(parent: &mut Ui, row: &RowData) -> Ui {
+        parent.new_child(UiBuilder::new().max_rect(row.rect.unwrap()))
     }
 
     /// Get the direction of the flex container.
@@ -485,14 +1128,14 @@ impl<'a> FlexInstance<'a> {
         self.flex.direction
     }
 
-    /// Is the flex container horizontal?
+    /// Is the flex container horizontal (including [`FlexDirection::HorizontalReverse`])?
     pub fn is_horizontal(&self) -> bool {
-        self.flex.direction == FlexDirection::Horizontal
+        self.flex.direction.is_horizontal()
     }
 
-    /// Is the flex container vertical?
+    /// Is the flex container vertical (including [`FlexDirection::VerticalReverse`])?
     pub fn is_vertical(&self) -> bool {
-        self.flex.direction == FlexDirection::Vertical
+        !self.flex.direction.is_horizontal()
     }
 
     /// Get the ui of the flex container (e.g. to read the style or access the context).
@@ -500,203 +1143,390 @@ impl<'a> FlexInstance<'a> {
         self.ui
     }
 
+    /// The size available to the items, the content wraps once it reaches this size.
+    pub fn available_size(&self) -> Vec2 {
+        self.available_size
+    }
+
+    /// The visible part of the flex container (its clip rect), e.g. the viewport of the
+    /// [`egui::ScrollArea`] it's shown in.
+    pub fn viewport(&self) -> Rect {
+        self.ui.clip_rect()
+    }
+
+    /// The [`FlexBreakpoint`] of the available width, e.g. to configure the items responsively.
+    pub fn breakpoint(&self) -> FlexBreakpoint {
+        FlexBreakpoint::from_width(self.available_size.x)
+    }
+
     /// Show a flex container. This is split in a outer and inner [Ui]. The outer [Ui] will
     /// grow according to the flex layout, while the inner [Ui] will be centered / positioned
     /// based on the [FlexItem::align_self_content].
     /// Use the [FlexContainerUi] to show your content in the inner [Ui].
-    #[allow(clippy::too_many_lines)] // TODO: Refactor this to be more readable
     pub fn add_container<R>(
         &mut self,
         item: FlexItem,
         container_ui: impl FnOnce(&mut Ui, FlexContainerUi) -> FlexContainerResponse<R>,
     ) -> InnerResponse<R> {
+        self.add_item(item, false, container_ui)
+            .expect("items are only skipped if virtualized")
+    }
+
+    /// Add an item, if `virtualize` is true it's skipped if it's outside of the viewport.
+    #[allow(clippy::too_many_lines)] // TODO: Refactor this to be more readable
+    fn add_item<R>(
+        &mut self,
+        item: FlexItem,
+        virtualize: bool,
+        container_ui: impl FnOnce(&mut Ui, FlexContainerUi) -> FlexContainerResponse<R>,
+    ) -> Option<InnerResponse<R>> {
         let item = FlexItem {
             grow: item.grow.or(self.flex.default_item.grow),
+            shrink: item.shrink.or(self.flex.default_item.shrink),
             basis: item.basis.or(self.flex.default_item.basis),
+            min_size: item.min_size.or(self.flex.default_item.min_size),
+            max_size: item.max_size.or(self.flex.default_item.max_size),
             align_self: item.align_self.or(self.flex.default_item.align_self),
             align_content: item.align_content.or(self.flex.default_item.align_content),
+            order: item.order.or(self.flex.default_item.order),
+            visible: item.visible.or(self.flex.default_item.visible),
+            id: item.id,
+            sticky: item.sticky.or(self.flex.default_item.sticky),
         };
+        let key = item.id.map_or_else(
+            || self.id.with(self.state.items.len()),
+            |id| self.id.with(id),
+        );
 
-        let row = self.rows.get_mut(self.current_row);
-
-        let res = self.row_ui.scope(|ui| {
-            let res = if let Some(row) = row {
-                let row_item_count = row.items.len();
-                // TODO: Handle when this is not set (Why doesn't this fail?)
-                let item_state = row.items.get_mut(self.current_row_index).unwrap();
-
-                let extra_length = if item_state.config.grow.unwrap_or(0.0) > 0.0
-                    && row.total_grow > 0.0
-                {
-                    f32::max(
-                        row.extra_space * item_state.config.grow.unwrap_or(0.0) / row.total_grow,
-                        0.0,
-                    )
-                } else {
-                    0.0
-                };
+        let align = item.align_self.unwrap_or_default();
+
+        // With wrap reverse the cross start is at the end
+        let (cross_start, cross_end) = if self.flex.wrap_reverse {
+            (Align::Max, Align::Min)
+        } else {
+            (Align::Min, Align::Max)
+        };
+        let cross_align = match align {
+            FlexAlign::Start => cross_start,
+            FlexAlign::End => cross_end,
+            FlexAlign::Center => Align::Center,
+            FlexAlign::Stretch | FlexAlign::Baseline => Align::Min,
+        };
 
-                let parent_min_rect = ui.min_rect();
+        // New and hidden items don't have a slot, they are shown in an invisible ui to measure them
+        let slot = if item.is_visible() {
+            self.slots.get(self.state.items.len()).copied().flatten()
+        } else {
+            None
+        };
 
-                let mut total_size = item_state.min_size_with_margin();
-                if let Some(basis) = item.basis {
-                    total_size[self.direction] = basis + item_state.margin.sum()[self.direction];
+        // Virtualized items outside of the viewport keep their size from the last pass
+        if let (true, Some((row_index, index_in_row))) = (virtualize, slot) {
+            let row = &self.rows[row_index];
+            let rect = row.item_rect(
+                index_in_row,
+                self.direction,
+                self.flex.direction.is_reverse(),
+            );
+            let mut sticky_offset = Vec2::ZERO;
+            sticky_offset[self.direction] = row.sticky_offsets[index_in_row];
+            if !self
+                .ui
+                .clip_rect()
+                .intersects(rect.translate(sticky_offset))
+            {
+                self.state.items.push(ItemState {
+                    id: key,
+                    config: item,
+                    ..row.items[index_in_row].clone()
+                });
+                let row_ui = &mut self.row_uis[row_index];
+                row_ui.expand_to_include_rect(rect);
+                self.rows[row_index].final_rect = Some(row_ui.min_rect());
+                #[cfg(feature = "animate")]
+                if let Some(animation) = &mut self.animation {
+                    animation.keep(key);
                 }
-                total_size[self.direction] += extra_length;
+                self.frame_rects.push(None);
+                return None;
+            }
+        }
+        let mut unplaced_ui;
+        let (parent_ui, row, index_in_row) = if let Some((row_index, index_in_row)) = slot {
+            (
+                &mut self.row_uis[row_index],
+                Some(&mut self.rows[row_index]),
+                index_in_row,
+            )
+        } else {
+            #[cfg(feature = "animate")]
+            if let (Some(animation), true) = (&mut self.animation, item.is_visible()) {
+                animation.measure();
+            }
+            unplaced_ui = self
+                .ui
+                .new_child(UiBuilder::new().max_rect(self.ui.available_rect_before_wrap()));
+            (&mut unplaced_ui, None, 0)
+        };
+
+        // The items are placed at their offset in the row, so they can be shown in any order
+        let mut scope_rect = parent_ui.max_rect();
+        if let Some(row) = &row {
+            let offset = row.item_offsets[index_in_row];
+            // Items that overflow the row (e.g. if it doesn't wrap) still need their full size
+            let length =
+                row.item_sizes[index_in_row] + row.items[index_in_row].margin.sum()[self.direction];
+            if self.flex.direction.is_reverse() {
+                scope_rect.max[self.direction] -= offset;
+                scope_rect.min[self.direction] = f32::min(
+                    scope_rect.min[self.direction],
+                    scope_rect.max[self.direction] - length,
+                );
+            } else {
+                scope_rect.min[self.direction] += offset;
+                scope_rect.max[self.direction] = f32::max(
+                    scope_rect.max[self.direction],
+                    scope_rect.min[self.direction] + length,
+                );
+            }
+        }
+
+        // Baseline aligned items are moved down until their baseline matches the row's baseline
+        let align_baseline = align == FlexAlign::Baseline && self.flex.direction.is_horizontal();
+        if align_baseline {
+            if let Some(row) = &row {
+                scope_rect.min.y += row.baseline - row.items[index_in_row].baseline_or_bottom();
+            }
+        }
+
+        // The scope's cross align makes sure the response only covers the item's rect
+        let scope_layout = parent_ui.layout().with_cross_align(cross_align);
+        let res = parent_ui.scope_builder(
+            UiBuilder::new().layout(scope_layout).max_rect(scope_rect),
+            |ui| {
+                let res = if let Some(row) = row {
+                    let row_item_count = row.items.len();
+                    let item_state = &row.items[index_in_row];
 
-                let available_size = ui.available_rect_before_wrap().size();
+                    let item_size = row.item_sizes[index_in_row];
+                    // If the item was shrunk, the content has to shrink as well
+                    let shrunk = item_size < item_state.inner_size[self.direction];
 
-                // If everything is wrapped we will limit the items size to the containers available
-                // size to prevent it from growing out of the container
-                if row_item_count == 1 {
+                    let mut total_size = item_state.min_size_with_margin();
                     total_size[self.direction] =
-                        f32::min(total_size[self.direction], available_size[self.direction]);
-                }
+                        item_size + item_state.margin.sum()[self.direction];
+
+                    let available_size = ui.available_rect_before_wrap().size();
 
-                let align = item.align_self.unwrap_or_default();
+                    // If everything is wrapped we will limit the items size to the containers available
+                    // size to prevent it from growing out of the container
+                    if row_item_count == 1 {
+                        total_size[self.direction] =
+                            f32::min(total_size[self.direction], available_size[self.direction]);
+                    }
 
-                let frame_align = match align {
-                    FlexAlign::Start => Some(Align::Min),
-                    FlexAlign::End => Some(Align::Max),
-                    FlexAlign::Center => Some(Align::Center),
-                    FlexAlign::Stretch => {
+                    if align == FlexAlign::Stretch {
                         total_size[1 - self.direction] = row.cross_size_with_extra_space;
-                        None
                     }
-                };
 
-                let frame_rect = match frame_align {
-                    None => Rect::from_min_size(parent_min_rect.min, total_size),
-                    Some(align) => {
-                        let mut align2 = Align2::LEFT_TOP;
-                        align2[1 - self.direction] = align;
-                        align2.align_size_within_rect(total_size, ui.max_rect())
+                    // In reversed layouts the items are added at the end of the row
+                    let mut frame_align = Align2::LEFT_TOP;
+                    if self.flex.direction.is_reverse() {
+                        frame_align[self.direction] = Align::Max;
                     }
-                };
+                    frame_align[1 - self.direction] = cross_align;
+                    let mut frame_rect =
+                        frame_align.align_size_within_rect(total_size, ui.max_rect());
+
+                    // The item is shown at its animated or sticky position, but the space is
+                    // allocated at its final position in the row
+                    let mut display_offset = Vec2::ZERO;
+                    #[cfg(feature = "animate")]
+                    if self.animation.is_some() {
+                        let position = animate_position(
+                            ui,
+                            key.with("flex_position"),
+                            frame_rect.min,
+                            self.flex.animation_time,
+                            easing::cubic_out,
+                            false,
+                        );
+                        display_offset = position - frame_rect.min;
+                    }
+                    display_offset[self.direction] += row.sticky_offsets[index_in_row];
+                    frame_rect = frame_rect.translate(display_offset);
 
-                let mut inner_size = item_state.inner_size;
-                if let Some(basis) = item.basis {
-                    inner_size[self.direction] = basis + extra_length;
-                }
-                inner_size[self.direction] = f32::min(
-                    inner_size[self.direction],
-                    available_size[self.direction] - item_state.margin.sum()[self.direction],
-                );
+                    let mut inner_size = item_state.inner_size;
+                    if item.basis.is_some() || shrunk {
+                        inner_size[self.direction] = item_size;
+                    }
+                    inner_size[self.direction] = f32::min(
+                        inner_size[self.direction],
+                        available_size[self.direction] - item_state.margin.sum()[self.direction],
+                    );
 
-                let content_align = item.align_content.unwrap_or(Align2::CENTER_CENTER);
+                    let content_align = item.align_content.unwrap_or(Align2::CENTER_CENTER);
+
+                    let frame_without_margin = Rect {
+                        min: frame_rect.min + item_state.margin.left_top(),
+                        max: frame_rect.max - item_state.margin.right_bottom(),
+                    };
+
+                    let mut content_rect =
+                        content_align.align_size_within_rect(inner_size, frame_without_margin);
+
+                    let max_content_size = self.max_item_size - item_state.margin.sum();
+                    // Because we want to allow the content to grow (e.g. in case the text gets longer),
+                    // we set the content_rect's size to match the flex ui's available size.
+                    content_rect.set_width(max_content_size.x);
+                    content_rect.set_height(max_content_size.y);
+                    // We only want to limit the content size in the main dir
+                    // TODO: Should there be an option to also limit it in the cross dir?
+                    content_rect.max[1 - self.direction] = self.max_item_size[1 - self.direction];
+                    // frame_rect.set_width(self.ui.available_width());
+                    // frame_rect.set_height(self.ui.available_height());
+
+                    if item.basis.is_some() || shrunk {
+                        let mut size = content_rect.size();
+                        size[self.direction] = item_size;
+                        content_rect = Rect::from_min_size(
+                            content_rect.min,
+                            size.min(self.ui.available_size() - item_state.margin.sum()),
+                        );
+                    }
 
-                let frame_without_margin = Rect {
-                    min: frame_rect.min + item_state.margin.left_top(),
-                    max: frame_rect.max - item_state.margin.right_bottom(),
-                };
+                    // The content of the items isn't reversed
+                    let item_layout = if self.flex.direction.is_horizontal() {
+                        FlexDirection::Horizontal
+                    } else {
+                        FlexDirection::Vertical
+                    }
+                    .layout();
+                    let mut child_ui =
+                        ui.new_child(UiBuilder::new().max_rect(frame_rect).layout(item_layout));
+                    child_ui.spacing_mut().item_spacing = self.item_spacing;
+                    // Items scrolling below pinned sticky items are hidden (and can't be clicked)
+                    if let (Some(unpinned), None) = (row.unpinned_range, item.sticky) {
+                        let mut clip_rect = child_ui.clip_rect();
+                        clip_rect.min[self.direction] =
+                            f32::max(clip_rect.min[self.direction], unpinned.min);
+                        clip_rect.max[self.direction] =
+                            f32::min(clip_rect.max[self.direction], unpinned.max);
+                        child_ui.set_clip_rect(clip_rect);
+                    }
+                    #[cfg(feature = "animate")]
+                    if let Some(animation) = &mut self.animation {
+                        child_ui.multiply_opacity(animation.appear(
+                            &child_ui,
+                            key,
+                            self.flex.animation_time,
+                        ));
+                    }
 
-                let mut content_rect =
-                    content_align.align_size_within_rect(inner_size, frame_without_margin);
-
-                let max_content_size = self.max_item_size - item_state.margin.sum();
-                // Because we want to allow the content to grow (e.g. in case the text gets longer),
-                // we set the content_rect's size to match the flex ui's available size.
-                content_rect.set_width(max_content_size.x);
-                content_rect.set_height(max_content_size.y);
-                // We only want to limit the content size in the main dir
-                // TODO: Should there be an option to also limit it in the cross dir?
-                content_rect.max[1 - self.direction] = self.max_item_size[1 - self.direction];
-                // frame_rect.set_width(self.ui.available_width());
-                // frame_rect.set_height(self.ui.available_height());
-
-                if let Some(basis) = item.basis {
-                    let mut size = content_rect.size();
-                    size[self.direction] = basis + extra_length;
-                    content_rect = Rect::from_min_size(
-                        content_rect.min,
-                        size.min(self.ui.available_size() - item_state.margin.sum()),
+                    // The shapes are needed to find the baseline and to fade out removed items
+                    #[cfg(feature = "animate")]
+                    let record_shapes = align_baseline || self.animation.is_some();
+                    #[cfg(not(feature = "animate"))]
+                    let record_shapes = align_baseline;
+                    let first_shape = record_shapes.then(|| next_shape_idx(&child_ui));
+
+                    let res = container_ui(
+                        &mut child_ui,
+                        FlexContainerUi {
+                            direction: self.direction,
+                            content_rect,
+                            frame_rect,
+                            margin: item_state.margin,
+                            max_item_size: max_content_size,
+                            // If the available space grows we want to remeasure the widget, in case
+                            // it's wrapped so it can un-wrap
+                            remeasure_widget: item_state.remeasure_widget
+                                || self.max_item_size[self.direction]
+                                    > self.last_max_item_size[self.direction],
+                            last_inner_size: Some(item_state.inner_size),
+                        },
+                    );
+                    ui.allocate_rect(
+                        child_ui.min_rect().translate(-display_offset),
+                        Sense::hover(),
                     );
-                }
 
-                let mut child_ui =
-                    ui.new_child(UiBuilder::new().max_rect(frame_rect).layout(*ui.layout()));
-                child_ui.spacing_mut().item_spacing = self.item_spacing;
-
-                let res = container_ui(
-                    &mut child_ui,
-                    FlexContainerUi {
-                        direction: self.direction,
-                        content_rect,
-                        frame_rect,
-                        margin: item_state.margin,
-                        max_item_size: max_content_size,
-                        // If the available space grows we want to remeasure the widget, in case
-                        // it's wrapped so it can un-wrap
-                        remeasure_widget: item_state.remeasure_widget
-                            || self.max_item_size[self.direction]
-                                > self.last_max_item_size[self.direction],
-                        last_inner_size: Some(item_state.inner_size),
-                    },
-                );
-                let (_, _r) = ui.allocate_space(child_ui.min_rect().size());
+                    #[cfg(feature = "animate")]
+                    if let (Some(animation), Some(first_shape)) = (&mut self.animation, first_shape)
+                    {
+                        animation.record(&child_ui, key, first_shape);
+                    }
 
-                (res, row.items.len(), child_ui.min_rect())
-            } else {
-                ui.set_invisible();
-
-                let rect = self.ui.available_rect_before_wrap();
-
-                let res = container_ui(
-                    ui,
-                    FlexContainerUi {
-                        direction: self.direction,
-                        content_rect: rect,
-                        frame_rect: rect,
-                        margin: Margin::ZERO,
-                        max_item_size: self.max_item_size,
-                        remeasure_widget: false,
-                        last_inner_size: None,
-                    },
-                );
+                    let baseline = res.baseline.or_else(|| {
+                        if child_ui.is_visible() {
+                            first_shape
+                                .and_then(|idx| first_text_baseline(&child_ui, idx))
+                                .map(|y| y - frame_rect.min.y)
+                        } else {
+                            // Nothing was painted (e.g. in a sizing pass), keep the last baseline
+                            item_state.baseline
+                        }
+                    });
+
+                    (res, child_ui.min_rect(), baseline, Some(frame_rect))
+                } else {
+                    ui.set_invisible();
+
+                    let rect = self.ui.available_rect_before_wrap();
+
+                    let res = container_ui(
+                        ui,
+                        FlexContainerUi {
+                            direction: self.direction,
+                            content_rect: rect,
+                            frame_rect: rect,
+                            margin: Margin::ZERO,
+                            max_item_size: self.max_item_size,
+                            remeasure_widget: false,
+                            last_inner_size: None,
+                        },
+                    );
 
-                (res, 0, self.ui.min_rect())
-            };
+                    let baseline = res.baseline;
+                    (res, self.ui.min_rect(), baseline, None)
+                };
 
-            let (res, row_len, outer_rect) = res;
-
-            // TODO: This calculates the top left margin, bottom right doesn't work as expected
-            // let margin_bottom_right = outer_rect.max - res.container_min_rect.max;
-            let margin_bottom_right = res.container_min_rect.min - outer_rect.min;
-            let margin = round_margin(Margin {
-                top: res.margin_top_left.y,
-                left: res.margin_top_left.x,
-                bottom: margin_bottom_right.y,
-                right: margin_bottom_right.x,
-            });
-
-            let item = ItemState {
-                margin,
-                inner_size: round_vec2(res.child_rect.size()),
-                id: ui.id(),
-                inner_min_size: round_vec2(Vec2::max(res.min_size, res.child_rect.size())),
-                config: item,
-                remeasure_widget: res.remeasure_widget,
-            };
+                let (res, outer_rect, baseline, frame_rect) = res;
 
-            (res.inner, item, row_len)
-        });
+                // TODO: This calculates the top left margin, bottom right doesn't work as expected
+                // let margin_bottom_right = outer_rect.max - res.container_min_rect.max;
+                let margin_bottom_right = res.container_min_rect.min - outer_rect.min;
+                let margin = round_margin(Margin {
+                    top: res.margin_top_left.y,
+                    left: res.margin_top_left.x,
+                    bottom: margin_bottom_right.y,
+                    right: margin_bottom_right.x,
+                });
+
+                let item = ItemState {
+                    margin,
+                    inner_size: round_vec2(res.child_rect.size()),
+                    id: key,
+                    inner_min_size: round_vec2(Vec2::max(res.min_size, res.child_rect.size())),
+                    config: item,
+                    remeasure_widget: res.remeasure_widget,
+                    baseline: baseline.map(round),
+                };
 
-        if let Some(row) = self.rows.get_mut(self.current_row) {
-            row.final_rect = Some(self.row_ui.min_rect());
+                (res.inner, item, frame_rect)
+            },
+        );
+
+        if let Some((row_index, _)) = slot {
+            self.rows[row_index].final_rect = Some(self.row_uis[row_index].min_rect());
         }
 
-        let (inner, item, row_len) = res.inner;
+        let (inner, item, frame_rect) = res.inner;
 
         self.state.items.push(item);
+        self.frame_rects.push(frame_rect);
 
-        self.current_row_index += 1;
-        if self.current_row_index >= row_len {
-            self.current_row += 1;
-            self.current_row_index = 0;
-            self.row_ui = FlexInstance::row_ui(self.ui, self.rows.get(self.current_row));
-        }
-
-        InnerResponse::new(inner, res.response)
+        Some(InnerResponse::new(inner, res.response))
     }
 
     /// Add a simple item to the flex container.
@@ -727,6 +1557,28 @@ impl<'a> FlexInstance<'a> {
         self.add_container(item, |ui, container| widget.flex_ui(ui, container))
     }
 
+    /// Like [`Self::add`], but the widget is skipped if the item is outside of the viewport
+    /// (e.g. when the flex container is in a [`egui::ScrollArea`] or uses [`Flex::scroll`]).
+    /// Skipped items keep the size they had when they were last shown, so this makes long
+    /// lists cheap. Returns `None` if the item was skipped.
+    pub fn add_virtual<W: FlexWidget>(
+        &mut self,
+        item: FlexItem,
+        widget: W,
+    ) -> Option<InnerResponse<W::Response>> {
+        self.add_item(item, true, |ui, container| widget.flex_ui(ui, container))
+    }
+
+    /// Like [`Self::add_ui`], but `content` isn't called if the item is outside of the viewport.
+    /// See [`Self::add_virtual`].
+    pub fn add_ui_virtual<R>(
+        &mut self,
+        item: FlexItem,
+        content: impl FnOnce(&mut Ui) -> R,
+    ) -> Option<InnerResponse<R>> {
+        self.add_item(item, true, |ui, container| container.content(ui, content))
+    }
+
     /// Add a [`egui::Widget`] to the flex container.
     /// The default egui widgets implement [`FlexWidget`] Aso you can just use [`Self::add`] instead.
     /// If the widget reports it's intrinsic size via the [`egui::Response`] it will be able to
@@ -791,7 +1643,7 @@ impl<'a> FlexInstance<'a> {
             .grow
             .or(self.flex.default_item.grow)
             .is_some_and(|g| g > 0.0)
-            && self.flex.direction != flex.direction
+            && self.flex.direction.is_horizontal() != flex.direction.is_horizontal()
         {
             flex.align_content = FlexAlignContent::Stretch;
         }
@@ -831,9 +1683,18 @@ pub struct FlexContainerResponse<T> {
     min_size: Vec2,
     container_min_rect: Rect,
     remeasure_widget: bool,
+    baseline: Option<f32>,
 }
 
 impl<T> FlexContainerResponse<T> {
+    /// Report the baseline of the first row of text for [`FlexAlign::Baseline`], as the offset
+    /// from the top of the [`Ui`] passed to [`FlexWidget::flex_ui`].
+    /// If this isn't set, the baseline of the first text painted by the widget is used.
+    pub fn with_baseline(mut self, baseline: f32) -> Self {
+        self.baseline = Some(baseline);
+        self
+    }
+
     /// Map the inner value of the response.
     pub fn map<U>(self, f: impl FnOnce(T) -> U) -> FlexContainerResponse<U> {
         FlexContainerResponse {
@@ -844,6 +1705,7 @@ impl<T> FlexContainerResponse<T> {
             min_size: self.min_size,
             container_min_rect: self.container_min_rect,
             remeasure_widget: self.remeasure_widget,
+            baseline: self.baseline,
         }
     }
 }
@@ -889,6 +1751,7 @@ impl FlexContainerUi {
             margin_top_left,
             container_min_rect,
             remeasure_widget: false,
+            baseline: None,
         }
     }
 
@@ -916,11 +1779,17 @@ impl FlexContainerUi {
         ui.set_width(ui.available_width());
         ui.set_height(ui.available_height());
 
+        let mut content = Some(content);
         let (min_size, res) = flex.show_inside(
             ui,
             Some(frame_rect.size() - margin.sum()),
             Some(max_item_size),
-            |instance| content(instance),
+            0,
+            |instance| {
+                content
+                    .take()
+                    .expect("nested flexes only run a single pass")(instance)
+            },
         );
 
         let container_min_rect = ui.min_rect();
@@ -933,6 +1802,7 @@ impl FlexContainerUi {
             margin_top_left,
             container_min_rect,
             remeasure_widget: false,
+            baseline: None,
         }
     }
 
@@ -950,7 +1820,9 @@ impl FlexContainerUi {
             .id_salt(id_salt)
             .layout(Layout::centered_and_justified(Direction::TopDown));
         if self.remeasure_widget {
-            ui.ctx().request_discard("Flex item remeasure");
+            if !ui.is_sizing_pass() {
+                FlexStats::request_discard(ui.ctx(), "Flex item remeasure");
+            }
             builder = builder.max_rect(self.content_rect);
         } else {
             ui.set_width(ui.available_width());
@@ -969,9 +1841,9 @@ impl FlexContainerUi {
             round(last_size[1 - self.direction]) != round(intrinsic_size[1 - self.direction])
         }) && !self.remeasure_widget;
 
-        if remeasure_widget {
+        if remeasure_widget && !ui.is_sizing_pass() {
             ui.ctx().request_repaint();
-            ui.ctx().request_discard("Triggering flex item remeasure");
+            FlexStats::request_discard(ui.ctx(), "Triggering flex item remeasure");
         }
 
         FlexContainerResponse {
@@ -982,11 +1854,49 @@ impl FlexContainerUi {
             margin_top_left,
             container_min_rect: ui.min_rect(),
             remeasure_widget,
+            baseline: None,
         }
     }
 }
 
+/// The index the next shape painted in the [`Ui`]'s layer will get
+fn next_shape_idx(ui: &Ui) -> usize {
+    ui.ctx().graphics(|graphics| {
+        graphics
+            .get(ui.layer_id())
+            .map_or(0, |list| list.next_idx().0)
+    })
+}
+
+/// The y position of the baseline of the first text painted in the [`Ui`]'s layer,
+/// starting at the shape `first_idx`
+fn first_text_baseline(ui: &Ui, first_idx: usize) -> Option<f32> {
+    ui.ctx().graphics(|graphics| {
+        graphics
+            .get(ui.layer_id())?
+            .all_entries()
+            .skip(first_idx)
+            .find_map(|clipped| {
+                if let Shape::Text(text) = &clipped.shape {
+                    let glyph = text.galley.rows.first()?.glyphs.first()?;
+                    Some(text.pos.y + glyph.pos.y)
+                } else {
+                    None
+                }
+            })
+    })
+}
+
 /// Round a float to 5 decimal places.
+/// The range of the rect in the direction (0 is x, 1 is y)
+fn range(rect: Rect, direction: usize) -> Rangef {
+    if direction == 0 {
+        rect.x_range()
+    } else {
+        rect.y_range()
+    }
+}
+
 fn round(i: f32) -> f32 {
     const PRECISION: f32 = 1e3;
     let i = (i * PRECISION).round() / PRECISION;
@@ -1021,3 +1931,90 @@ fn round_rect(rect: Rect) -> Rect {
         max: round_pos2(rect.max),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// An item with a content size of `size` in the main (x) direction
+    fn item_state(size: f32, config: FlexItem) -> ItemState {
+        ItemState {
+            id: Id::new(size.to_bits()),
+            config,
+            inner_size: Vec2::new(size, 10.0),
+            inner_min_size: Vec2::new(size, 10.0),
+            margin: Margin::ZERO,
+            remeasure_widget: false,
+            baseline: None,
+        }
+    }
+
+    fn resolve(items: &[ItemState], available_length: f32) -> Vec<f32> {
+        resolve_item_sizes(items, available_length, 0.0, 0)
+    }
+
+    #[test]
+    fn grow_distributes_free_space_by_factor() {
+        let items = [
+            item_state(10.0, item().grow(1.0)),
+            item_state(10.0, item().grow(3.0)),
+        ];
+        assert_eq!(resolve(&items, 100.0), vec![30.0, 70.0]);
+    }
+
+    #[test]
+    fn max_violation_freezes_item_and_redistributes() {
+        let items = [
+            item_state(10.0, item().grow(1.0).max_size(20.0)),
+            item_state(10.0, item().grow(1.0)),
+        ];
+        assert_eq!(resolve(&items, 100.0), vec![20.0, 80.0]);
+    }
+
+    #[test]
+    fn min_violation_freezes_item_and_redistributes() {
+        let items = [
+            item_state(50.0, item().shrink(1.0).min_size(45.0)),
+            item_state(50.0, item().shrink(1.0)),
+        ];
+        // Shrinking both by 20 would put the first item below its min size
+        assert_eq!(resolve(&items, 60.0), vec![45.0, 15.0]);
+    }
+
+    #[test]
+    fn zero_total_violation_freezes_all_items() {
+        // Both items violate their constraints by the same amount in opposite directions
+        let items = [
+            item_state(0.0, item().grow(1.0).max_size(30.0)),
+            item_state(0.0, item().grow(1.0).min_size(70.0)),
+        ];
+        assert_eq!(resolve(&items, 100.0), vec![30.0, 70.0]);
+    }
+
+    #[test]
+    fn shrink_is_weighted_by_base_size() {
+        let items = [
+            item_state(20.0, item().shrink(1.0)),
+            item_state(80.0, item().shrink(1.0)),
+        ];
+        // The items are 50 too large, the larger item shrinks 4 times as much
+        assert_eq!(resolve(&items, 50.0), vec![10.0, 40.0]);
+    }
+
+    #[test]
+    fn infinite_available_length_does_not_grow() {
+        let items = [
+            item_state(10.0, item().grow(1.0)),
+            item_state(20.0, item().grow(1.0).max_size(15.0)),
+        ];
+        assert_eq!(resolve(&items, f32::INFINITY), vec![10.0, 15.0]);
+    }
+
+    #[test]
+    fn margin_and_gap_are_subtracted() {
+        let mut first = item_state(10.0, item().grow(1.0));
+        first.margin = Margin::symmetric(5.0, 0.0);
+        let items = [first, item_state(10.0, item().grow(1.0))];
+        assert_eq!(resolve_item_sizes(&items, 100.0, 10.0, 0), vec![40.0, 40.0]);
+    }
+}
@@ -0,0 +1,68 @@
+use crate::FlexStats;
+use egui::{Id, InnerResponse, Ui, UiBuilder};
+
+/// Show the items of a container ([`crate::Flex`] or [`crate::Grid`]) and store their state,
+/// measuring them in up to `sizing_passes` invisible sizing passes first.
+///
+/// `layout` adds the items to the ui, based on the state of the previous pass, and returns the
+/// state measured in this pass. The sizing passes are repeated until the state converges. If the
+/// state still changed in the visible pass, the frame is discarded with `discard_reason`.
+#[track_caller]
+pub(crate) fn show_measured<S, T>(
+    ui: &mut Ui,
+    id: Id,
+    builder: UiBuilder,
+    sizing_passes: usize,
+    discard_reason: &'static str,
+    mut layout: impl FnMut(&mut Ui, &S) -> (T, S),
+) -> InnerResponse<T>
+where
+    S: Clone + Default + PartialEq + Send + Sync + 'static,
+{
+    let mut previous_state: S = ui
+        .ctx()
+        .memory(|mem| mem.data.get_temp(id).unwrap_or_default());
+
+    if sizing_passes > 0 {
+        // Sizing passes disable the widgets, which would make them lose focus
+        let focused = ui.memory(egui::Memory::focused);
+
+        // The sizing ui gets the same id as the real one, so stateful widgets are measured
+        // with their current state
+        for _ in 0..sizing_passes {
+            let mut sizing_ui = ui.new_child(builder.clone().sizing_pass().invisible());
+            let (_, state) = layout(&mut sizing_ui, &previous_state);
+            FlexStats::add_sizing_pass(ui.ctx());
+
+            let converged = state == previous_state;
+            previous_state = state;
+            if converged {
+                break;
+            }
+        }
+
+        if let Some(focused) = focused {
+            ui.memory_mut(|mem| {
+                if mem.focused().is_none() {
+                    mem.request_focus(focused);
+                }
+            });
+        }
+    }
+
+    let r = ui.scope_builder(builder, |ui| layout(ui, &previous_state));
+    let (inner, state) = r.inner;
+
+    let state_changed = previous_state != state;
+    ui.ctx().memory_mut(|mem| {
+        mem.data.insert_temp(id, state);
+    });
+
+    // In a sizing pass the items are measured, so there is no need to discard
+    if state_changed && !ui.is_sizing_pass() {
+        FlexStats::request_discard(ui.ctx(), discard_reason);
+        ui.ctx().request_repaint();
+    }
+
+    InnerResponse::new(inner, r.response)
+}
@@ -0,0 +1,43 @@
+use egui::{Context, Id};
+
+/// Counters of how often flex containers had to re-layout, accumulated since the start of the app.
+/// Use them to check if your layout converges, e.g. by showing them in a debug window.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FlexStats {
+    /// How often a frame was discarded ([`Context::request_discard`]) because an item
+    /// was added / removed or its size changed
+    pub discards: u64,
+    /// How many sizing passes were run to measure the items, see [`crate::Flex::show_measured`]
+    pub sizing_passes: u64,
+}
+
+impl FlexStats {
+    fn id() -> Id {
+        Id::new("egui_flex_stats")
+    }
+
+    /// Get the current stats
+    pub fn get(ctx: &Context) -> Self {
+        ctx.data(|data| data.get_temp(Self::id()).unwrap_or_default())
+    }
+
+    /// Reset the stats to zero
+    pub fn reset(ctx: &Context) {
+        ctx.data_mut(|data| data.remove::<Self>(Self::id()));
+    }
+
+    fn update(ctx: &Context, f: impl FnOnce(&mut Self)) {
+        ctx.data_mut(|data| f(data.get_temp_mut_or_default(Self::id())));
+    }
+
+    pub(crate) fn add_sizing_pass(ctx: &Context) {
+        Self::update(ctx, |stats| stats.sizing_passes += 1);
+    }
+
+    /// Discard the frame and count it
+    #[track_caller]
+    pub(crate) fn request_discard(ctx: &Context, reason: &'static str) {
+        Self::update(ctx, |stats| stats.discards += 1);
+        ctx.request_discard(reason);
+    }
+}
@@ -0,0 +1,149 @@
+//! Check that animated flex containers move, fade in and fade out their items over time.
+
+mod common;
+
+use common::{input, run_frame, GAP};
+use egui::{vec2, Color32, Context, RawInput, Rect, Shape};
+use egui_flex::{item, Flex};
+
+const RED: Color32 = Color32::RED;
+
+/// Show the items at the given time. Each item is a 50x20 red rect, identified by its name.
+/// Returns the painted rect of each item and the painted shapes.
+fn frame(ctx: &Context, animate: bool, time: f64, items: &[&str]) -> (Vec<Rect>, Vec<Shape>) {
+    frame_with_shapes(ctx, animate, time, items, 1)
+}
+
+/// Like [`frame`], but each item paints its rect `shapes` times
+fn frame_with_shapes(
+    ctx: &Context,
+    animate: bool,
+    time: f64,
+    items: &[&str],
+    shapes: usize,
+) -> (Vec<Rect>, Vec<Shape>) {
+    let input = RawInput {
+        time: Some(time),
+        ..input()
+    };
+    let mut rects = Vec::new();
+    let output = run_frame(ctx, input, |ui| {
+        rects = Flex::horizontal()
+            .animate(animate)
+            .gap(GAP)
+            .show(ui, |flex| {
+                items
+                    .iter()
+                    .map(|name| {
+                        flex.add_ui(item().id(name), |ui| {
+                            let (rect, _) =
+                                ui.allocate_exact_size(vec2(50.0, 20.0), egui::Sense::hover());
+                            for _ in 0..shapes {
+                                ui.painter().rect_filled(rect, 0.0, RED);
+                            }
+                            rect
+                        })
+                        .inner
+                    })
+                    .collect()
+            })
+            .inner;
+    });
+    let shapes = output.shapes.into_iter().map(|shape| shape.shape).collect();
+    (rects, shapes)
+}
+
+/// The alpha of the (premultiplied) red rects that were painted
+fn red_alphas(shapes: &[Shape]) -> Vec<u8> {
+    shapes
+        .iter()
+        .filter_map(|shape| match shape {
+            Shape::Rect(rect) if rect.fill.r() > 0 && rect.fill.g() == 0 && rect.fill.b() == 0 => {
+                Some(rect.fill.a())
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn items_move_to_their_new_position() {
+    for animate in [false, true] {
+        let ctx = Context::default();
+        for _ in 0..4 {
+            frame(&ctx, animate, 0.0, &["a", "b"]);
+        }
+        let (rects, _) = frame(&ctx, animate, 0.0, &["a", "b"]);
+        assert_eq!(rects[1].min.x, 60.0);
+
+        // Remove the first item, the second one moves to the start
+        frame(&ctx, animate, 1.0, &["b"]);
+        let (rects, _) = frame(&ctx, animate, 1.1, &["b"]);
+        if animate {
+            assert!(rects[0].min.x > 0.0 && rects[0].min.x < 60.0, "{rects:?}");
+        } else {
+            assert_eq!(rects[0].min.x, 0.0);
+        }
+
+        let (rects, _) = frame(&ctx, animate, 2.0, &["b"]);
+        assert_eq!(rects[0].min.x, 0.0);
+    }
+}
+
+#[test]
+fn removed_items_fade_out() {
+    let ctx = Context::default();
+    for _ in 0..4 {
+        frame(&ctx, true, 0.0, &["a", "b"]);
+    }
+    let (_, shapes) = frame(&ctx, true, 0.0, &["a", "b"]);
+    assert_eq!(red_alphas(&shapes), [255, 255]);
+
+    frame(&ctx, true, 1.0, &["b"]);
+    let (_, shapes) = frame(&ctx, true, 1.1, &["b"]);
+    let alphas = red_alphas(&shapes);
+    assert_eq!(alphas.len(), 2, "the removed item is still painted");
+    assert!(
+        alphas.iter().any(|alpha| *alpha > 0 && *alpha < 255),
+        "{alphas:?}"
+    );
+
+    let (_, shapes) = frame(&ctx, true, 2.0, &["b"]);
+    assert_eq!(red_alphas(&shapes), [255]);
+}
+
+#[test]
+fn new_items_fade_in() {
+    let ctx = Context::default();
+    for _ in 0..4 {
+        frame(&ctx, true, 0.0, &["a"]);
+    }
+    // Items shown in the first frame don't fade in
+    let (_, shapes) = frame(&ctx, true, 0.0, &["a"]);
+    assert_eq!(red_alphas(&shapes), [255]);
+
+    frame(&ctx, true, 1.0, &["a", "b"]);
+    let (_, shapes) = frame(&ctx, true, 1.1, &["a", "b"]);
+    let alphas = red_alphas(&shapes);
+    assert_eq!(alphas.len(), 2);
+    assert!(alphas.iter().any(|alpha| *alpha < 255), "{alphas:?}");
+
+    let (_, shapes) = frame(&ctx, true, 2.0, &["a", "b"]);
+    assert_eq!(red_alphas(&shapes), [255, 255]);
+}
+
+#[test]
+fn items_with_many_shapes_dont_fade_out() {
+    let ctx = Context::default();
+    for _ in 0..4 {
+        frame_with_shapes(&ctx, true, 0.0, &["a", "b"], 40);
+    }
+    let (_, shapes) = frame_with_shapes(&ctx, true, 0.0, &["a", "b"], 40);
+    // The items aren't recorded, but they don't fade in again either
+    assert_eq!(red_alphas(&shapes), [255; 80]);
+
+    // The removed item disappears right away
+    frame_with_shapes(&ctx, true, 1.0, &["b"], 40);
+    let (_, shapes) = frame_with_shapes(&ctx, true, 1.1, &["b"], 40);
+    assert_eq!(red_alphas(&shapes), [255; 40]);
+}
@@ -0,0 +1,57 @@
+//! The fixture shared by the integration tests: a headless [`Context`] showing a frameless
+//! [`CentralPanel`] on a 400x300 screen.
+
+// Every test binary only uses some of the helpers
+#![allow(dead_code)]
+
+use egui::{vec2, CentralPanel, Context, Frame, FullOutput, Pos2, RawInput, Rect, Ui, Vec2};
+
+pub const SCREEN: Vec2 = vec2(400.0, 300.0);
+pub const GAP: Vec2 = vec2(10.0, 10.0);
+
+/// The input of a frame on the test screen, without any events
+pub fn input() -> RawInput {
+    RawInput {
+        screen_rect: Some(Rect::from_min_size(Pos2::ZERO, SCREEN)),
+        ..RawInput::default()
+    }
+}
+
+/// Run a single frame with `input`, showing `add_contents` in a frameless [`CentralPanel`]
+pub fn run_frame(
+    ctx: &Context,
+    input: RawInput,
+    mut add_contents: impl FnMut(&mut Ui),
+) -> FullOutput {
+    ctx.run(input, |ctx| {
+        CentralPanel::default()
+            .frame(Frame::none())
+            .show(ctx, &mut add_contents);
+    })
+}
+
+/// Run a few frames in a new context, so the sizes measured in the previous frame are used.
+/// Returns the result of `add_contents` in the last frame.
+pub fn show<R>(mut add_contents: impl FnMut(&mut Ui) -> R) -> R {
+    let ctx = Context::default();
+    let mut result = None;
+    for _ in 0..4 {
+        run_frame(&ctx, input(), |ui| result = Some(add_contents(ui)));
+    }
+    result.expect("the contents were shown")
+}
+
+/// Compare the rects to the expected `[x, y, width, height]` of each item
+#[track_caller]
+pub fn assert_rects(actual: &[Rect], expected: &[[f32; 4]]) {
+    let actual: Vec<[f32; 4]> = actual
+        .iter()
+        .map(|rect| [rect.min.x, rect.min.y, rect.width(), rect.height()])
+        .collect();
+    let matches = actual.len() == expected.len()
+        && actual
+            .iter()
+            .zip(expected)
+            .all(|(a, e)| a.iter().zip(e).all(|(a, e)| (a - e).abs() < 0.5));
+    assert!(matches, "expected {expected:?}\n     got {actual:?}");
+}
@@ -0,0 +1,76 @@
+//! Check the debug overlay of flex containers.
+
+mod common;
+
+use common::{input, run_frame};
+use egui::epaint::ClippedShape;
+use egui::{Context, Event, Pos2, RawInput, Shape};
+use egui_flex::{item, Flex, FlexDebug};
+
+/// Show a horizontal flex with a growing item for a few frames, with the pointer at `pointer`
+fn show(ctx: &Context, debug: bool, pointer: Option<Pos2>) -> Vec<ClippedShape> {
+    let mut shapes = Vec::new();
+    for _ in 0..4 {
+        let input = RawInput {
+            events: pointer.map(Event::PointerMoved).into_iter().collect(),
+            ..input()
+        };
+        shapes = run_frame(ctx, input, |ui| {
+            Flex::horizontal().debug(debug).show(ui, |flex| {
+                flex.add(item().grow(1.0), egui::Button::new("Grow"));
+                flex.add(item(), egui::Button::new("Fixed"));
+            });
+        })
+        .shapes;
+    }
+    shapes
+}
+
+fn texts(shapes: &[ClippedShape]) -> Vec<String> {
+    shapes
+        .iter()
+        .filter_map(|shape| match &shape.shape {
+            Shape::Text(text) => Some(text.galley.text().to_owned()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn debug_paints_overlay() {
+    let plain = show(&Context::default(), false, None);
+    let debug = show(&Context::default(), true, None);
+    assert!(debug.len() > plain.len());
+    // The growing item shows how much it grew
+    assert!(
+        texts(&debug).iter().any(|text| text.starts_with('+')),
+        "{:?}",
+        texts(&debug)
+    );
+    assert!(!texts(&plain).iter().any(|text| text.starts_with('+')));
+}
+
+#[test]
+fn debug_can_be_enabled_globally() {
+    let ctx = Context::default();
+    let plain = show(&ctx, false, None);
+    FlexDebug::set_enabled(&ctx, true);
+    assert!(FlexDebug::is_enabled(&ctx));
+    let debug = show(&ctx, false, None);
+    assert!(debug.len() > plain.len());
+}
+
+#[test]
+fn debug_tooltip_on_hover() {
+    let ctx = Context::default();
+    let shapes = show(&ctx, true, Some(Pos2::new(5.0, 5.0)));
+    let hovered = texts(&shapes);
+    assert!(
+        hovered.iter().any(|text| text == "Flex item 0"),
+        "{hovered:?}"
+    );
+    assert!(hovered.iter().any(|text| text == "basis"), "{hovered:?}");
+
+    let shapes = show(&ctx, false, Some(Pos2::new(5.0, 5.0)));
+    assert!(!texts(&shapes).iter().any(|text| text == "basis"));
+}
@@ -0,0 +1,212 @@
+//! Grid layout snapshots: the rects computed by the grid are compared to the rects a browser
+//! computes for the same css grid layout.
+
+mod common;
+
+use common::{assert_rects, input, run_frame, GAP};
+use egui::{vec2, Context, Rect, Ui, Vec2};
+use egui_flex::{grid_item, Grid, GridItem, GridTrack, GridTrackSize};
+
+/// Show the grid in `ui`, with [`Grid::show_measured`] if `measured` is set
+fn show_grid(ui: &mut Ui, grid: &Grid, items: &[(GridItem, Vec2)], measured: bool) -> Vec<Rect> {
+    let add_items = |grid: &mut egui_flex::GridInstance| {
+        items
+            .iter()
+            .map(|(item, size)| {
+                grid.add_ui(item.clone(), |ui| {
+                    ui.allocate_exact_size(*size, egui::Sense::hover())
+                })
+                .response
+                .rect
+            })
+            .collect()
+    };
+    let grid = grid.clone().gap(GAP);
+    if measured {
+        grid.show_measured(ui, add_items).inner
+    } else {
+        grid.show(ui, add_items).inner
+    }
+}
+
+/// Show a grid with items of a fixed content size and return the rects of the items.
+/// A few frames are run, so the sizes measured in the previous frame are used.
+fn layout(grid: &Grid, items: &[(GridItem, Vec2)]) -> Vec<Rect> {
+    common::show(|ui| show_grid(ui, grid, items, false))
+}
+
+fn sized(width: f32, height: f32) -> (GridItem, Vec2) {
+    (grid_item(), vec2(width, height))
+}
+
+#[test]
+fn px_and_fr_columns() {
+    // 400 - 100 - 2 * 10 = 280 free space, split 1:2
+    let rects = layout(
+        &Grid::new().columns([GridTrack::px(100.0), GridTrack::fr(1.0), GridTrack::fr(2.0)]),
+        &[sized(50.0, 20.0), sized(50.0, 30.0), sized(50.0, 20.0)],
+    );
+    assert_rects(
+        &rects,
+        &[
+            [0.0, 0.0, 100.0, 30.0],
+            [110.0, 0.0, 93.33, 30.0],
+            [213.33, 0.0, 186.67, 30.0],
+        ],
+    );
+}
+
+#[test]
+fn fr_column_fits_content() {
+    // The first column doesn't fit in its share of 195, so the second column gets the rest
+    let rects = layout(
+        &Grid::new().columns([GridTrack::fr(1.0), GridTrack::fr(1.0)]),
+        &[sized(300.0, 20.0), sized(50.0, 20.0)],
+    );
+    assert_rects(&rects, &[[0.0, 0.0, 300.0, 20.0], [310.0, 0.0, 90.0, 20.0]]);
+}
+
+#[test]
+fn auto_columns_stretch() {
+    // 400 - 50 - 100 - 10 = 240 free space is added to both auto columns
+    let rects = layout(
+        &Grid::new().columns([GridTrack::auto(), GridTrack::auto()]),
+        &[sized(50.0, 20.0), sized(100.0, 20.0)],
+    );
+    assert_rects(
+        &rects,
+        &[[0.0, 0.0, 170.0, 20.0], [180.0, 0.0, 220.0, 20.0]],
+    );
+}
+
+#[test]
+fn minmax_columns() {
+    let rects = layout(
+        &Grid::new().columns([
+            GridTrack::minmax(GridTrackSize::Px(50.0), GridTrackSize::Px(80.0)),
+            GridTrack::fr(1.0),
+        ]),
+        &[sized(20.0, 20.0), sized(20.0, 20.0)],
+    );
+    assert_rects(&rects, &[[0.0, 0.0, 80.0, 20.0], [90.0, 0.0, 310.0, 20.0]]);
+}
+
+#[test]
+fn auto_placement_with_spans() {
+    // (400 - 20) / 3 = 126.67 per column. The second row is 30 high, the third row is empty,
+    // so the item spanning both is 30 + 10 high
+    let rects = layout(
+        &Grid::new().columns([GridTrack::fr(1.0); 3]),
+        &[
+            (grid_item().column_span(2), vec2(50.0, 20.0)),
+            sized(50.0, 20.0),
+            sized(50.0, 20.0),
+            (grid_item().row_span(2), vec2(50.0, 20.0)),
+            sized(50.0, 30.0),
+        ],
+    );
+    assert_rects(
+        &rects,
+        &[
+            [0.0, 0.0, 263.33, 20.0],
+            [273.33, 0.0, 126.67, 20.0],
+            [0.0, 30.0, 126.67, 30.0],
+            [136.67, 30.0, 126.67, 40.0],
+            [273.33, 30.0, 126.67, 30.0],
+        ],
+    );
+}
+
+#[test]
+fn explicit_placement() {
+    let rects = layout(
+        &Grid::new().columns([GridTrack::fr(1.0), GridTrack::fr(1.0)]),
+        &[
+            sized(50.0, 20.0),
+            (grid_item().row(0).column(0), vec2(50.0, 20.0)),
+            sized(50.0, 20.0),
+        ],
+    );
+    assert_rects(
+        &rects,
+        &[
+            [205.0, 0.0, 195.0, 20.0],
+            [0.0, 0.0, 195.0, 20.0],
+            [0.0, 30.0, 195.0, 20.0],
+        ],
+    );
+}
+
+#[test]
+fn spanning_item_grows_auto_rows() {
+    // The spanning item needs 70, the rows have 20 + 10 gap, so each row grows by 20
+    let rects = layout(
+        &Grid::new().columns([GridTrack::fr(1.0), GridTrack::fr(1.0)]),
+        &[
+            (grid_item().row_span(2), vec2(50.0, 70.0)),
+            sized(50.0, 20.0),
+            sized(50.0, 20.0),
+        ],
+    );
+    assert_rects(
+        &rects,
+        &[
+            [0.0, 0.0, 195.0, 70.0],
+            [205.0, 0.0, 195.0, 30.0],
+            [205.0, 40.0, 195.0, 30.0],
+        ],
+    );
+}
+
+#[test]
+fn areas() {
+    let rects = layout(
+        &Grid::new()
+            .columns([GridTrack::px(100.0), GridTrack::fr(1.0)])
+            .areas(["header header", "sidebar main"]),
+        &[
+            (grid_item().area("main"), vec2(50.0, 40.0)),
+            (grid_item().area("header"), vec2(50.0, 20.0)),
+            (grid_item().area("sidebar"), vec2(50.0, 20.0)),
+        ],
+    );
+    assert_rects(
+        &rects,
+        &[
+            [110.0, 30.0, 290.0, 40.0],
+            [0.0, 0.0, 400.0, 20.0],
+            [0.0, 30.0, 100.0, 40.0],
+        ],
+    );
+}
+
+#[test]
+fn fill_height() {
+    // 300 - 10 = 290, split 1:1
+    let rects = layout(
+        &Grid::new()
+            .rows([GridTrack::fr(1.0), GridTrack::fr(1.0)])
+            .fill_height(true),
+        &[sized(50.0, 20.0), sized(50.0, 20.0)],
+    );
+    assert_rects(
+        &rects,
+        &[[0.0, 0.0, 400.0, 145.0], [0.0, 155.0, 400.0, 145.0]],
+    );
+}
+
+#[test]
+fn measured_without_discard() {
+    let ctx = Context::default();
+    let grid = Grid::new().columns([GridTrack::fr(1.0), GridTrack::fr(1.0)]);
+    let items = [
+        (grid_item().column_span(2), vec2(50.0, 20.0)),
+        sized(50.0, 20.0),
+    ];
+    let mut rects = Vec::new();
+    run_frame(&ctx, input(), |ui| {
+        rects = show_grid(ui, &grid, &items, true);
+    });
+    assert_rects(&rects, &[[0.0, 0.0, 400.0, 20.0], [0.0, 30.0, 195.0, 20.0]]);
+    assert_eq!(egui_flex::FlexStats::get(&ctx).discards, 0);
+}
@@ -0,0 +1,584 @@
+//! Layout snapshots: the rects computed by egui_flex are compared to the rects a browser computes
+//! for the same css flexbox layout.
+
+mod common;
+
+use common::{assert_rects, input, run_frame, GAP};
+use egui::{vec2, Context, RawInput, Rect, Vec2};
+use egui_flex::{
+    item, Flex, FlexAlign, FlexAlignContent, FlexBreakpoint, FlexDirection, FlexInstance, FlexItem,
+    FlexStats,
+};
+
+/// Add items with a fixed content size, returning their rects
+fn add_sized(flex: &mut FlexInstance, items: &[(FlexItem, Vec2)]) -> Vec<Rect> {
+    items
+        .iter()
+        .map(|(item, size)| {
+            flex.add_ui(*item, |ui| {
+                ui.allocate_exact_size(*size, egui::Sense::hover())
+            })
+            .response
+            .rect
+        })
+        .collect()
+}
+
+/// Show a flex with items of a fixed content size and return the rects of the items.
+/// A few frames are run, so the sizes measured in the previous frame are used.
+fn layout(flex: &Flex, items: &[(FlexItem, Vec2)]) -> Vec<Rect> {
+    common::show(|ui| {
+        flex.clone()
+            .gap(GAP)
+            .show(ui, |flex| add_sized(flex, items))
+            .inner
+    })
+}
+
+fn sized(width: f32, height: f32) -> (FlexItem, Vec2) {
+    (item(), vec2(width, height))
+}
+
+#[test]
+fn row() {
+    let rects = layout(
+        &Flex::horizontal().align_items(FlexAlign::Start),
+        &[sized(50.0, 20.0), sized(80.0, 30.0), sized(40.0, 10.0)],
+    );
+    assert_rects(
+        &rects,
+        &[
+            [0.0, 0.0, 50.0, 20.0],
+            [60.0, 0.0, 80.0, 30.0],
+            [150.0, 0.0, 40.0, 10.0],
+        ],
+    );
+}
+
+#[test]
+fn row_align_items_end() {
+    let rects = layout(
+        &Flex::horizontal().align_items(FlexAlign::End),
+        &[sized(50.0, 20.0), sized(80.0, 30.0)],
+    );
+    assert_rects(&rects, &[[0.0, 10.0, 50.0, 20.0], [60.0, 0.0, 80.0, 30.0]]);
+}
+
+#[test]
+fn row_align_items_stretch() {
+    let rects = layout(&Flex::horizontal(), &[sized(50.0, 20.0), sized(80.0, 30.0)]);
+    assert_rects(&rects, &[[0.0, 0.0, 50.0, 30.0], [60.0, 0.0, 80.0, 30.0]]);
+}
+
+#[test]
+fn column() {
+    let rects = layout(
+        &Flex::vertical().align_items(FlexAlign::Start),
+        &[sized(50.0, 20.0), sized(80.0, 30.0)],
+    );
+    assert_rects(&rects, &[[0.0, 0.0, 50.0, 20.0], [0.0, 30.0, 80.0, 30.0]]);
+}
+
+#[test]
+fn grow() {
+    // 400 - 50 - 100 - 2 * 10 = 230 free space, split 1:2
+    let rects = layout(
+        &Flex::horizontal(),
+        &[
+            (item().grow(1.0), vec2(50.0, 20.0)),
+            (item().grow(2.0), vec2(100.0, 20.0)),
+            sized(0.0, 20.0),
+        ],
+    );
+    assert_rects(
+        &rects,
+        &[
+            [0.0, 0.0, 126.67, 20.0],
+            [136.67, 0.0, 253.33, 20.0],
+            [400.0, 0.0, 0.0, 20.0],
+        ],
+    );
+}
+
+#[test]
+fn grow_max_size() {
+    // The first item is frozen at its max size, the rest goes to the second item
+    let rects = layout(
+        &Flex::horizontal(),
+        &[
+            (item().grow(1.0).basis(0.0).max_size(50.0), vec2(0.0, 20.0)),
+            (item().grow(1.0).basis(0.0), vec2(0.0, 20.0)),
+        ],
+    );
+    assert_rects(&rects, &[[0.0, 0.0, 50.0, 20.0], [60.0, 0.0, 340.0, 20.0]]);
+}
+
+#[test]
+fn shrink_min_size() {
+    // 200 + 100 + 100 + 20 = 420, 20 too much. Shrinking is weighted by the basis, so the first
+    // item would shrink to 186.67 and the second to 93.33, below its min size of 95.
+    let rects = layout(
+        &Flex::horizontal().wrap(false),
+        &[
+            (item().basis(200.0).shrink(1.0), vec2(0.0, 20.0)),
+            (
+                item().basis(100.0).shrink(1.0).min_size(95.0),
+                vec2(0.0, 20.0),
+            ),
+            (item().basis(100.0), vec2(0.0, 20.0)),
+        ],
+    );
+    assert_rects(
+        &rects,
+        &[
+            [0.0, 0.0, 185.0, 20.0],
+            [195.0, 0.0, 95.0, 20.0],
+            [300.0, 0.0, 100.0, 20.0],
+        ],
+    );
+}
+
+#[test]
+fn wrap() {
+    let rects = layout(
+        &Flex::horizontal(),
+        &[sized(150.0, 20.0), sized(150.0, 30.0), sized(150.0, 20.0)],
+    );
+    assert_rects(
+        &rects,
+        &[
+            [0.0, 0.0, 150.0, 30.0],
+            [160.0, 0.0, 150.0, 30.0],
+            [0.0, 40.0, 150.0, 20.0],
+        ],
+    );
+}
+
+#[test]
+fn no_wrap_overflows() {
+    let rects = layout(
+        &Flex::horizontal().wrap(false),
+        &[sized(150.0, 20.0), sized(150.0, 20.0), sized(150.0, 20.0)],
+    );
+    assert_rects(
+        &rects,
+        &[
+            [0.0, 0.0, 150.0, 20.0],
+            [160.0, 0.0, 150.0, 20.0],
+            [320.0, 0.0, 150.0, 20.0],
+        ],
+    );
+}
+
+#[test]
+fn order() {
+    let rects = layout(
+        &Flex::horizontal().align_items(FlexAlign::Start),
+        &[
+            (item().order(1), vec2(50.0, 20.0)),
+            sized(80.0, 30.0),
+            (item().order(-1), vec2(40.0, 10.0)),
+        ],
+    );
+    assert_rects(
+        &rects,
+        &[
+            [140.0, 0.0, 50.0, 20.0],
+            [50.0, 0.0, 80.0, 30.0],
+            [0.0, 0.0, 40.0, 10.0],
+        ],
+    );
+}
+
+#[test]
+fn order_wrap() {
+    let rects = layout(
+        &Flex::horizontal(),
+        &[
+            (item().order(1), vec2(150.0, 20.0)),
+            sized(150.0, 30.0),
+            sized(150.0, 20.0),
+        ],
+    );
+    assert_rects(
+        &rects,
+        &[
+            [0.0, 40.0, 150.0, 20.0],
+            [0.0, 0.0, 150.0, 30.0],
+            [160.0, 0.0, 150.0, 30.0],
+        ],
+    );
+}
+
+#[test]
+fn hidden_items_take_no_space() {
+    let rects = layout(
+        &Flex::horizontal().align_items(FlexAlign::Start),
+        &[
+            sized(50.0, 20.0),
+            (item().visible(false), vec2(80.0, 30.0)),
+            sized(40.0, 10.0),
+        ],
+    );
+    assert_eq!(rects.len(), 3);
+    assert_rects(
+        &[rects[0], rects[2]],
+        &[[0.0, 0.0, 50.0, 20.0], [60.0, 0.0, 40.0, 10.0]],
+    );
+}
+
+#[test]
+fn breakpoints() {
+    assert_eq!(FlexBreakpoint::from_width(360.0), FlexBreakpoint::Compact);
+    assert_eq!(FlexBreakpoint::from_width(600.0), FlexBreakpoint::Medium);
+    assert_eq!(FlexBreakpoint::from_width(1200.0), FlexBreakpoint::Expanded);
+    assert!(FlexBreakpoint::Expanded > FlexBreakpoint::Medium);
+
+    // The test screen is 400 wide, so the responsive flex is vertical
+    let ctx = Context::default();
+    run_frame(&ctx, input(), |ui| {
+        let flex = Flex::responsive(ui, |width| {
+            if FlexBreakpoint::from_width(width) == FlexBreakpoint::Compact {
+                Flex::vertical()
+            } else {
+                Flex::horizontal()
+            }
+        });
+        assert_eq!(flex, Flex::vertical());
+        flex.show(ui, |flex| {
+            assert_eq!(flex.breakpoint(), FlexBreakpoint::Compact);
+        });
+    });
+}
+
+/// Three items that wrap into two rows of height 20, leaving 300 - 50 = 250 free cross space
+fn wrapping_items() -> [(FlexItem, Vec2); 3] {
+    [sized(150.0, 20.0), sized(150.0, 20.0), sized(150.0, 20.0)]
+}
+
+#[test]
+fn align_content() {
+    let cases = [
+        (FlexAlignContent::Normal, 0.0, 30.0, 20.0),
+        (FlexAlignContent::Start, 0.0, 30.0, 20.0),
+        (FlexAlignContent::End, 250.0, 280.0, 20.0),
+        (FlexAlignContent::Center, 125.0, 155.0, 20.0),
+        (FlexAlignContent::SpaceBetween, 0.0, 280.0, 20.0),
+        (FlexAlignContent::SpaceAround, 62.5, 217.5, 20.0),
+        (FlexAlignContent::Stretch, 0.0, 155.0, 145.0),
+    ];
+    for (align_content, first_row, second_row, height) in cases {
+        let rects = layout(
+            &Flex::horizontal().align_content(align_content),
+            &wrapping_items(),
+        );
+        assert_rects(
+            &rects,
+            &[
+                [0.0, first_row, 150.0, height],
+                [160.0, first_row, 150.0, height],
+                [0.0, second_row, 150.0, height],
+            ],
+        );
+    }
+}
+
+#[test]
+fn align_content_single_row() {
+    let rects = layout(
+        &Flex::horizontal().align_content(FlexAlignContent::SpaceBetween),
+        &[sized(150.0, 20.0)],
+    );
+    assert_rects(&rects, &[[0.0, 0.0, 150.0, 20.0]]);
+
+    let rects = layout(
+        &Flex::horizontal().align_content(FlexAlignContent::SpaceAround),
+        &[sized(150.0, 20.0)],
+    );
+    assert_rects(&rects, &[[0.0, 140.0, 150.0, 20.0]]);
+}
+
+#[test]
+fn align_content_vertical() {
+    // 400 - 50 = 350 free cross space
+    let rects = layout(
+        &Flex::vertical().align_content(FlexAlignContent::Center),
+        &[sized(20.0, 140.0), sized(20.0, 140.0), sized(20.0, 140.0)],
+    );
+    assert_rects(
+        &rects,
+        &[
+            [175.0, 0.0, 20.0, 140.0],
+            [175.0, 150.0, 20.0, 140.0],
+            [205.0, 0.0, 20.0, 140.0],
+        ],
+    );
+}
+
+#[test]
+fn row_reverse() {
+    let rects = layout(
+        &Flex::horizontal()
+            .direction(FlexDirection::HorizontalReverse)
+            .align_items(FlexAlign::Start),
+        &[sized(50.0, 20.0), sized(80.0, 30.0), sized(40.0, 10.0)],
+    );
+    assert_rects(
+        &rects,
+        &[
+            [350.0, 0.0, 50.0, 20.0],
+            [260.0, 0.0, 80.0, 30.0],
+            [210.0, 0.0, 40.0, 10.0],
+        ],
+    );
+}
+
+#[test]
+fn row_reverse_grow_wrap() {
+    let rects = layout(
+        &Flex::new().direction(FlexDirection::HorizontalReverse),
+        &[
+            (item().grow(1.0), vec2(150.0, 20.0)),
+            sized(150.0, 20.0),
+            sized(150.0, 20.0),
+        ],
+    );
+    assert_rects(
+        &rects,
+        &[
+            [160.0, 0.0, 240.0, 20.0],
+            [0.0, 0.0, 150.0, 20.0],
+            [250.0, 30.0, 150.0, 20.0],
+        ],
+    );
+}
+
+#[test]
+fn column_reverse() {
+    let rects = layout(
+        &Flex::new()
+            .direction(FlexDirection::VerticalReverse)
+            .align_items(FlexAlign::Start),
+        &[sized(50.0, 20.0), sized(80.0, 30.0), sized(40.0, 10.0)],
+    );
+    assert_rects(
+        &rects,
+        &[
+            [0.0, 280.0, 50.0, 20.0],
+            [0.0, 240.0, 80.0, 30.0],
+            [0.0, 220.0, 40.0, 10.0],
+        ],
+    );
+}
+
+#[test]
+fn wrap_reverse() {
+    // Without a definite cross size (align-content: normal), the container is as high as its rows
+    let rects = layout(&Flex::horizontal().wrap_reverse(true), &wrapping_items());
+    assert_rects(
+        &rects,
+        &[
+            [0.0, 30.0, 150.0, 20.0],
+            [160.0, 30.0, 150.0, 20.0],
+            [0.0, 0.0, 150.0, 20.0],
+        ],
+    );
+
+    // Otherwise, the rows start at the bottom
+    let rects = layout(
+        &Flex::horizontal()
+            .wrap_reverse(true)
+            .align_content(FlexAlignContent::Start),
+        &wrapping_items(),
+    );
+    assert_rects(
+        &rects,
+        &[
+            [0.0, 280.0, 150.0, 20.0],
+            [160.0, 280.0, 150.0, 20.0],
+            [0.0, 250.0, 150.0, 20.0],
+        ],
+    );
+}
+
+#[test]
+fn wrap_reverse_align_items() {
+    // The cross start is at the bottom of the row
+    let rects = layout(
+        &Flex::horizontal()
+            .wrap_reverse(true)
+            .align_items(FlexAlign::Start),
+        &[sized(50.0, 20.0), sized(80.0, 30.0)],
+    );
+    assert_rects(&rects, &[[0.0, 10.0, 50.0, 20.0], [60.0, 0.0, 80.0, 30.0]]);
+}
+
+/// Run a single frame, showing the items with [`Flex::show_measured`]
+fn layout_measured(ctx: &Context, flex: &Flex, items: &[(FlexItem, Vec2)]) -> Vec<Rect> {
+    let mut rects = Vec::new();
+    run_frame(ctx, input(), |ui| {
+        rects = flex
+            .clone()
+            .gap(GAP)
+            .show_measured(ui, |flex| add_sized(flex, items))
+            .inner;
+    });
+    rects
+}
+
+#[test]
+fn measured_without_discard() {
+    let ctx = Context::default();
+    let items = [
+        (item().grow(1.0), vec2(50.0, 20.0)),
+        sized(150.0, 30.0),
+        sized(150.0, 20.0),
+        sized(100.0, 20.0),
+    ];
+    let rects = layout_measured(&ctx, &Flex::horizontal(), &items);
+    assert_rects(
+        &rects,
+        &[
+            [0.0, 0.0, 80.0, 30.0],
+            [90.0, 0.0, 150.0, 30.0],
+            [250.0, 0.0, 150.0, 30.0],
+            [0.0, 40.0, 100.0, 20.0],
+        ],
+    );
+    assert_eq!(FlexStats::get(&ctx).discards, 0);
+    let sizing_passes = FlexStats::get(&ctx).sizing_passes;
+    assert!(sizing_passes > 0);
+
+    // Once the sizes are known, a single sizing pass confirms them
+    layout_measured(&ctx, &Flex::horizontal(), &items);
+    assert_eq!(FlexStats::get(&ctx).discards, 0);
+    assert_eq!(FlexStats::get(&ctx).sizing_passes, sizing_passes + 1);
+}
+
+/// Run a single frame with a growing text edit for `name` and a label greeting `name`.
+/// Returns the rects of both and how often the contents of [`Flex::show_measured`] ran.
+fn greeting(ctx: &Context, input: RawInput, name: &mut String) -> ([Rect; 2], usize) {
+    let mut rects = [Rect::NOTHING; 2];
+    let mut passes = 0;
+    run_frame(ctx, input, |ui| {
+        passes = 0;
+        rects = Flex::horizontal()
+            .gap(GAP)
+            .show_measured(ui, |flex| {
+                passes += 1;
+                let text_edit = egui::TextEdit::singleline(&mut *name).id(egui::Id::new("name"));
+                let text_edit = flex.add(item().grow(1.0), text_edit).response.rect;
+                let label = flex.add(item(), egui::Label::new(format!("Hello {name}!")));
+                [text_edit, label.response.rect]
+            })
+            .inner;
+    });
+    (rects, passes)
+}
+
+#[test]
+fn measured_text() {
+    let ctx = Context::default();
+    let mut name = String::from("a");
+    for _ in 0..4 {
+        greeting(&ctx, input(), &mut name);
+    }
+    let ([_, label], _) = greeting(&ctx, input(), &mut name);
+    assert_eq!(label.max.x, 400.0);
+    let width = label.width();
+
+    // The text changed since the last frame
+    name.push_str(" longer text");
+    FlexStats::reset(&ctx);
+    let ([text_edit, label], passes) = greeting(&ctx, input(), &mut name);
+    // f ran in the sizing passes and the visible pass
+    assert!(passes > 1);
+    // The label is measured with the new text in the same frame, the text edit shrinks
+    assert!(label.width() > width + 20.0, "{label:?}");
+    assert_eq!(label.max.x, 400.0);
+    assert_eq!(text_edit.max.x + GAP.x, label.min.x);
+    assert_eq!(FlexStats::get(&ctx).discards, 0);
+
+    // Typing into the text edit. Widgets are disabled in the sizing passes, so the text is
+    // typed once and the new size is only known after the visible pass.
+    ctx.memory_mut(|mem| mem.request_focus(egui::Id::new("name")));
+    let width = label.width();
+    let input = RawInput {
+        events: vec![egui::Event::Text(" and more".to_owned())],
+        ..input()
+    };
+    let ([_, label], _) = greeting(&ctx, input, &mut name);
+    assert_eq!(name, "a longer text and more");
+    assert!(label.width() > width + 20.0, "{label:?}");
+    assert_eq!(label.max.x, 400.0);
+}
+
+#[test]
+fn discards_are_counted() {
+    let ctx = Context::default();
+    run_frame(&ctx, input(), |ui| {
+        Flex::horizontal().show(ui, |flex| {
+            flex.add_ui(item(), |ui| ui.label("Hello"));
+        });
+    });
+    assert!(FlexStats::get(&ctx).discards > 0);
+
+    FlexStats::reset(&ctx);
+    assert_eq!(FlexStats::get(&ctx), FlexStats::default());
+}
+
+/// Show labels of different sizes, returning the rect and the baseline of the first text row of each
+fn layout_text(flex: &Flex, sizes: &[f32]) -> Vec<(Rect, f32)> {
+    common::show(|ui| {
+        flex.clone()
+            .gap(GAP)
+            .show(ui, |flex| {
+                sizes
+                    .iter()
+                    .map(|size| {
+                        let text = egui::RichText::new("Baseline").size(*size);
+                        let galley = egui::WidgetText::from(text).into_galley(
+                            flex.ui(),
+                            Some(egui::TextWrapMode::Extend),
+                            f32::INFINITY,
+                            egui::TextStyle::Body,
+                        );
+                        let baseline = galley.rows[0].glyphs[0].pos.y;
+                        let rect = flex
+                            .add_ui(item(), |ui| ui.label(galley.clone()).rect)
+                            .inner;
+                        (rect, rect.min.y + baseline)
+                    })
+                    .collect()
+            })
+            .inner
+    })
+}
+
+#[test]
+fn align_baseline() {
+    let items = layout_text(
+        &Flex::horizontal().align_items(FlexAlign::Baseline),
+        &[10.0, 30.0, 16.0],
+    );
+    let baseline = items[1].1;
+    for (rect, item_baseline) in &items {
+        assert!(
+            (item_baseline - baseline).abs() < 0.5,
+            "baselines differ: {items:?}"
+        );
+        assert!(
+            rect.min.y >= -0.5,
+            "item outside of the container: {items:?}"
+        );
+    }
+    // The biggest text determines the row's height, so it starts at the top
+    assert!(items[1].0.min.y.abs() < 0.5, "{items:?}");
+
+    // Without baseline alignment the small text is at the top
+    let items = layout_text(
+        &Flex::horizontal().align_items(FlexAlign::Start),
+        &[10.0, 30.0],
+    );
+    assert!((items[0].1 - items[1].1).abs() > 5.0, "{items:?}");
+}
@@ -0,0 +1,174 @@
+//! Check scrolling flex containers, sticky items and virtualized items.
+
+mod common;
+
+use common::{input, run_frame, GAP};
+use egui::epaint::ClippedShape;
+use egui::{vec2, Color32, Context, Rect, ScrollArea, Vec2};
+use egui_flex::{item, Flex, FlexEdge, FlexItem};
+
+struct Output {
+    /// The rect of each item, `None` if it was skipped
+    rects: Vec<Option<Rect>>,
+    /// How many items were shown
+    shown: usize,
+    content_size: Vec2,
+    shapes: Vec<ClippedShape>,
+}
+
+/// Show a vertical flex container in a scroll area scrolled to `offset`.
+/// Each item is a 50 wide rect with the given height.
+fn show_list(ctx: &Context, offset: f32, items: &[(FlexItem, f32)], virtualize: bool) -> Output {
+    let mut rects = Vec::new();
+    let mut shown = 0;
+    let mut content_size = Vec2::ZERO;
+    let output = run_frame(ctx, input(), |ui| {
+        let scroll = ScrollArea::vertical()
+            .vertical_scroll_offset(offset)
+            .show(ui, |ui| {
+                Flex::vertical().wrap(false).gap(GAP).show(ui, |flex| {
+                    for (item, height) in items {
+                        let content = |ui: &mut egui::Ui| {
+                            shown += 1;
+                            let (rect, _) =
+                                ui.allocate_exact_size(vec2(50.0, *height), egui::Sense::hover());
+                            ui.painter().rect_filled(rect, 0.0, Color32::RED);
+                            rect
+                        };
+                        rects.push(if virtualize {
+                            flex.add_ui_virtual(*item, content).map(|res| res.inner)
+                        } else {
+                            Some(flex.add_ui(*item, content).inner)
+                        });
+                    }
+                });
+            });
+        content_size = scroll.content_size;
+    });
+    Output {
+        rects,
+        shown,
+        content_size,
+        shapes: output.shapes,
+    }
+}
+
+/// Run a few frames, so the sizes are known and the scroll offset is applied
+fn layout(offset: f32, items: &[(FlexItem, f32)], virtualize: bool) -> Output {
+    let ctx = Context::default();
+    let mut output = show_list(&ctx, offset, items, virtualize);
+    for _ in 0..4 {
+        output = show_list(&ctx, offset, items, virtualize);
+    }
+    output
+}
+
+/// A list of 20 high items, items at the given indices are sticky
+fn list(count: usize, sticky: &[(usize, FlexEdge)]) -> Vec<(FlexItem, f32)> {
+    (0..count)
+        .map(|i| {
+            let item = sticky
+                .iter()
+                .find(|(index, _)| *index == i)
+                .map_or(item(), |(_, edge)| item().sticky(*edge));
+            (item, 20.0)
+        })
+        .collect()
+}
+
+#[test]
+fn scroll_overflows_instead_of_wrapping() {
+    let rects = common::show(|ui| {
+        Flex::horizontal()
+            .scroll(true)
+            .gap(GAP)
+            .show(ui, |flex| {
+                (0..10)
+                    .map(|_| {
+                        flex.add_ui(item(), |ui| {
+                            ui.allocate_exact_size(vec2(50.0, 20.0), egui::Sense::hover())
+                                .0
+                        })
+                        .inner
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .inner
+    });
+    assert_eq!(rects.len(), 10);
+    assert!(rects.iter().all(|rect| rect.min.y == 0.0), "{rects:?}");
+    assert_eq!(rects[9].min.x, 540.0);
+}
+
+#[test]
+fn sticky_header() {
+    let items = list(30, &[(0, FlexEdge::Start)]);
+
+    let output = layout(0.0, &items, false);
+    assert_eq!(output.rects[0].unwrap().min.y, 0.0);
+    assert_eq!(output.rects[1].unwrap().min.y, 30.0);
+
+    let output = layout(100.0, &items, false);
+    assert_eq!(
+        output.rects[0].unwrap().min.y,
+        0.0,
+        "the header stays on top"
+    );
+    assert_eq!(output.rects[4].unwrap().min.y, 20.0);
+    // The other items are clipped below the header
+    let clip_tops: Vec<f32> = output
+        .shapes
+        .iter()
+        .filter(|shape| matches!(shape.shape, egui::Shape::Rect(_)))
+        .map(|shape| shape.clip_rect.min.y)
+        .collect();
+    assert!(clip_tops.iter().filter(|top| **top == 20.0).count() > 5);
+}
+
+#[test]
+fn sticky_header_is_pushed_by_the_next_one() {
+    // The second header is at 150, at an offset of 140 it pushes the first header up by 10
+    let items = list(30, &[(0, FlexEdge::Start), (5, FlexEdge::Start)]);
+    let output = layout(140.0, &items, false);
+    assert_eq!(output.rects[0].unwrap().min.y, -10.0);
+    assert_eq!(output.rects[5].unwrap().min.y, 10.0);
+
+    let output = layout(200.0, &items, false);
+    assert_eq!(output.rects[5].unwrap().min.y, 0.0);
+}
+
+#[test]
+fn sticky_footer() {
+    let items = list(30, &[(29, FlexEdge::End)]);
+    let output = layout(0.0, &items, false);
+    assert_eq!(output.rects[29].unwrap().max.y, 300.0);
+
+    // At the end of the list the footer is at its position in the list
+    let output = layout(590.0, &items, false);
+    assert_eq!(output.rects[29].unwrap().max.y, 300.0);
+    assert_eq!(output.rects[28].unwrap().max.y, 270.0);
+}
+
+#[test]
+fn virtualized_items_outside_of_the_viewport_are_skipped() {
+    let items = list(100, &[(0, FlexEdge::Start)]);
+    let all = layout(1000.0, &items, false);
+    let virtualized = layout(1000.0, &items, true);
+
+    assert_eq!(all.shown, 100);
+    assert!(
+        virtualized.shown < 20,
+        "{} items were shown",
+        virtualized.shown
+    );
+    // The skipped items still take up their space
+    assert_eq!(virtualized.content_size, all.content_size);
+    assert!(virtualized.rects[50].is_none());
+    // The sticky header is always shown
+    assert_eq!(virtualized.rects[0].unwrap().min.y, 0.0);
+    for (virtualized, all) in virtualized.rects.iter().zip(&all.rects) {
+        if let Some(virtualized) = virtualized {
+            assert_eq!(Some(*virtualized), *all);
+        }
+    }
+}
@@ -0,0 +1,106 @@
+//! Check that widgets stretch their frame with the flex layout, while the content keeps its size.
+
+mod common;
+
+use common::GAP;
+use egui::{vec2, Frame, Pos2, Rect, Ui};
+use egui_flex::{Flex, FlexFrameWidget, FlexInstance};
+
+/// Show a horizontal flex for a few frames, so the sizes measured in the previous frame are used
+fn show<R>(mut f: impl FnMut(&mut FlexInstance) -> R) -> R {
+    common::show(|ui| Flex::horizontal().gap(GAP).show(ui, &mut f).inner)
+}
+
+/// A frame with a 5 px margin around a 50x20 rect
+struct Card;
+
+impl FlexFrameWidget for Card {
+    type Response = Rect;
+
+    fn frame(&self, _ui: &Ui) -> Frame {
+        Frame::none().inner_margin(5.0)
+    }
+
+    fn content(self, ui: &mut Ui) -> Rect {
+        ui.allocate_exact_size(vec2(50.0, 20.0), egui::Sense::hover())
+            .0
+    }
+}
+
+#[test]
+fn frame_widget_stretches_frame() {
+    let [first, second] = show(|flex| {
+        [(); 2].map(|()| {
+            let res = flex.add(egui_flex::item().grow(1.0), Card);
+            (res.response.rect, res.inner)
+        })
+    });
+
+    // (400 - 10) / 2 = 195 per item, the content is centered in the frame
+    assert_eq!(first.0, Rect::from_min_size(Pos2::ZERO, vec2(195.0, 30.0)));
+    assert_eq!(second.0.min.x, 205.0);
+    assert_eq!(
+        first.1,
+        Rect::from_min_size(Pos2::new(72.5, 5.0), vec2(50.0, 20.0))
+    );
+}
+
+#[cfg(feature = "egui_form")]
+#[test]
+fn form_field_fills_item() {
+    use egui_form::{EguiValidationReport, Form, FormField, IntoFieldPath};
+    use std::borrow::Cow;
+
+    #[derive(Clone)]
+    struct Field;
+
+    impl IntoFieldPath<Field> for Field {
+        fn into_field_path(self) -> Field {
+            self
+        }
+    }
+
+    struct NoErrors;
+
+    impl EguiValidationReport for NoErrors {
+        type FieldPath<'a> = Field;
+        type Errors = ();
+
+        fn get_field_error(&self, _field: Field) -> Option<Cow<'static, str>> {
+            None
+        }
+
+        fn has_errors(&self) -> bool {
+            false
+        }
+
+        fn error_count(&self) -> usize {
+            0
+        }
+
+        fn get_errors(&self) -> Option<&()> {
+            None
+        }
+    }
+
+    let mut text = String::new();
+    let (item_rect, text_edit_rect) = show(|flex| {
+        let mut form = Form::<NoErrors>::new();
+        flex.add(egui_flex::item(), egui::Button::new("Submit"));
+        let res = flex.add(
+            egui_flex::item().grow(1.0),
+            (
+                FormField::new(&mut form, Field).label("Name"),
+                egui::TextEdit::singleline(&mut text),
+            ),
+        );
+        (res.response.rect, res.inner.rect)
+    });
+
+    // The item grows to fill the row and the text edit fills the item (its rect doesn't
+    // include the text edit's margin)
+    assert_eq!(item_rect.max.x, 400.0);
+    assert_eq!(text_edit_rect.width(), item_rect.width() - 8.0);
+    // The item includes the label above the text edit
+    assert!(item_rect.height() > text_edit_rect.height() + 10.0);
+}
@@ -24,19 +24,44 @@ pub fn initialize(ctx: &egui::Context) {
 }
 
 pub fn icon_button(ui: &mut egui::Ui, icon: &str) -> Response {
-    Frame::none()
-        .inner_margin(Margin {
-            left: 0.0,
-            right: 0.0,
-            top: 0.0,
-            bottom: 0.0,
-        })
-        .show(ui, |ui| {
-            Button::new(RichText::new(icon).size(18.0))
-                .frame(false)
-                .ui(ui)
-        })
-        .inner
+    IconButton::new(icon).ui(ui)
+}
+
+/// A frameless button showing a material icon, like [`icon_button`].
+pub struct IconButton<'a> {
+    icon: &'a str,
+    size: f32,
+}
+
+impl<'a> IconButton<'a> {
+    /// Create a new icon button, pass one of the [`icons`].
+    pub fn new(icon: &'a str) -> Self {
+        Self { icon, size: 18.0 }
+    }
+
+    /// Set the size of the icon. Default is 18.
+    pub fn size(mut self, size: f32) -> Self {
+        self.size = size;
+        self
+    }
+}
+
+impl Widget for IconButton<'_> {
+    fn ui(self, ui: &mut egui::Ui) -> Response {
+        Frame::none()
+            .inner_margin(Margin {
+                left: 0.0,
+                right: 0.0,
+                top: 0.0,
+                bottom: 0.0,
+            })
+            .show(ui, |ui| {
+                Button::new(RichText::new(self.icon).size(self.size))
+                    .frame(false)
+                    .ui(ui)
+            })
+            .inner
+    }
 }
 
 pub fn icon_text(icon: &str) -> RichText {
@@ -0,0 +1,137 @@
+#![allow(clippy::needless_pass_by_value)] // It's ok here as it is an example
+use eframe::NativeOptions;
+use egui::{CentralPanel, Ui, ViewportBuilder, ViewportId};
+use egui_inbox::UiInbox;
+use egui_router::history::MemoryHistory;
+use egui_router::{EguiRouter, Request, Route, RouteTable};
+
+struct AppState {
+    inbox: UiInbox<RouterMessage>,
+}
+
+/// A message sent by a route, tagged with the viewport of the router that should handle it
+struct RouterMessage {
+    viewport: ViewportId,
+    action: RouterAction,
+}
+
+enum RouterAction {
+    Navigate(String),
+    Detach(String),
+    Back,
+}
+
+impl AppState {
+    /// Send an action to the router of the viewport `ui` is shown in
+    fn send(&self, ui: &Ui, action: RouterAction) {
+        self.inbox
+            .sender()
+            .send(RouterMessage {
+                viewport: ui.ctx().viewport_id(),
+                action,
+            })
+            .ok();
+    }
+}
+
+struct DetachedWindow {
+    id: ViewportId,
+    router: EguiRouter<AppState, MemoryHistory>,
+    open: bool,
+}
+
+fn main() -> eframe::Result<()> {
+    let mut state = AppState {
+        inbox: UiInbox::new(),
+    };
+
+    let table: RouteTable<AppState> = EguiRouter::<AppState>::builder()
+        .route("/", home)
+        .route("/post/{id}", post)
+        .default_path("/")
+        .build_table();
+
+    let mut router: EguiRouter<AppState> = table.router(&mut state);
+    let mut windows: Vec<DetachedWindow> = Vec::new();
+
+    eframe::run_simple_native(
+        "Multi Viewport Router Example",
+        NativeOptions::default(),
+        move |ctx, _frame| {
+            // Messages from detached windows are only read here, in the root viewport.
+            // Each message is handled by the router of the viewport it was sent from.
+            state.inbox.read(ctx).for_each(|msg| match msg.action {
+                RouterAction::Navigate(route) => {
+                    if msg.viewport == ViewportId::ROOT {
+                        router.navigate(&mut state, route).ok();
+                    } else if let Some(window) = windows.iter_mut().find(|w| w.id == msg.viewport) {
+                        window.router.navigate(&mut state, route).ok();
+                    }
+                }
+                RouterAction::Detach(route) => {
+                    let id = ViewportId::from_hash_of(("detached", windows.len()));
+                    windows.push(DetachedWindow {
+                        id,
+                        router: router.route_table().router_at(&mut state, route),
+                        open: true,
+                    });
+                }
+                RouterAction::Back => {
+                    if msg.viewport == ViewportId::ROOT {
+                        router.back().ok();
+                    } else if let Some(window) = windows.iter_mut().find(|w| w.id == msg.viewport) {
+                        window.router.back().ok();
+                    }
+                }
+            });
+
+            CentralPanel::default().show(ctx, |ui| {
+                router.ui(ui, &mut state);
+            });
+
+            for window in &mut windows {
+                ctx.show_viewport_immediate(
+                    window.id,
+                    ViewportBuilder::default().with_title("Detached route"),
+                    |ctx, _class| {
+                        CentralPanel::default().show(ctx, |ui| {
+                            window.router.ui(ui, &mut state);
+                        });
+                        if ctx.input(|i| i.viewport().close_requested()) {
+                            window.open = false;
+                        }
+                    },
+                );
+            }
+            windows.retain(|w| w.open);
+        },
+    )
+}
+
+fn home(_request: Request<AppState>) -> impl Route<AppState> {
+    |ui: &mut Ui, state: &mut AppState| {
+        ui.heading("Home!");
+
+        for id in 1..=3 {
+            ui.horizontal(|ui| {
+                if ui.link(format!("Post {id}")).clicked() {
+                    state.send(ui, RouterAction::Navigate(format!("/post/{id}")));
+                }
+                if ui.button("Open in new window").clicked() {
+                    state.send(ui, RouterAction::Detach(format!("/post/{id}")));
+                }
+            });
+        }
+    }
+}
+
+fn post(request: Request<AppState>) -> impl Route<AppState> {
+    let id = request.params.get("id").unwrap_or_default().to_owned();
+
+    move |ui: &mut Ui, state: &mut AppState| {
+        ui.heading(format!("Post {id}"));
+        if ui.button("back").clicked() {
+            state.send(ui, RouterAction::Back);
+        }
+    }
+}
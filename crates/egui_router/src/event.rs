@@ -0,0 +1,102 @@
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use web_time::Duration;
+
+/// Identifies the [`crate::EguiRouter`] that emitted a [`RouterEvent`].
+/// Routers created from the same [`crate::RouteTable`] share their events, compare the id with
+/// [`crate::EguiRouter::id`] to tell them apart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RouterId(pub(crate) usize);
+
+/// A matched route, including its params
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouteInfo {
+    /// The path that was navigated to (without the query string)
+    pub path: String,
+    /// The parsed path params
+    pub params: BTreeMap<String, String>,
+    /// The parsed query params
+    pub query: BTreeMap<String, String>,
+}
+
+impl RouteInfo {
+    pub(crate) fn new(
+        path: &str,
+        params: &matchit::Params,
+        query: &BTreeMap<Cow<str>, Cow<str>>,
+    ) -> Self {
+        Self {
+            path: path.to_string(),
+            params: params
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            query: query
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        }
+    }
+}
+
+/// Events emitted by the [`crate::EguiRouter`].
+/// Subscribe via [`crate::EguiRouter::subscribe`] or [`crate::RouteTable::subscribe`],
+/// e.g. to log page views or measure navigation timings.
+#[derive(Debug, Clone)]
+pub enum RouterEvent {
+    /// A route was matched and its handler is about to be called
+    NavigationStarted {
+        /// The router that is navigating
+        router: RouterId,
+        /// The previously active route
+        from: Option<RouteInfo>,
+        /// The route we are navigating to
+        to: RouteInfo,
+    },
+    /// A redirect route was matched
+    Redirected {
+        /// The router that is navigating
+        router: RouterId,
+        /// The redirect route that was requested
+        from: RouteInfo,
+        /// The path we are redirected to
+        to: String,
+        /// Time since the navigation started
+        duration: Duration,
+    },
+    /// The handler returned an error. The error ui will be shown.
+    HandlerFailed {
+        /// The router that is navigating
+        router: RouterId,
+        /// The previously active route
+        from: Option<RouteInfo>,
+        /// The route whose handler failed
+        to: RouteInfo,
+        /// The error message
+        error: String,
+        /// How long the handler took
+        duration: Duration,
+    },
+    /// A page transition finished
+    TransitionCompleted {
+        /// The router that showed the transition
+        router: RouterId,
+        /// The route that was transitioned away from
+        from: Option<RouteInfo>,
+        /// The route that is now active
+        to: RouteInfo,
+        /// Time since the navigation started, including the handler and the transition
+        duration: Duration,
+    },
+    /// The router went back in the history
+    Back {
+        /// The router that went back
+        router: RouterId,
+        /// The route that was left
+        from: RouteInfo,
+        /// The route that is now active
+        to: RouteInfo,
+        /// How long the route that was left was active
+        duration: Duration,
+    },
+}
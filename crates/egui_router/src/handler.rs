@@ -25,7 +25,7 @@ pub trait MakeHandler<State, Args> {
 }
 
 pub(crate) type Handler<State> =
-    Box<dyn FnMut(Request<State>) -> HandlerResult<Box<dyn Route<State>>>>;
+    Box<dyn Fn(Request<State>) -> HandlerResult<Box<dyn Route<State>>>>;
 
 impl<F, State, R> MakeHandler<State, (Request<'static, State>, ())> for F
 where
@@ -0,0 +1,248 @@
+use crate::history::{History, HistoryEvent, HistoryResult};
+use egui::Context;
+use egui_inbox::{UiInbox, UiInboxSender};
+
+/// A history implementation for native apps that can be opened via deep links
+/// (e.g. `myapp://post/42`).
+///
+/// The initial route is taken from the command line arguments or an environment variable,
+/// and external url open requests can be sent via [`DeepLinkHistory::sender`].
+/// Besides that, it behaves like [`crate::history::MemoryHistory`].
+///
+/// # Example
+/// ```rust
+/// # use egui_router::history::DeepLinkHistory;
+/// # use egui_router::EguiRouter;
+/// let history = DeepLinkHistory::new("myapp")
+///     .with_env_var("MYAPP_ROUTE")
+///     .with_args(std::env::args().skip(1));
+/// let sender = history.sender();
+///
+/// let router: EguiRouter<(), DeepLinkHistory> = EguiRouter::<()>::builder()
+///     .route("/", || |ui: &mut egui::Ui, _: &mut ()| {
+///         ui.label("Home");
+///     })
+///     .default_path("/")
+///     .build_table()
+///     .router_with_history(&mut (), history);
+///
+/// // E.g. from your OS url handler:
+/// sender.send("myapp://post/42".to_string()).ok();
+/// ```
+#[derive(Debug)]
+pub struct DeepLinkHistory {
+    scheme: String,
+    initial_route: Option<String>,
+    inbox: UiInbox<String>,
+    state: u32,
+    accept_paths: bool,
+}
+
+impl DeepLinkHistory {
+    /// Create a new [`DeepLinkHistory`] that handles urls with the given scheme (e.g. `"myapp"`).
+    pub fn new(scheme: impl Into<String>) -> Self {
+        Self {
+            scheme: scheme.into(),
+            initial_route: None,
+            inbox: UiInbox::new(),
+            state: 0,
+            accept_paths: false,
+        }
+    }
+
+    /// Also accept plain paths starting with `/` (e.g. `/post/42`) from
+    /// [`DeepLinkHistory::with_env_var`] and [`DeepLinkHistory::sender`], not only deep links.
+    /// Call this before [`DeepLinkHistory::with_env_var`].
+    /// Command line arguments are never accepted as plain paths, since they are usually files.
+    ///
+    /// Default is `false`.
+    pub fn accept_paths(mut self, accept_paths: bool) -> Self {
+        self.accept_paths = accept_paths;
+        self
+    }
+
+    /// Parse a deep link, or a plain path if [`DeepLinkHistory::accept_paths`] is set
+    fn parse(&self, url: &str) -> Option<String> {
+        if self.accept_paths {
+            parse_path(url).or_else(|| parse_deep_link(&self.scheme, url))
+        } else {
+            parse_deep_link(&self.scheme, url)
+        }
+    }
+
+    /// Take the initial route from the first argument that is a deep link (see [`parse_deep_link`]).
+    /// Usually you'd pass `std::env::args().skip(1)`.
+    /// Has no effect if an initial route was already set.
+    pub fn with_args(mut self, args: impl IntoIterator<Item = impl AsRef<str>>) -> Self {
+        if self.initial_route.is_none() {
+            self.initial_route = route_from_args(&self.scheme, args);
+        }
+        self
+    }
+
+    /// Take the initial route from the given environment variable, if it is set and contains
+    /// a deep link (see [`parse_deep_link`]) or a path (see [`DeepLinkHistory::accept_paths`]).
+    /// Has no effect if an initial route was already set.
+    pub fn with_env_var(mut self, name: &str) -> Self {
+        if self.initial_route.is_none() {
+            self.initial_route = std::env::var(name)
+                .ok()
+                .and_then(|value| self.parse(&value));
+        }
+        self
+    }
+
+    /// Set the initial route directly, either as a deep link (see [`parse_deep_link`])
+    /// or as a plain path starting with `/`.
+    pub fn with_initial_url(mut self, url: &str) -> Self {
+        self.initial_route = parse_path(url).or_else(|| parse_deep_link(&self.scheme, url));
+        self
+    }
+
+    /// Get a sender to open urls. Urls that can't be parsed by [`parse_deep_link`] are ignored,
+    /// as are plain paths unless [`DeepLinkHistory::accept_paths`] is set.
+    pub fn sender(&self) -> UiInboxSender<String> {
+        self.inbox.sender()
+    }
+}
+
+impl History for DeepLinkHistory {
+    fn update(&mut self, ctx: &Context) -> impl Iterator<Item = HistoryEvent> + 'static {
+        let locations: Vec<_> = self
+            .inbox
+            .read(ctx)
+            .filter_map(|url| self.parse(&url))
+            .collect();
+        let events: Vec<_> = locations
+            .into_iter()
+            .map(|location| {
+                self.state += 1;
+                HistoryEvent {
+                    location,
+                    state: Some(self.state),
+                }
+            })
+            .collect();
+        events.into_iter()
+    }
+
+    fn active_route(&self) -> Option<(String, Option<u32>)> {
+        self.initial_route.clone().map(|route| (route, None))
+    }
+
+    fn push(&mut self, _url: &str, state: u32) -> HistoryResult {
+        self.state = state;
+        Ok(())
+    }
+
+    fn replace(&mut self, _url: &str, state: u32) -> HistoryResult {
+        self.state = state;
+        Ok(())
+    }
+
+    fn back(&mut self) -> HistoryResult {
+        self.state = self.state.saturating_sub(1);
+        Ok(())
+    }
+
+    fn forward(&mut self) -> HistoryResult {
+        Ok(())
+    }
+}
+
+/// Parse a deep link into a router path.
+///
+/// Accepts `scheme://path`, `scheme:/path` and `scheme:path` (the scheme is matched case-insensitively).
+/// Query and fragment are preserved. Returns `None` for any other input, including plain paths
+/// like `/post/42`, since they could just as well be a file path.
+///
+/// ```rust
+/// # use egui_router::history::parse_deep_link;
+/// assert_eq!(parse_deep_link("myapp", "myapp://post/42?a=b"), Some("/post/42?a=b".to_string()));
+/// assert_eq!(parse_deep_link("myapp", "/post/42"), None);
+/// assert_eq!(parse_deep_link("myapp", "https://example.com"), None);
+/// ```
+pub fn parse_deep_link(scheme: &str, url: &str) -> Option<String> {
+    let url = url.trim();
+    let (url_scheme, rest) = url.split_once(':')?;
+    if !url_scheme.eq_ignore_ascii_case(scheme) {
+        return None;
+    }
+
+    let rest = rest.trim_start_matches('/');
+    Some(format!("/{rest}"))
+}
+
+/// A plain path starting with `/`
+fn parse_path(url: &str) -> Option<String> {
+    let url = url.trim();
+    url.starts_with('/').then(|| url.to_string())
+}
+
+/// Find the first argument that is a deep link (see [`parse_deep_link`]) and return its path.
+/// Arguments starting with `-` are skipped, so flags can be mixed with the url, and plain paths
+/// are ignored, so a file passed to the app isn't opened as a route.
+pub fn route_from_args(
+    scheme: &str,
+    args: impl IntoIterator<Item = impl AsRef<str>>,
+) -> Option<String> {
+    args.into_iter()
+        .filter(|arg| !arg.as_ref().starts_with('-'))
+        .find_map(|arg| parse_deep_link(scheme, arg.as_ref()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_deep_link, route_from_args, DeepLinkHistory};
+    use crate::history::History;
+
+    #[test]
+    fn parses_scheme_variants() {
+        for url in [
+            "myapp://post/42",
+            "myapp:/post/42",
+            "myapp:post/42",
+            "MyApp://post/42",
+        ] {
+            assert_eq!(parse_deep_link("myapp", url), Some("/post/42".to_string()));
+        }
+        assert_eq!(parse_deep_link("myapp", "myapp://"), Some("/".to_string()));
+        assert_eq!(
+            parse_deep_link("myapp", "myapp://search?q=a%20b#top"),
+            Some("/search?q=a%20b#top".to_string())
+        );
+    }
+
+    #[test]
+    fn rejects_other_schemes() {
+        assert_eq!(parse_deep_link("myapp", "otherapp://post/42"), None);
+        assert_eq!(parse_deep_link("myapp", "post/42"), None);
+        assert_eq!(parse_deep_link("myapp", "/post/42"), None);
+        assert_eq!(parse_deep_link("myapp", ""), None);
+    }
+
+    #[test]
+    fn finds_route_in_args() {
+        assert_eq!(
+            route_from_args("myapp", ["--verbose", "myapp://post/1", "/ignored"]),
+            Some("/post/1".to_string())
+        );
+        assert_eq!(route_from_args("myapp", ["--verbose"]), None);
+        assert_eq!(route_from_args("myapp", ["/home/user/file.txt"]), None);
+    }
+
+    #[test]
+    fn paths_are_only_accepted_explicitly() {
+        let history = DeepLinkHistory::new("myapp").with_args(["/home/user/file.txt"]);
+        assert_eq!(history.active_route(), None);
+
+        let history = DeepLinkHistory::new("myapp").with_initial_url("/post/1");
+        assert_eq!(history.active_route(), Some(("/post/1".to_string(), None)));
+
+        let history = DeepLinkHistory::new("myapp");
+        assert_eq!(history.parse("/post/1"), None);
+        let history = history.accept_paths(true);
+        assert_eq!(history.parse("/post/1"), Some("/post/1".to_string()));
+        assert_eq!(history.parse("myapp://post/1"), Some("/post/1".to_string()));
+    }
+}
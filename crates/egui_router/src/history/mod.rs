@@ -1,10 +1,12 @@
 #[cfg(target_arch = "wasm32")]
 mod browser;
+mod deep_link;
 mod memory;
 
 use crate::history;
 #[cfg(target_arch = "wasm32")]
 pub use browser::BrowserHistory;
+pub use deep_link::{parse_deep_link, route_from_args, DeepLinkHistory};
 pub use memory::MemoryHistory;
 
 /// Implement this trait to provide a custom history implementation
@@ -40,7 +42,7 @@ pub struct HistoryEvent {
 }
 
 /// History Result type
-type HistoryResult<T = ()> = Result<T, HistoryError>;
+pub type HistoryResult<T = ()> = Result<T, HistoryError>;
 
 /// History error
 #[derive(Debug, thiserror::Error)]
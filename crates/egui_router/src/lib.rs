@@ -4,14 +4,19 @@
 
 #[cfg(feature = "async")]
 mod async_route;
+mod event;
 mod handler;
 /// History types
 pub mod history;
 mod route_kind;
+mod route_table;
 mod router;
 mod router_builder;
+#[cfg(feature = "testing")]
+pub mod testing;
 /// Transition types
 pub mod transition;
+mod transition_rules;
 
 use crate::history::HistoryError;
 use crate::transition::{ActiveTransition, SlideFadeTransition, SlideTransition, Transition};
@@ -21,7 +26,9 @@ use std::borrow::Cow;
 use std::collections::BTreeMap;
 use std::sync::atomic::AtomicUsize;
 
+pub use event::{RouteInfo, RouterEvent, RouterId};
 pub use handler::{HandlerError, HandlerResult};
+pub use route_table::RouteTable;
 pub use router::EguiRouter;
 pub use router_builder::RouterBuilder;
 
@@ -41,6 +48,10 @@ static ID: AtomicUsize = AtomicUsize::new(0);
 
 struct RouteState<State> {
     path: String,
+    pattern: String,
+    info: RouteInfo,
+    /// When the route was navigated to
+    shown_at: web_time::Instant,
     route: HandlerResult<Box<dyn Route<State>>>,
     id: usize,
     state: u32,
@@ -138,6 +149,8 @@ impl TransitionConfig {
 struct CurrentTransition<State> {
     active_transition: ActiveTransition,
     leaving_route: Option<RouteState<State>>,
+    from: Option<RouteInfo>,
+    started: web_time::Instant,
 }
 
 /// Request passed to a [`handler::MakeHandler`]
@@ -1,6 +1,9 @@
 use crate::handler::Handler;
 
 pub(crate) enum RouteKind<State> {
-    Route(Handler<State>),
+    Route {
+        pattern: String,
+        handler: Handler<State>,
+    },
     Redirect(String),
 }
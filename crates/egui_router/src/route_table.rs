@@ -0,0 +1,78 @@
+use crate::event::RouterEvent;
+use crate::history::History;
+use crate::route_kind::RouteKind;
+use crate::router_builder::ErrorUi;
+use crate::transition_rules::TransitionRules;
+use crate::{EguiRouter, TransitionConfig};
+use egui_inbox::broadcast::{Broadcast, BroadcastReceiver};
+use std::rc::Rc;
+
+pub(crate) struct RouteTableInner<State> {
+    pub(crate) router: matchit::Router<RouteKind<State>>,
+    pub(crate) default_route: Option<String>,
+
+    pub(crate) forward_transition: TransitionConfig,
+    pub(crate) backward_transition: TransitionConfig,
+    pub(crate) replace_transition: TransitionConfig,
+    pub(crate) transition_rules: TransitionRules,
+
+    pub(crate) default_duration: Option<f32>,
+
+    pub(crate) error_ui: ErrorUi<State>,
+
+    pub(crate) events: Broadcast<RouterEvent>,
+}
+
+/// The routes and transition configuration of a router, created by [`crate::RouterBuilder::build_table`].
+///
+/// A [`RouteTable`] is cheap to clone and can create any number of [`EguiRouter`]s.
+/// All of them share the same handlers, but each keeps its own history.
+/// This is useful to drive multiple egui viewports (e.g. a route that is detached into its own
+/// window) from a single router definition.
+///
+/// The handlers and routes aren't `Send`, so the table and its routers must stay on the ui thread.
+/// Use immediate viewports ([`egui::Context::show_viewport_immediate`]) to show them,
+/// deferred viewports are not supported.
+pub struct RouteTable<State> {
+    pub(crate) inner: Rc<RouteTableInner<State>>,
+}
+
+impl<State> Clone for RouteTable<State> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<State: 'static> RouteTable<State> {
+    /// Subscribe to the [`RouterEvent`]s of all routers created from this table.
+    pub fn subscribe(&self) -> BroadcastReceiver<RouterEvent> {
+        self.inner.events.subscribe()
+    }
+
+    /// Create a new router with the default history, starting at the default path.
+    pub fn router<H: History + Default>(&self, state: &mut State) -> EguiRouter<State, H> {
+        self.router_with_history(state, H::default())
+    }
+
+    /// Create a new router with a custom history, starting at the history's active route or
+    /// the default path.
+    pub fn router_with_history<H: History>(
+        &self,
+        state: &mut State,
+        history: H,
+    ) -> EguiRouter<State, H> {
+        EguiRouter::from_table(self.clone(), history, None, state)
+    }
+
+    /// Create a new router with the default history, starting at `path`.
+    /// Use this e.g. to open a route in a new viewport.
+    pub fn router_at<H: History + Default>(
+        &self,
+        state: &mut State,
+        path: impl Into<String>,
+    ) -> EguiRouter<State, H> {
+        EguiRouter::from_table(self.clone(), H::default(), Some(path.into()), state)
+    }
+}
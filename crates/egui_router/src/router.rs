@@ -1,31 +1,33 @@
+use crate::event::{RouteInfo, RouterEvent, RouterId};
+use crate::handler::Handler;
 use crate::history::{DefaultHistory, History};
 use crate::route_kind::RouteKind;
-use crate::router_builder::{ErrorUi, RouterBuilder};
+use crate::route_table::RouteTable;
+use crate::router_builder::RouterBuilder;
 use crate::transition::{ActiveTransition, ActiveTransitionResult};
 use crate::{
-    CurrentTransition, Request, RouteState, RouterError, RouterResult, TransitionConfig, ID,
+    CurrentTransition, HandlerResult, Request, Route, RouteState, RouterError, RouterResult,
+    TransitionConfig, ID,
 };
 use egui::Ui;
+use egui_inbox::broadcast::BroadcastReceiver;
 use matchit::MatchError;
 use std::borrow::Cow;
 use std::collections::BTreeMap;
-use std::sync::atomic::Ordering;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use web_time::Instant;
+
+static ROUTER_ID: AtomicUsize = AtomicUsize::new(0);
 
 /// A router instance
 pub struct EguiRouter<State, History = DefaultHistory> {
-    router: matchit::Router<RouteKind<State>>,
+    id: RouterId,
+    table: RouteTable<State>,
     history: Vec<RouteState<State>>,
 
     history_kind: History,
 
-    forward_transition: TransitionConfig,
-    backward_transition: TransitionConfig,
-    replace_transition: TransitionConfig,
-
     current_transition: Option<CurrentTransition<State>>,
-    default_duration: Option<f32>,
-
-    error_ui: ErrorUi<State>,
 }
 
 impl<State: 'static, H: History + Default> EguiRouter<State, H> {
@@ -33,30 +35,34 @@ impl<State: 'static, H: History + Default> EguiRouter<State, H> {
     pub fn builder() -> RouterBuilder<State, H> {
         RouterBuilder::new()
     }
+}
 
-    pub(crate) fn from_builder(builder: RouterBuilder<State, H>, state: &mut State) -> Self {
+impl<State: 'static, H: History> EguiRouter<State, H> {
+    pub(crate) fn from_table(
+        table: RouteTable<State>,
+        history_kind: H,
+        initial_path: Option<String>,
+        state: &mut State,
+    ) -> Self {
+        let default_route = table.inner.default_route.clone();
         let mut router = Self {
-            router: builder.router,
+            id: RouterId(ROUTER_ID.fetch_add(1, Ordering::SeqCst)),
+            table,
             history: Vec::new(),
-            history_kind: builder.history_kind.unwrap_or_default(),
+            history_kind,
             current_transition: None,
-            forward_transition: builder.forward_transition,
-            backward_transition: builder.backward_transition,
-            replace_transition: builder.replace_transition,
-            default_duration: builder.default_duration,
-            error_ui: builder.error_ui,
         };
 
-        if let Some((r, state_index)) = router
-            .history_kind
-            .active_route()
-            .or(builder.default_route.map(|d| (d, None)))
+        if let Some((r, state_index)) = initial_path
+            .map(|p| (p, None))
+            .or_else(|| router.history_kind.active_route())
+            .or(default_route.map(|d| (d, None)))
         {
             router
                 .navigate_impl(
                     state,
                     &r,
-                    TransitionConfig::none(),
+                    Some(TransitionConfig::none()),
                     state_index.unwrap_or(0),
                 )
                 .unwrap();
@@ -65,11 +71,61 @@ impl<State: 'static, H: History + Default> EguiRouter<State, H> {
         router
     }
 
+    /// The id of this router, as passed in its [`RouterEvent`]s
+    pub fn id(&self) -> RouterId {
+        self.id
+    }
+
+    /// Get the [`RouteTable`] of this router, e.g. to create another router for a new viewport
+    /// that shares the same routes.
+    pub fn route_table(&self) -> &RouteTable<State> {
+        &self.table
+    }
+
     /// Get the active route
     pub fn active_route(&self) -> Option<&str> {
         self.history.last().map(|r| r.path.as_str())
     }
 
+    /// Get the paths of all routes in the history stack, starting with the oldest
+    pub fn history_stack(&self) -> impl ExactSizeIterator<Item = &str> {
+        self.history.iter().map(|r| r.path.as_str())
+    }
+
+    /// Get the progress (0.0 to 1.0) of the currently running transition,
+    /// or `None` if no transition is running
+    pub fn transition_progress(&self) -> Option<f32> {
+        self.current_transition
+            .as_ref()
+            .map(|t| t.active_transition.progress())
+    }
+
+    /// Subscribe to [`RouterEvent`]s. Routers created from the same [`RouteTable`] share their events.
+    /// Use [`crate::RouterBuilder::subscribe`] to also receive the events of the initial navigation.
+    pub fn subscribe(&self) -> BroadcastReceiver<RouterEvent> {
+        self.table.subscribe()
+    }
+
+    fn emit(&self, event: RouterEvent) {
+        self.table.inner.events.send(event);
+    }
+
+    /// Resolve the transition between the routes with the patterns `from` and `to`,
+    /// falling back to `default` if there is no matching transition rule.
+    fn resolve_transition(
+        &self,
+        from: Option<&str>,
+        to: &str,
+        default: &TransitionConfig,
+    ) -> TransitionConfig {
+        self.table
+            .inner
+            .transition_rules
+            .resolve(from, to)
+            .unwrap_or(default)
+            .clone()
+    }
+
     fn parse_path(path: &str) -> (&str, BTreeMap<Cow<str>, Cow<str>>) {
         path.split_once('?')
             .map(|(path, q)| (path, form_urlencoded::parse(q.as_bytes()).collect()))
@@ -80,37 +136,64 @@ impl<State: 'static, H: History + Default> EguiRouter<State, H> {
         &mut self,
         state: &mut State,
         path: &str,
-        transition_config: TransitionConfig,
+        transition_config: Option<TransitionConfig>,
         new_state: u32,
     ) -> RouterResult {
         let (path, query) = Self::parse_path(path);
+        let from = self.history.last().map(|r| r.info.clone());
+        let from_pattern = self.history.last().map(|r| r.pattern.clone());
+        let started = Instant::now();
 
         let mut redirect = None;
-        let result = self.router.at_mut(path);
+        let result = self.table.inner.router.at(path);
 
         let result = match result {
             Ok(match_) => {
                 match match_.value {
-                    RouteKind::Route(handler) => {
-                        let route = handler(Request {
-                            state,
-                            params: match_.params,
-                            query,
+                    RouteKind::Route { pattern, handler } => {
+                        let transition_config = transition_config.clone().unwrap_or_else(|| {
+                            self.resolve_transition(
+                                from_pattern.as_deref(),
+                                pattern,
+                                &self.table.inner.forward_transition,
+                            )
                         });
+                        let info = RouteInfo::new(path, &match_.params, &query);
+                        let route = self.handle_request(
+                            handler,
+                            from.clone(),
+                            info.clone(),
+                            Request {
+                                state,
+                                params: match_.params,
+                                query,
+                            },
+                        );
                         self.history.push(RouteState {
                             path: path.to_string(),
+                            pattern: pattern.clone(),
+                            info,
+                            shown_at: Instant::now(),
                             route,
                             id: ID.fetch_add(1, Ordering::SeqCst),
                             state: new_state,
                         });
 
                         self.current_transition = Some(CurrentTransition {
-                            active_transition: ActiveTransition::forward(transition_config.clone())
-                                .with_default_duration(self.default_duration),
+                            active_transition: ActiveTransition::forward(transition_config)
+                                .with_default_duration(self.table.inner.default_duration),
                             leaving_route: None,
+                            from,
+                            started,
                         });
                     }
                     RouteKind::Redirect(r) => {
+                        self.emit(RouterEvent::Redirected {
+                            router: self.id,
+                            from: RouteInfo::new(path, &match_.params, &query),
+                            to: r.clone(),
+                            duration: started.elapsed(),
+                        });
                         redirect = Some(r.clone());
                     }
                 }
@@ -129,6 +212,19 @@ impl<State: 'static, H: History + Default> EguiRouter<State, H> {
         result
     }
 
+    fn push_impl(
+        &mut self,
+        state: &mut State,
+        path: &str,
+        transition_config: Option<TransitionConfig>,
+    ) -> RouterResult {
+        let current_state = self.history.last().map_or(0, |r| r.state);
+        let new_state = current_state + 1;
+        self.history_kind.push(path, new_state)?;
+        self.navigate_impl(state, path, transition_config, new_state)?;
+        Ok(())
+    }
+
     /// Navigate with a custom transition
     pub fn navigate_transition(
         &mut self,
@@ -136,26 +232,40 @@ impl<State: 'static, H: History + Default> EguiRouter<State, H> {
         path: impl Into<String>,
         transition_config: TransitionConfig,
     ) -> RouterResult {
-        let path = path.into();
-        let current_state = self.history.last().map_or(0, |r| r.state);
-        let new_state = current_state + 1;
-        self.history_kind.push(&path, new_state)?;
-        self.navigate_impl(state, &path, transition_config, new_state)?;
-        Ok(())
+        self.push_impl(state, &path.into(), Some(transition_config))
     }
 
-    /// Navigate with the default transition
+    /// Navigate with the transition configured for the routes (see [`crate::RouterBuilder::transition_rule`]),
+    /// or the default forward transition
     pub fn navigate(&mut self, state: &mut State, route: impl Into<String>) -> RouterResult {
-        self.navigate_transition(state, route, self.forward_transition.clone())
+        self.push_impl(state, &route.into(), None)
     }
 
-    fn back_impl(&mut self, transition_config: TransitionConfig) {
+    fn back_impl(&mut self, transition_config: Option<TransitionConfig>) {
         if self.history.len() > 1 {
             let leaving_route = self.history.pop();
+            let transition_config = transition_config.unwrap_or_else(|| {
+                self.resolve_transition(
+                    self.history.last().map(|r| r.pattern.as_str()),
+                    leaving_route.as_ref().map_or("", |r| r.pattern.as_str()),
+                    &self.table.inner.backward_transition,
+                )
+            });
+            let from = leaving_route.as_ref().map(|r| r.info.clone());
+            if let (Some(leaving), Some(active)) = (&leaving_route, self.history.last()) {
+                self.emit(RouterEvent::Back {
+                    router: self.id,
+                    from: leaving.info.clone(),
+                    to: active.info.clone(),
+                    duration: leaving.shown_at.elapsed(),
+                });
+            }
             self.current_transition = Some(CurrentTransition {
                 active_transition: ActiveTransition::backward(transition_config)
-                    .with_default_duration(self.default_duration),
+                    .with_default_duration(self.table.inner.default_duration),
                 leaving_route,
+                from,
+                started: Instant::now(),
             });
         }
     }
@@ -163,13 +273,16 @@ impl<State: 'static, H: History + Default> EguiRouter<State, H> {
     /// Go back with a custom transition
     pub fn back_transition(&mut self, transition_config: TransitionConfig) -> RouterResult {
         self.history_kind.back()?;
-        self.back_impl(transition_config);
+        self.back_impl(Some(transition_config));
         Ok(())
     }
 
-    /// Go back with the default transition
+    /// Go back with the transition configured for the routes (played in reverse),
+    /// or the default backward transition
     pub fn back(&mut self) -> RouterResult {
-        self.back_transition(self.backward_transition.clone())
+        self.history_kind.back()?;
+        self.back_impl(None);
+        Ok(())
     }
 
     /// Replace the current route with a custom transition
@@ -178,43 +291,84 @@ impl<State: 'static, H: History + Default> EguiRouter<State, H> {
         state: &mut State,
         path: impl Into<String>,
         transition_config: TransitionConfig,
+    ) -> RouterResult {
+        self.replace_impl(state, &path.into(), Some(transition_config))
+    }
+
+    /// Replace the current route with the transition configured for the routes,
+    /// or the default replace transition
+    pub fn replace(&mut self, state: &mut State, path: impl Into<String>) -> RouterResult {
+        self.replace_impl(state, &path.into(), None)
+    }
+
+    fn replace_impl(
+        &mut self,
+        state: &mut State,
+        path: &str,
+        transition_config: Option<TransitionConfig>,
     ) -> RouterResult {
         let mut redirect = None;
+        let from = self.history.last().map(|r| r.info.clone());
+        let from_pattern = self.history.last().map(|r| r.pattern.clone());
+        let started = Instant::now();
 
-        let path = path.into();
-        let result = self.router.at_mut(&path);
+        let result = self.table.inner.router.at(path);
 
         let current_state = self.history.last().map_or(0, |r| r.state);
         let new_state = current_state;
 
-        let (path, query) = Self::parse_path(&path);
+        let (path, query) = Self::parse_path(path);
 
         let result = match result {
             Ok(match_) => match match_.value {
-                RouteKind::Route(handler) => {
+                RouteKind::Route { pattern, handler } => {
+                    let transition_config = transition_config.clone().unwrap_or_else(|| {
+                        self.resolve_transition(
+                            from_pattern.as_deref(),
+                            pattern,
+                            &self.table.inner.replace_transition,
+                        )
+                    });
                     self.history_kind.replace(path, new_state)?;
                     let leaving_route = self.history.pop();
-                    let route = handler(Request {
-                        state,
-                        params: match_.params,
-                        query,
-                    });
+                    let info = RouteInfo::new(path, &match_.params, &query);
+                    let route = self.handle_request(
+                        handler,
+                        from.clone(),
+                        info.clone(),
+                        Request {
+                            state,
+                            params: match_.params,
+                            query,
+                        },
+                    );
                     self.history.push(RouteState {
                         path: path.to_string(),
+                        pattern: pattern.clone(),
+                        info,
+                        shown_at: Instant::now(),
                         route,
                         id: ID.fetch_add(1, Ordering::SeqCst),
                         state: new_state,
                     });
 
                     self.current_transition = Some(CurrentTransition {
-                        active_transition: ActiveTransition::forward(transition_config.clone())
-                            .with_default_duration(self.default_duration),
+                        active_transition: ActiveTransition::forward(transition_config)
+                            .with_default_duration(self.table.inner.default_duration),
                         leaving_route,
+                        from,
+                        started,
                     });
 
                     Ok(())
                 }
                 RouteKind::Redirect(r) => {
+                    self.emit(RouterEvent::Redirected {
+                        router: self.id,
+                        from: RouteInfo::new(path, &match_.params, &query),
+                        to: r.clone(),
+                        duration: started.elapsed(),
+                    });
                     redirect = Some(r.clone());
                     Ok(())
                 }
@@ -224,17 +378,12 @@ impl<State: 'static, H: History + Default> EguiRouter<State, H> {
 
         if let Some(redirect) = redirect {
             self.history_kind.replace(&redirect, new_state)?;
-            self.replace_transition(state, redirect, transition_config)?;
+            self.replace_impl(state, &redirect, transition_config)?;
         }
 
         result
     }
 
-    /// Replace the current route with the default transition
-    pub fn replace(&mut self, state: &mut State, path: impl Into<String>) -> RouterResult {
-        self.replace_transition(state, path, self.replace_transition.clone())
-    }
-
     /// Render the router
     pub fn ui(&mut self, ui: &mut Ui, state: &mut State) {
         for e in self.history_kind.update(ui.ctx()) {
@@ -254,14 +403,14 @@ impl<State: 'static, H: History + Default> EguiRouter<State, H> {
                     .retain(|r| r.state <= route_state || r.state == active_state);
 
                 if route_state < active_state {
-                    self.back_impl(self.backward_transition.clone());
+                    self.back_impl(None);
                 }
             } else {
-                self.navigate_impl(state, &path, self.forward_transition.clone(), state_index)
-                    .ok();
+                self.navigate_impl(state, &path, None, state_index).ok();
             }
         }
 
+        let error_ui = &self.table.inner.error_ui;
         if let Some((last, previous)) = self.history.split_last_mut() {
             let result = if let Some(transition) = &mut self.current_transition {
                 let leaving_route_state = transition.leaving_route.as_mut().or(previous.last_mut());
@@ -273,7 +422,7 @@ impl<State: 'static, H: History + Default> EguiRouter<State, H> {
                             route.ui(ui, state);
                         }
                         Err(err) => {
-                            (self.error_ui)(ui, state, err);
+                            error_ui(ui, state, err);
                         }
                     }),
                     leaving_route_state.map(|r| {
@@ -282,7 +431,7 @@ impl<State: 'static, H: History + Default> EguiRouter<State, H> {
                                 route.ui(ui, state);
                             }
                             Err(err) => {
-                                (self.error_ui)(ui, state, err);
+                                error_ui(ui, state, err);
                             }
                         })
                     }),
@@ -293,7 +442,7 @@ impl<State: 'static, H: History + Default> EguiRouter<State, H> {
                         route.ui(ui, state);
                     }
                     Err(err) => {
-                        (self.error_ui)(ui, state, err);
+                        error_ui(ui, state, err);
                     }
                 });
                 None
@@ -301,10 +450,49 @@ impl<State: 'static, H: History + Default> EguiRouter<State, H> {
 
             match result {
                 Some(ActiveTransitionResult::Done) => {
-                    self.current_transition = None;
+                    if let Some(transition) = self.current_transition.take() {
+                        self.table
+                            .inner
+                            .events
+                            .send(RouterEvent::TransitionCompleted {
+                                router: self.id,
+                                from: transition.from,
+                                to: last.info.clone(),
+                                duration: transition.started.elapsed(),
+                            });
+                    }
                 }
                 Some(ActiveTransitionResult::Continue) | None => {}
             }
         }
     }
+
+    fn handle_request(
+        &self,
+        handler: &Handler<State>,
+        from: Option<RouteInfo>,
+        to: RouteInfo,
+        request: Request<State>,
+    ) -> HandlerResult<Box<dyn Route<State>>> {
+        self.emit(RouterEvent::NavigationStarted {
+            router: self.id,
+            from: from.clone(),
+            to: to.clone(),
+        });
+
+        let started = Instant::now();
+        let result = handler(request);
+
+        if let Err(err) = &result {
+            self.emit(RouterEvent::HandlerFailed {
+                router: self.id,
+                from,
+                to,
+                error: err.to_string(),
+                duration: started.elapsed(),
+            });
+        }
+
+        result
+    }
 }
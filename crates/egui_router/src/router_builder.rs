@@ -1,7 +1,13 @@
+use crate::event::RouterEvent;
 use crate::handler::MakeHandler;
 use crate::history::History;
 use crate::route_kind::RouteKind;
+use crate::route_table::{RouteTable, RouteTableInner};
+use crate::transition_rules::TransitionRules;
 use crate::{EguiRouter, TransitionConfig};
+use egui_inbox::broadcast::{Broadcast, BroadcastReceiver};
+use std::cell::RefCell;
+use std::rc::Rc;
 use std::sync::Arc;
 
 pub(crate) type ErrorUi<State> =
@@ -10,12 +16,14 @@ pub(crate) type LoadingUi<State> = Arc<Box<dyn Fn(&mut egui::Ui, &State) + Send
 
 /// Builder to create a [`EguiRouter`]
 pub struct RouterBuilder<State, H> {
-    pub(crate) router: matchit::Router<RouteKind<State>>,
+    /// The routes with their pattern, inserted into the [`matchit::Router`] in [`Self::build_table`]
+    pub(crate) routes: Vec<(String, RouteKind<State>)>,
     pub(crate) default_route: Option<String>,
 
     pub(crate) forward_transition: TransitionConfig,
     pub(crate) backward_transition: TransitionConfig,
     pub(crate) replace_transition: TransitionConfig,
+    pub(crate) transition_rules: TransitionRules,
 
     pub(crate) default_duration: Option<f32>,
 
@@ -23,6 +31,8 @@ pub struct RouterBuilder<State, H> {
 
     pub(crate) error_ui: ErrorUi<State>,
     pub(crate) loading_ui: LoadingUi<State>,
+
+    pub(crate) events: Broadcast<RouterEvent>,
 }
 
 impl<State: 'static, H: History + Default> Default for RouterBuilder<State, H> {
@@ -35,11 +45,12 @@ impl<State: 'static, H: History + Default> RouterBuilder<State, H> {
     /// Create a new router builder
     pub fn new() -> Self {
         Self {
-            router: matchit::Router::new(),
+            routes: Vec::new(),
             default_route: None,
             forward_transition: TransitionConfig::default(),
             backward_transition: TransitionConfig::default(),
             replace_transition: TransitionConfig::fade(),
+            transition_rules: TransitionRules::default(),
             default_duration: None,
             history_kind: None,
             error_ui: Arc::new(Box::new(|ui, _, err| {
@@ -48,6 +59,7 @@ impl<State: 'static, H: History + Default> RouterBuilder<State, H> {
             loading_ui: Arc::new(Box::new(|ui, _| {
                 ui.spinner();
             })),
+            events: Broadcast::new(),
         }
     }
 
@@ -76,6 +88,46 @@ impl<State: 'static, H: History + Default> RouterBuilder<State, H> {
         self
     }
 
+    /// Set the transition that is used when navigating to `route`.
+    /// `route` is the pattern as passed to [`RouterBuilder::route`].
+    /// When going back from the route, the transition is played in reverse.
+    pub fn enter_transition(mut self, route: &str, transition: TransitionConfig) -> Self {
+        self.transition_rules.set_enter(route, transition);
+        self
+    }
+
+    /// Set the transition that is used when navigating away from `route`,
+    /// unless the route we navigate to has an enter transition.
+    /// `route` is the pattern as passed to [`RouterBuilder::route`].
+    pub fn exit_transition(mut self, route: &str, transition: TransitionConfig) -> Self {
+        self.transition_rules.set_exit(route, transition);
+        self
+    }
+
+    /// Set the transition that is used when navigating from any route in `from` to any route in `to`.
+    /// Routes are the patterns as passed to [`RouterBuilder::route`], `"*"` matches any route.
+    /// Rules are checked in the order they were added and take precedence over
+    /// [`RouterBuilder::enter_transition`] and [`RouterBuilder::exit_transition`].
+    /// When going back, the transition of the reverse navigation is played in reverse.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use egui_router::{EguiRouter, TransitionConfig};
+    /// let tabs = ["/", "/search", "/settings"];
+    /// let builder = EguiRouter::<()>::builder()
+    ///     .transition_rule(&tabs, &tabs, TransitionConfig::fade())
+    ///     .transition_rule(&["/search"], &["/post/{id}"], TransitionConfig::slide());
+    /// ```
+    pub fn transition_rule(
+        mut self,
+        from: &[&str],
+        to: &[&str],
+        transition: TransitionConfig,
+    ) -> Self {
+        self.transition_rules.add_pair(from, to, transition);
+        self
+    }
+
     /// Set the default duration for transitions
     pub fn default_duration(mut self, duration: f32) -> Self {
         self.default_duration = Some(duration);
@@ -111,6 +163,12 @@ impl<State: 'static, H: History + Default> RouterBuilder<State, H> {
         self
     }
 
+    /// Subscribe to the [`RouterEvent`]s of the router(s) built from this builder.
+    /// Subscribing here (instead of via [`EguiRouter::subscribe`]) also captures the initial navigation.
+    pub fn subscribe(&self) -> BroadcastReceiver<RouterEvent> {
+        self.events.subscribe()
+    }
+
     /// Add a route. Check the [matchit] documentation for information about the route syntax.
     /// The handler will be called with [`crate::Request`] and should return a [Route].
     ///
@@ -139,14 +197,17 @@ impl<State: 'static, H: History + Default> RouterBuilder<State, H> {
     pub fn route<HandlerArgs, Han: MakeHandler<State, HandlerArgs> + 'static>(
         mut self,
         route: &str,
-        mut handler: Han,
+        handler: Han,
     ) -> Self {
-        self.router
-            .insert(
-                route,
-                RouteKind::Route(Box::new(move |req| handler.handle(req))),
-            )
-            .unwrap();
+        // The handlers are shared by all routers of a RouteTable, so they are called via &self
+        let handler = RefCell::new(handler);
+        self.routes.push((
+            route.to_string(),
+            RouteKind::Route {
+                pattern: route.to_string(),
+                handler: Box::new(move |req| handler.borrow_mut().handle(req)),
+            },
+        ));
         self
     }
 
@@ -185,10 +246,11 @@ impl<State: 'static, H: History + Default> RouterBuilder<State, H> {
     {
         let loading_ui = self.loading_ui.clone();
         let error_ui = self.error_ui.clone();
-        self.router
-            .insert(
-                route,
-                RouteKind::Route(Box::new(move |req| {
+        self.routes.push((
+            route.to_string(),
+            RouteKind::Route {
+                pattern: route.to_string(),
+                handler: Box::new(move |req| {
                     let loading_ui = loading_ui.clone();
                     let error_ui = error_ui.clone();
 
@@ -220,22 +282,55 @@ impl<State: 'static, H: History + Default> RouterBuilder<State, H> {
                     };
 
                     Ok(Box::new(route))
-                })),
-            )
-            .unwrap();
+                }),
+            },
+        ));
         self
     }
 
     /// Add a redirect route. Whenever this route matches, it'll redirect to the route you specified.
     pub fn route_redirect(mut self, route: &str, redirect: impl Into<String>) -> Self {
-        self.router
-            .insert(route, RouteKind::Redirect(redirect.into()))
-            .unwrap();
+        self.routes
+            .push((route.to_string(), RouteKind::Redirect(redirect.into())));
         self
     }
 
     /// Build the router
-    pub fn build(self, state: &mut State) -> EguiRouter<State, H> {
-        EguiRouter::from_builder(self, state)
+    ///
+    /// # Panics
+    /// If a route is invalid or conflicts with another route, see [`Self::build_table`]
+    pub fn build(mut self, state: &mut State) -> EguiRouter<State, H> {
+        let history = self.history_kind.take().unwrap_or_default();
+        self.build_table().router_with_history(state, history)
+    }
+
+    /// Build a [`RouteTable`] that can be shared between multiple routers, e.g. one per viewport.
+    /// The history set via [`RouterBuilder::history`] is ignored, pass it to
+    /// [`RouteTable::router_with_history`] instead.
+    ///
+    /// # Panics
+    /// If a route is invalid (e.g. `/{id` with an unclosed parameter) or conflicts with another
+    /// route (e.g. `/{id}` and `/{name}`), see [`matchit::InsertError`]. The routes are only
+    /// checked here, not when they are added.
+    pub fn build_table(self) -> RouteTable<State> {
+        let mut router = matchit::Router::new();
+        for (pattern, route) in self.routes {
+            if let Err(err) = router.insert(pattern, route) {
+                panic!("Invalid route: {err}");
+            }
+        }
+        RouteTable {
+            inner: Rc::new(RouteTableInner {
+                router,
+                default_route: self.default_route,
+                forward_transition: self.forward_transition,
+                backward_transition: self.backward_transition,
+                replace_transition: self.replace_transition,
+                transition_rules: self.transition_rules,
+                default_duration: self.default_duration,
+                error_ui: self.error_ui,
+                events: self.events,
+            }),
+        }
     }
 }
@@ -0,0 +1,420 @@
+//! Utilities to test routers without running an eframe app.
+//!
+//! [`TestRouter`] drives an [`EguiRouter`] with a headless [`egui::Context`] and a [`TestHistory`],
+//! which behaves like the browser history (including popstate events).
+//!
+//! # Example
+//! ```rust
+//! # use egui_router::testing::TestRouter;
+//! # use egui_router::EguiRouter;
+//! let builder = EguiRouter::<()>::builder()
+//!     .route("/", || |ui: &mut egui::Ui, _: &mut ()| {
+//!         ui.label("Home");
+//!     })
+//!     .route("/post/{id}", || |ui: &mut egui::Ui, _: &mut ()| {
+//!         ui.label("Post");
+//!     })
+//!     .route_redirect("/old", "/post/1")
+//!     .default_path("/");
+//!
+//! let mut router = TestRouter::new(builder, ());
+//! router.navigate("/old").unwrap();
+//! router.assert_active_route("/post/1");
+//! router.assert_history(&["/", "/post/1"]);
+//!
+//! router.step();
+//! router.assert_rendered(&["/post/1", "/"]);
+//!
+//! router.settle();
+//! router.assert_no_transition();
+//! router.step();
+//! router.assert_rendered(&["/post/1"]);
+//!
+//! router.history().simulate_back();
+//! router.settle();
+//! router.assert_history(&["/"]);
+//! ```
+
+use crate::handler::Handler;
+use crate::history::{History, HistoryEvent, HistoryResult};
+use crate::route_kind::RouteKind;
+use crate::{EguiRouter, Route, RouterBuilder, RouterResult};
+use egui::{CentralPanel, Context, Pos2, RawInput, Rect, Ui, Vec2};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+#[derive(Debug, Default)]
+struct TestHistoryInner {
+    entries: Vec<(String, u32)>,
+    index: usize,
+    events: Vec<HistoryEvent>,
+}
+
+/// A fake [`History`] that behaves like the browser history.
+///
+/// It is a cheap handle, clones share the same entries, so you can keep a clone to inspect it
+/// or to simulate the user pressing the browser's back / forward buttons.
+#[derive(Debug, Clone)]
+pub struct TestHistory {
+    inner: Rc<RefCell<TestHistoryInner>>,
+}
+
+impl TestHistory {
+    /// Create a new history with a single entry
+    pub fn new(initial_path: impl Into<String>) -> Self {
+        Self {
+            inner: Rc::new(RefCell::new(TestHistoryInner {
+                entries: vec![(initial_path.into(), 0)],
+                ..TestHistoryInner::default()
+            })),
+        }
+    }
+
+    /// Get all entries (path and state) of the history
+    pub fn entries(&self) -> Vec<(String, u32)> {
+        self.inner.borrow().entries.clone()
+    }
+
+    /// Get the current entry (path and state) of the history
+    pub fn current(&self) -> Option<(String, u32)> {
+        let inner = self.inner.borrow();
+        inner.entries.get(inner.index).cloned()
+    }
+
+    /// Simulate the user pressing the back button, queueing a popstate event
+    pub fn simulate_back(&self) {
+        self.go(-1);
+    }
+
+    /// Simulate the user pressing the forward button, queueing a popstate event
+    pub fn simulate_forward(&self) {
+        self.go(1);
+    }
+
+    /// Simulate a navigation that the router didn't initiate (e.g. the user editing the url),
+    /// queueing a popstate event with a new state
+    pub fn simulate_navigation(&self, path: impl Into<String>) {
+        let path = path.into();
+        let mut inner = self.inner.borrow_mut();
+        let state = inner.entries.get(inner.index).map_or(0, |(_, s)| s + 1);
+        inner.push(path.clone(), state);
+        inner.events.push(HistoryEvent {
+            location: path,
+            state: Some(state),
+        });
+    }
+
+    fn go(&self, delta: isize) {
+        let mut inner = self.inner.borrow_mut();
+        let Some(index) = inner.index.checked_add_signed(delta) else {
+            return;
+        };
+        if let Some((location, state)) = inner.entries.get(index).cloned() {
+            inner.index = index;
+            inner.events.push(HistoryEvent {
+                location,
+                state: Some(state),
+            });
+        }
+    }
+}
+
+impl TestHistoryInner {
+    fn push(&mut self, path: String, state: u32) {
+        self.entries.truncate(self.index + 1);
+        self.entries.push((path, state));
+        self.index = self.entries.len() - 1;
+    }
+}
+
+impl Default for TestHistory {
+    fn default() -> Self {
+        Self::new("/")
+    }
+}
+
+impl History for TestHistory {
+    fn update(&mut self, _ctx: &Context) -> impl Iterator<Item = HistoryEvent> + 'static {
+        std::mem::take(&mut self.inner.borrow_mut().events).into_iter()
+    }
+
+    fn active_route(&self) -> Option<(String, Option<u32>)> {
+        self.current().map(|(path, state)| (path, Some(state)))
+    }
+
+    fn push(&mut self, url: &str, state: u32) -> HistoryResult {
+        self.inner.borrow_mut().push(url.to_string(), state);
+        Ok(())
+    }
+
+    fn replace(&mut self, url: &str, state: u32) -> HistoryResult {
+        let mut inner = self.inner.borrow_mut();
+        let index = inner.index;
+        if let Some(entry) = inner.entries.get_mut(index) {
+            *entry = (url.to_string(), state);
+        }
+        Ok(())
+    }
+
+    fn back(&mut self) -> HistoryResult {
+        self.go(-1);
+        Ok(())
+    }
+
+    fn forward(&mut self) -> HistoryResult {
+        self.go(1);
+        Ok(())
+    }
+}
+
+/// The paths of the routes rendered in the current frame
+type Rendered = Rc<RefCell<Vec<String>>>;
+
+/// Wraps a route to record its path whenever it is rendered
+struct RecordingRoute<State> {
+    path: String,
+    route: Box<dyn Route<State>>,
+    rendered: Rendered,
+}
+
+impl<State> Route<State> for RecordingRoute<State> {
+    fn ui(&mut self, ui: &mut Ui, state: &mut State) {
+        self.rendered.borrow_mut().push(self.path.clone());
+        self.route.ui(ui, state);
+    }
+}
+
+/// Wrap the handler, so the routes it creates record when they are rendered
+fn record_rendered<State: 'static>(
+    pattern: String,
+    handler: Handler<State>,
+    rendered: Rendered,
+) -> Handler<State> {
+    Box::new(move |request| {
+        // Fill in the params of the pattern, to get the path that was navigated to
+        let path = request
+            .params
+            .iter()
+            .fold(pattern.clone(), |path, (key, value)| {
+                path.replace(&format!("{{{key}}}"), value)
+                    .replace(&format!("{{*{key}}}"), value)
+            });
+        let route = handler(request)?;
+        Ok(Box::new(RecordingRoute {
+            path,
+            route,
+            rendered: rendered.clone(),
+        }))
+    })
+}
+
+/// A headless test harness for an [`EguiRouter`]. See the [module docs](self) for an example.
+pub struct TestRouter<State> {
+    router: EguiRouter<State, TestHistory>,
+    history: TestHistory,
+    rendered: Rendered,
+    state: State,
+    ctx: Context,
+    time: f64,
+    frame_time: f32,
+    screen_size: Vec2,
+}
+
+impl<State: 'static> TestRouter<State> {
+    /// Create a new test router from the routes of `builder`,
+    /// starting at its default path (or `/`)
+    pub fn new<H: History + Default>(builder: RouterBuilder<State, H>, state: State) -> Self {
+        let path = builder
+            .default_route
+            .clone()
+            .unwrap_or_else(|| "/".to_string());
+        Self::with_history(builder, TestHistory::new(path), state)
+    }
+
+    /// Create a new test router from the routes of `builder` with a custom [`TestHistory`].
+    /// The history of the builder is ignored.
+    pub fn with_history<H: History + Default>(
+        mut builder: RouterBuilder<State, H>,
+        history: TestHistory,
+        mut state: State,
+    ) -> Self {
+        let rendered = Rendered::default();
+        builder.routes = std::mem::take(&mut builder.routes)
+            .into_iter()
+            .map(|(path, route)| {
+                let route = match route {
+                    RouteKind::Route { pattern, handler } => RouteKind::Route {
+                        handler: record_rendered(pattern.clone(), handler, rendered.clone()),
+                        pattern,
+                    },
+                    RouteKind::Redirect(to) => RouteKind::Redirect(to),
+                };
+                (path, route)
+            })
+            .collect();
+
+        let router = builder
+            .build_table()
+            .router_with_history(&mut state, history.clone());
+        Self {
+            router,
+            history,
+            rendered,
+            state,
+            ctx: Context::default(),
+            time: 0.0,
+            frame_time: 1.0 / 60.0,
+            screen_size: Vec2::new(800.0, 600.0),
+        }
+    }
+
+    /// Set the simulated time between frames (default is 1/60 s)
+    pub fn with_frame_time(mut self, frame_time: f32) -> Self {
+        self.frame_time = frame_time;
+        self
+    }
+
+    /// Set the simulated screen size (default is 800x600)
+    pub fn with_screen_size(mut self, size: Vec2) -> Self {
+        self.screen_size = size;
+        self
+    }
+
+    /// The router under test
+    pub fn router(&self) -> &EguiRouter<State, TestHistory> {
+        &self.router
+    }
+
+    /// The router under test
+    pub fn router_mut(&mut self) -> &mut EguiRouter<State, TestHistory> {
+        &mut self.router
+    }
+
+    /// The paths of the routes that were rendered in the last frame, in the order they were
+    /// rendered. Routes whose handler failed (showing the error ui) are not included.
+    pub fn rendered_routes(&self) -> Vec<String> {
+        self.rendered.borrow().clone()
+    }
+
+    /// The fake history. Use it to simulate back / forward button presses.
+    pub fn history(&self) -> &TestHistory {
+        &self.history
+    }
+
+    /// The state that is passed to the routes
+    pub fn state(&self) -> &State {
+        &self.state
+    }
+
+    /// The state that is passed to the routes
+    pub fn state_mut(&mut self) -> &mut State {
+        &mut self.state
+    }
+
+    /// The headless egui context
+    pub fn ctx(&self) -> &Context {
+        &self.ctx
+    }
+
+    /// Navigate with the default transition
+    pub fn navigate(&mut self, path: impl Into<String>) -> RouterResult {
+        self.router.navigate(&mut self.state, path)
+    }
+
+    /// Replace the current route with the default transition
+    pub fn replace(&mut self, path: impl Into<String>) -> RouterResult {
+        self.router.replace(&mut self.state, path)
+    }
+
+    /// Go back with the default transition
+    pub fn back(&mut self) -> RouterResult {
+        self.router.back()
+    }
+
+    /// Run a single frame
+    pub fn step(&mut self) {
+        self.time += f64::from(self.frame_time);
+        let input = RawInput {
+            screen_rect: Some(Rect::from_min_size(Pos2::ZERO, self.screen_size)),
+            time: Some(self.time),
+            predicted_dt: self.frame_time,
+            ..RawInput::default()
+        };
+        let router = &mut self.router;
+        let state = &mut self.state;
+        let rendered = &self.rendered;
+        let _ = self.ctx.run(input, |ctx| {
+            // egui might run multiple passes, only keep the routes of the last one
+            rendered.borrow_mut().clear();
+            CentralPanel::default().show(ctx, |ui| {
+                router.ui(ui, state);
+            });
+        });
+    }
+
+    /// Run `frames` frames
+    pub fn step_frames(&mut self, frames: usize) {
+        for _ in 0..frames {
+            self.step();
+        }
+    }
+
+    /// Run frames until the current transition is done
+    ///
+    /// # Panics
+    /// If the transition doesn't finish within 1000 frames
+    #[track_caller]
+    pub fn settle(&mut self) {
+        for _ in 0..1000 {
+            self.step();
+            if self.router.transition_progress().is_none() {
+                return;
+            }
+        }
+        panic!("Transition didn't finish within 1000 frames");
+    }
+
+    /// Assert that `path` is the active route
+    #[track_caller]
+    pub fn assert_active_route(&self, path: &str) {
+        assert_eq!(self.router.active_route(), Some(path), "active route");
+    }
+
+    /// Assert that the router's history stack contains exactly `paths` (oldest first)
+    #[track_caller]
+    pub fn assert_history(&self, paths: &[&str]) {
+        let stack: Vec<_> = self.router.history_stack().collect();
+        assert_eq!(stack, paths, "history stack");
+    }
+
+    /// Assert that the last frame rendered exactly `paths`, in any order.
+    /// During a transition this includes the leaving route.
+    #[track_caller]
+    pub fn assert_rendered(&self, paths: &[&str]) {
+        let mut rendered = self.rendered_routes();
+        rendered.sort();
+        let mut expected = paths.to_vec();
+        expected.sort_unstable();
+        assert_eq!(rendered, expected, "rendered routes");
+    }
+
+    /// Assert that a transition is running and its progress is within `range`
+    #[track_caller]
+    pub fn assert_transition_progress(&self, range: std::ops::RangeInclusive<f32>) {
+        let progress = self.router.transition_progress();
+        assert!(
+            progress.is_some_and(|p| range.contains(&p)),
+            "expected transition progress in {range:?}, got {progress:?}"
+        );
+    }
+
+    /// Assert that no transition is running
+    #[track_caller]
+    pub fn assert_no_transition(&self) {
+        assert_eq!(
+            self.router.transition_progress(),
+            None,
+            "transition progress"
+        );
+    }
+}
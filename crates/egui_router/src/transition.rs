@@ -189,6 +189,10 @@ impl ActiveTransition {
         }
     }
 
+    pub fn progress(&self) -> f32 {
+        self.progress.min(1.0)
+    }
+
     pub fn with_default_duration(mut self, duration: Option<f32>) -> Self {
         if self.duration.is_none() {
             self.duration = duration;
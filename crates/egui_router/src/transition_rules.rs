@@ -0,0 +1,114 @@
+use crate::TransitionConfig;
+use std::collections::HashMap;
+
+struct PairRule {
+    from: Vec<String>,
+    to: Vec<String>,
+    transition: TransitionConfig,
+}
+
+/// Transition overrides per route and per route pair.
+/// Routes are identified by their pattern, as passed to [`crate::RouterBuilder::route`].
+#[derive(Default)]
+pub(crate) struct TransitionRules {
+    enter: HashMap<String, TransitionConfig>,
+    exit: HashMap<String, TransitionConfig>,
+    pairs: Vec<PairRule>,
+}
+
+impl TransitionRules {
+    pub fn set_enter(&mut self, pattern: &str, transition: TransitionConfig) {
+        self.enter.insert(pattern.to_string(), transition);
+    }
+
+    pub fn set_exit(&mut self, pattern: &str, transition: TransitionConfig) {
+        self.exit.insert(pattern.to_string(), transition);
+    }
+
+    pub fn add_pair(&mut self, from: &[&str], to: &[&str], transition: TransitionConfig) {
+        self.pairs.push(PairRule {
+            from: from.iter().map(ToString::to_string).collect(),
+            to: to.iter().map(ToString::to_string).collect(),
+            transition,
+        });
+    }
+
+    /// Resolve the transition for navigating from the route `from` to the route `to`.
+    /// The first matching pair rule wins, then the enter transition of `to`, then the exit
+    /// transition of `from`.
+    /// For back navigations, pass the routes in forward order (the transition is played in reverse).
+    pub fn resolve(&self, from: Option<&str>, to: &str) -> Option<&TransitionConfig> {
+        self.pairs
+            .iter()
+            .find(|rule| matches(&rule.from, from) && matches(&rule.to, Some(to)))
+            .map(|rule| &rule.transition)
+            .or_else(|| self.enter.get(to))
+            .or_else(|| from.and_then(|from| self.exit.get(from)))
+    }
+}
+
+fn matches(patterns: &[String], pattern: Option<&str>) -> bool {
+    patterns
+        .iter()
+        .any(|p| p == "*" || Some(p.as_str()) == pattern)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TransitionRules;
+    use crate::TransitionConfig;
+
+    /// The transitions are told apart by their duration
+    fn resolved(rules: &TransitionRules, from: Option<&str>, to: &str) -> Option<f32> {
+        rules.resolve(from, to).and_then(|t| t.duration)
+    }
+
+    fn transition(duration: f32) -> TransitionConfig {
+        TransitionConfig::fade().with_duration(duration)
+    }
+
+    #[test]
+    fn pair_rule_wins_over_enter_and_exit() {
+        let mut rules = TransitionRules::default();
+        rules.set_enter("/b", transition(2.0));
+        rules.set_exit("/a", transition(3.0));
+        rules.add_pair(&["/a"], &["/b"], transition(1.0));
+
+        assert_eq!(resolved(&rules, Some("/a"), "/b"), Some(1.0));
+    }
+
+    #[test]
+    fn first_matching_pair_rule_wins() {
+        let mut rules = TransitionRules::default();
+        rules.add_pair(&["/a"], &["*"], transition(1.0));
+        rules.add_pair(&["*"], &["/b"], transition(2.0));
+
+        assert_eq!(resolved(&rules, Some("/a"), "/b"), Some(1.0));
+        assert_eq!(resolved(&rules, Some("/c"), "/b"), Some(2.0));
+        assert_eq!(resolved(&rules, None, "/b"), Some(2.0));
+        // The initial navigation has no route to match `/a`
+        assert_eq!(resolved(&rules, None, "/c"), None);
+    }
+
+    #[test]
+    fn enter_wins_over_exit() {
+        let mut rules = TransitionRules::default();
+        rules.set_enter("/b", transition(2.0));
+        rules.set_exit("/a", transition(3.0));
+
+        assert_eq!(resolved(&rules, Some("/a"), "/b"), Some(2.0));
+        assert_eq!(resolved(&rules, Some("/a"), "/c"), Some(3.0));
+        assert_eq!(resolved(&rules, None, "/c"), None);
+    }
+
+    #[test]
+    fn no_rule_matches() {
+        let mut rules = TransitionRules::default();
+        rules.add_pair(&["/a"], &["/b"], transition(1.0));
+
+        assert_eq!(resolved(&rules, Some("/b"), "/a"), None);
+        assert!(TransitionRules::default()
+            .resolve(Some("/a"), "/b")
+            .is_none());
+    }
+}
@@ -0,0 +1,85 @@
+//! Check the [`RouterEvent`]s emitted while navigating.
+
+use egui::Ui;
+use egui_router::history::DefaultHistory;
+use egui_router::testing::{TestHistory, TestRouter};
+use egui_router::{EguiRouter, RouterBuilder, RouterEvent, RouterId};
+
+fn builder() -> RouterBuilder<(), DefaultHistory> {
+    EguiRouter::<()>::builder()
+        .route("/", || {
+            |ui: &mut Ui, (): &mut ()| {
+                ui.label("Home");
+            }
+        })
+        .route("/post/{id}", || {
+            |ui: &mut Ui, (): &mut ()| {
+                ui.label("Post");
+            }
+        })
+        .route_redirect("/old/{id}", "/post/1")
+        .default_path("/")
+}
+
+/// The id of the router that emitted the event
+fn router_id(event: &RouterEvent) -> RouterId {
+    match event {
+        RouterEvent::NavigationStarted { router, .. }
+        | RouterEvent::Redirected { router, .. }
+        | RouterEvent::HandlerFailed { router, .. }
+        | RouterEvent::TransitionCompleted { router, .. }
+        | RouterEvent::Back { router, .. } => *router,
+    }
+}
+
+#[test]
+fn events_carry_the_router_id() {
+    let mut first = TestRouter::new(builder(), ());
+    let table = first.router().route_table().clone();
+    let mut second: EguiRouter<(), TestHistory> = table.router(&mut ());
+    assert_ne!(first.router().id(), second.id());
+
+    let events = table.subscribe();
+    first.navigate("/post/1").unwrap();
+    second.navigate(&mut (), "/post/2").unwrap();
+
+    let ids: Vec<_> = events.read_without_ctx().map(|e| router_id(&e)).collect();
+    assert_eq!(ids, [first.router().id(), second.id()]);
+}
+
+#[test]
+fn redirect_and_back_events_carry_route_info() {
+    let mut router = TestRouter::new(builder(), ());
+    let events = router.router().subscribe();
+
+    router.navigate("/old/7?ref=mail").unwrap();
+    router.settle();
+    router.back().unwrap();
+    router.settle();
+
+    let events: Vec<_> = events.read_without_ctx().collect();
+    let RouterEvent::Redirected { from, to, .. } = &events[0] else {
+        panic!("expected a redirect, got {:?}", events[0]);
+    };
+    assert_eq!(from.path, "/old/7");
+    assert_eq!(from.params["id"], "7");
+    assert_eq!(from.query["ref"], "mail");
+    assert_eq!(to, "/post/1");
+
+    let RouterEvent::NavigationStarted { from, to, .. } = &events[1] else {
+        panic!("expected a navigation, got {:?}", events[1]);
+    };
+    assert_eq!(from.as_ref().map(|f| f.path.as_str()), Some("/"));
+    assert_eq!(to.params["id"], "1");
+
+    let RouterEvent::TransitionCompleted { to, .. } = &events[2] else {
+        panic!("expected a completed transition, got {:?}", events[2]);
+    };
+    assert_eq!(to.path, "/post/1");
+
+    let RouterEvent::Back { from, to, .. } = &events[3] else {
+        panic!("expected a back event, got {:?}", events[3]);
+    };
+    assert_eq!(from.params["id"], "1");
+    assert_eq!(to.path, "/");
+}
@@ -0,0 +1,149 @@
+//! Check the [`TestRouter`] harness: stepping frames, settling transitions and the asserts.
+
+use egui::Ui;
+use egui_router::history::DefaultHistory;
+use egui_router::testing::{TestHistory, TestRouter};
+use egui_router::{EguiRouter, HandlerError, RouterBuilder};
+
+/// Routes with a transition of one second
+fn builder() -> RouterBuilder<(), DefaultHistory> {
+    EguiRouter::<()>::builder()
+        .route("/", || {
+            |ui: &mut Ui, (): &mut ()| {
+                ui.label("Home");
+            }
+        })
+        .route("/post/{id}", || {
+            |ui: &mut Ui, (): &mut ()| {
+                ui.label("Post");
+            }
+        })
+        .route("/files/{*path}", || {
+            |ui: &mut Ui, (): &mut ()| {
+                ui.label("File");
+            }
+        })
+        .route("/broken", || {
+            Err::<fn(&mut Ui, &mut ()), _>(HandlerError::Message("broken".to_string()))
+        })
+        .default_path("/")
+        .default_duration(1.0)
+}
+
+/// A router whose frames take a quarter of the transition, after the initial transition
+fn router() -> TestRouter<()> {
+    let mut router = TestRouter::new(builder(), ()).with_frame_time(0.25);
+    router.settle();
+    router
+}
+
+#[test]
+fn step_renders_the_active_route() {
+    let mut router = router();
+    router.step();
+    router.assert_active_route("/");
+    router.assert_rendered(&["/"]);
+    router.assert_no_transition();
+}
+
+#[test]
+fn step_advances_the_transition() {
+    let mut router = router();
+    router.navigate("/post/1").unwrap();
+
+    router.step();
+    router.assert_transition_progress(0.2..=0.3);
+    router.assert_rendered(&["/post/1", "/"]);
+
+    router.step_frames(2);
+    router.assert_transition_progress(0.7..=0.8);
+}
+
+#[test]
+fn settle_finishes_the_transition() {
+    let mut router = router();
+    router.navigate("/post/1").unwrap();
+    router.settle();
+    router.assert_no_transition();
+
+    router.step();
+    router.assert_rendered(&["/post/1"]);
+}
+
+#[test]
+fn rendered_routes_have_their_params_filled_in() {
+    let mut router = router();
+    router.navigate("/files/a/b.txt").unwrap();
+    router.settle();
+    router.step();
+    assert_eq!(router.rendered_routes(), ["/files/a/b.txt"]);
+}
+
+#[test]
+fn failed_routes_are_not_rendered() {
+    let mut router = router();
+    router.navigate("/broken").unwrap();
+    router.settle();
+    router.step();
+    router.assert_active_route("/broken");
+    router.assert_rendered(&[]);
+}
+
+#[test]
+fn history_follows_back_and_forward() {
+    let history = TestHistory::new("/");
+    let mut router = TestRouter::with_history(builder(), history.clone(), ());
+    router.navigate("/post/1").unwrap();
+    router.navigate("/post/2").unwrap();
+    router.assert_history(&["/", "/post/1", "/post/2"]);
+
+    router.back().unwrap();
+    router.settle();
+    router.assert_history(&["/", "/post/1"]);
+    assert_eq!(history.current(), Some(("/post/1".to_string(), 1)));
+
+    history.simulate_back();
+    router.settle();
+    router.assert_active_route("/");
+
+    history.simulate_navigation("/post/3");
+    router.settle();
+    router.assert_history(&["/", "/post/3"]);
+}
+
+#[test]
+#[should_panic(expected = "active route")]
+fn assert_active_route_fails() {
+    router().assert_active_route("/post/1");
+}
+
+#[test]
+#[should_panic(expected = "history stack")]
+fn assert_history_fails() {
+    router().assert_history(&["/", "/post/1"]);
+}
+
+#[test]
+#[should_panic(expected = "rendered routes")]
+fn assert_rendered_fails() {
+    let mut router = router();
+    router.step();
+    router.assert_rendered(&["/post/1"]);
+}
+
+#[test]
+#[should_panic(expected = "expected transition progress")]
+fn assert_transition_progress_fails() {
+    let mut router = router();
+    router.step();
+    router.assert_transition_progress(0.0..=1.0);
+}
+
+#[test]
+#[should_panic(expected = "transition progress")]
+fn assert_no_transition_fails() {
+    let mut router = router();
+    router.navigate("/post/1").unwrap();
+    router.step();
+    router.assert_no_transition();
+}
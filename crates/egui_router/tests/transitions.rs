@@ -0,0 +1,106 @@
+//! Check which transition the router picks for navigate, back and replace.
+//! The transitions are told apart by their duration: with frames of 0.25 s, the progress after
+//! the first frame is `0.25 / duration`.
+
+use egui::Ui;
+use egui_router::history::DefaultHistory;
+use egui_router::testing::TestRouter;
+use egui_router::{EguiRouter, RouterBuilder, TransitionConfig};
+
+const FRAME_TIME: f32 = 0.25;
+
+fn transition(duration: f32) -> TransitionConfig {
+    TransitionConfig::fade().with_duration(duration)
+}
+
+fn builder() -> RouterBuilder<(), DefaultHistory> {
+    let mut builder = EguiRouter::<()>::builder();
+    for path in ["/", "/a", "/b", "/c"] {
+        builder = builder.route(path, || {
+            |ui: &mut Ui, (): &mut ()| {
+                ui.label("Page");
+            }
+        });
+    }
+    builder
+        .default_path("/")
+        .forward_transition(transition(1.0))
+        .backward_transition(transition(2.0))
+        .replace_transition(transition(4.0))
+}
+
+fn settled(builder: RouterBuilder<(), DefaultHistory>) -> TestRouter<()> {
+    let mut router = TestRouter::new(builder, ()).with_frame_time(FRAME_TIME);
+    router.settle();
+    router
+}
+
+/// Assert that the running transition has the given duration
+#[track_caller]
+fn assert_duration(router: &mut TestRouter<()>, duration: f32) {
+    router.step();
+    let progress = FRAME_TIME / duration;
+    router.assert_transition_progress(progress - 0.01..=progress + 0.01);
+    router.settle();
+}
+
+#[test]
+fn falls_back_to_forward_backward_and_replace() {
+    let mut router = settled(builder());
+
+    router.navigate("/a").unwrap();
+    assert_duration(&mut router, 1.0);
+    router.back().unwrap();
+    assert_duration(&mut router, 2.0);
+    router.replace("/b").unwrap();
+    assert_duration(&mut router, 4.0);
+}
+
+#[test]
+fn pair_rule_then_enter_then_exit() {
+    let mut router = settled(
+        builder()
+            .exit_transition("/", transition(0.5))
+            .enter_transition("/b", transition(8.0))
+            .transition_rule(&["/"], &["/b"], transition(16.0)),
+    );
+
+    router.navigate("/b").unwrap();
+    assert_duration(&mut router, 16.0);
+    router.back().unwrap();
+    assert_duration(&mut router, 16.0);
+
+    router.navigate("/a").unwrap();
+    assert_duration(&mut router, 0.5);
+    router.navigate("/b").unwrap();
+    assert_duration(&mut router, 8.0);
+    router.back().unwrap();
+    assert_duration(&mut router, 8.0);
+
+    // Neither the rule nor the exit of "/" match, so the defaults are used
+    router.navigate("/c").unwrap();
+    assert_duration(&mut router, 1.0);
+    router.back().unwrap();
+    assert_duration(&mut router, 2.0);
+}
+
+#[test]
+fn replace_uses_the_rules() {
+    let mut router = settled(builder().transition_rule(&["*"], &["/c"], transition(0.5)));
+
+    router.replace("/c").unwrap();
+    assert_duration(&mut router, 0.5);
+    router.replace("/a").unwrap();
+    assert_duration(&mut router, 4.0);
+}
+
+#[test]
+fn explicit_transition_wins_over_rules() {
+    let mut router = settled(builder().enter_transition("/a", transition(8.0)));
+
+    router
+        .router_mut()
+        .navigate_transition(&mut (), "/a", transition(0.5))
+        .unwrap();
+    assert_duration(&mut router, 0.5);
+}